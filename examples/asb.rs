@@ -0,0 +1,24 @@
+use escpos::printer::{AsbListener, Printer};
+use escpos::utils::*;
+use escpos::{driver::*, errors::Result};
+
+fn main() -> Result<()> {
+    env_logger::init();
+
+    let driver = NetworkDriver::open("192.168.1.248", 9100, None)?;
+    // let driver = UsbDriver::open(0x0525, 0xa700, None)?;
+
+    Printer::new(driver.clone(), Protocol::default(), None)
+        .debug_mode(Some(DebugMode::Dec))
+        .automatic_status_back(AsbEvents::all())?
+        .print()?;
+
+    // The printer sends its status when enabling automatic status back, then on each change
+    AsbListener::new(driver).listen(|response| {
+        match response {
+            PrinterResponse::AutomaticStatus(status) => println!("Status: {status}"),
+            response => println!("Other response: {response:?}"),
+        }
+        true
+    })
+}
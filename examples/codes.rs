@@ -51,7 +51,7 @@ fn main() -> Result<()> {
         )?
         // MaxiCode
         .writeln("MaxiCode")?
-        .maxi_code_option("1245789658745", MaxiCodeMode::Mode2)?
+        .maxi_code_carrier_message(&MaxiCodeCarrierMessage::us("152382802", 1)?.secondary_message("1245789658745")?)?
         // DataMatrix
         .writeln("DataMatrix")?
         .data_matrix("test1245789658745")?
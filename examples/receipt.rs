@@ -1,4 +1,5 @@
 use escpos::printer::Printer;
+use escpos::printer_options::PrinterOptions;
 use escpos::utils::*;
 use escpos::{driver::*, errors::Result};
 
@@ -30,7 +31,14 @@ fn main() -> Result<()> {
 
     // let driver = NetworkDriver::open("192.168.1.248", 9100, None)?;
     let driver = ConsoleDriver::open(true);
-    let mut printer = Printer::new(driver, Protocol::default(), None);
+
+    // Column format images (`ESC *`) are also supported by printers without raster image support
+    #[allow(unused_mut)]
+    let mut options = PrinterOptions::default();
+    #[cfg(feature = "graphics")]
+    options.bit_image_mode(BitImageMode::Column24DotDoubleDensity);
+
+    let mut printer = Printer::new(driver, Protocol::default(), Some(options));
     printer.init()?.justify(JustifyMode::CENTER)?;
 
     // Logo
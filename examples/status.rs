@@ -12,33 +12,19 @@ fn main() -> Result<()> {
     // let driver = UsbDriver::open(0x0525, 0xa700, None)?;
     // let driver = NativeUsbDriver::open(0x0525, 0xa700)?;
 
-    loop {
-        Printer::new(driver.clone(), Protocol::default(), None)
-            .debug_mode(Some(DebugMode::Dec))
-            .real_time_status(RealTimeStatusRequest::Printer)?
-            .real_time_status(RealTimeStatusRequest::RollPaperSensor)?
-            .send_status()?;
+    let mut printer = Printer::new(driver, Protocol::default(), None);
+    printer.debug_mode(Some(DebugMode::Dec));
+
+    // Printer identity, to select the options matching the model
+    println!("{}", printer.identify()?);
 
+    loop {
         // From Epson documentation: if this command must be transmitted continuously,
         // it is possible to transmit up to 4 commands at once.
-        let mut buf = [0; 2];
-        driver.read(&mut buf)?;
-
-        // Online/Offline status
-        let status = RealTimeStatusResponse::parse(RealTimeStatusRequest::Printer, buf[0])?;
-        println!(
-            "Printer online: {}",
-            status.get(&RealTimeStatusResponse::Online).unwrap_or(&false)
-        );
-
-        // Roll paper near-end sensor status
-        let status = RealTimeStatusResponse::parse(RealTimeStatusRequest::RollPaperSensor, buf[1])?;
-        println!(
-            "Roll paper near-end sensor => paper adequate:  {}",
-            status
-                .get(&RealTimeStatusResponse::RollPaperNearEndSensorPaperAdequate)
-                .unwrap_or(&false)
-        );
+        let state = printer.query_status(&[RealTimeStatusRequest::Printer, RealTimeStatusRequest::RollPaperSensor])?;
+        println!("{state}");
+        println!("Printer online: {}", state.is_online());
+        println!("Roll paper adequate: {}", state.is_paper_adequate());
 
         sleep(Duration::from_secs(10));
     }
@@ -0,0 +1,318 @@
+//! Automatic status back (ASB) and printer responses
+//!
+//! [Epson Documentation](https://download4.epson.biz/sec_pubs/pos/reference_en/escpos/gs_a.html)
+
+use crate::errors::PrinterError;
+use std::fmt;
+
+/// Number of bytes of an automatic status back packet
+const ASB_PACKET_LENGTH: usize = 4;
+/// Maximum length of a block response
+const MAX_BLOCK_RESPONSE_LENGTH: usize = 1024;
+
+/// Automatic status back events
+///
+/// The printer sends its status automatically when an enabled status changes.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct AsbEvents {
+    /// Drawer kick-out connector pin 3
+    pub drawer: bool,
+    /// Online or offline
+    pub online: bool,
+    /// Errors
+    pub error: bool,
+    /// Roll paper sensors
+    pub paper_sensor: bool,
+    /// Panel switch (paper feed button)
+    pub panel_switch: bool,
+}
+
+impl AsbEvents {
+    /// Enable all events
+    pub fn all() -> Self {
+        Self {
+            drawer: true,
+            online: true,
+            error: true,
+            paper_sensor: true,
+            panel_switch: true,
+        }
+    }
+}
+
+impl From<AsbEvents> for u8 {
+    fn from(value: AsbEvents) -> Self {
+        u8::from(value.drawer)
+            | u8::from(value.online) << 1
+            | u8::from(value.error) << 2
+            | u8::from(value.paper_sensor) << 3
+            | u8::from(value.panel_switch) << 6
+    }
+}
+
+/// Automatic status back packet (4 bytes)
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct AsbStatus {
+    pub drawer_kick_out_connector_pin3_low: bool,
+    pub online: bool,
+    pub cover_open: bool,
+    pub paper_fed_by_paper_feed_button: bool,
+    pub recoverable_error: bool,
+    pub autocutter_error: bool,
+    pub unrecoverable_error: bool,
+    pub auto_recoverable_error: bool,
+    /// Roll paper near-end sensor: paper adequate
+    pub paper_adequate: bool,
+    /// Roll paper end sensor: paper present
+    pub paper_present: bool,
+    pub panel_switch_pressed: bool,
+}
+
+impl AsbStatus {
+    /// Check if the byte starts an automatic status back packet, i.e. 0xx1xx00b
+    pub fn is_first_byte(byte: u8) -> bool {
+        byte & 0b1001_0011 == 0b0001_0000
+    }
+
+    /// Parse an automatic status back packet
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let status = AsbStatus::parse(&[0b0001_0100, 0b0000_1000, 0b0000_0011, 0])?;
+    ///
+    /// assert!(status.online);
+    /// assert!(status.autocutter_error);
+    /// assert!(!status.paper_adequate);
+    /// assert!(AsbStatus::parse(&[0b0001_0110, 0, 0, 0]).is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn parse(packet: &[u8; ASB_PACKET_LENGTH]) -> Result<Self, PrinterError> {
+        if !Self::is_first_byte(packet[0]) || packet[1..].iter().any(|byte| byte & 0b1001_0000 != 0) {
+            let packet = packet.map(|byte| format!("{byte:08b}")).join(" ");
+            return Err(PrinterError::InvalidResponse(format!(
+                "invalid automatic status back packet: {packet} (0xx1xx00 0xx0xxxx 0xx0xxxx 0xx0xxxx expected)"
+            )));
+        }
+
+        let bit = |byte: usize, bit: u8| packet[byte] & (1 << bit) != 0;
+        Ok(Self {
+            drawer_kick_out_connector_pin3_low: !bit(0, 2),
+            online: !bit(0, 3),
+            cover_open: bit(0, 5),
+            paper_fed_by_paper_feed_button: bit(0, 6),
+            recoverable_error: bit(1, 2),
+            autocutter_error: bit(1, 3),
+            unrecoverable_error: bit(1, 5),
+            auto_recoverable_error: bit(1, 6),
+            paper_adequate: !bit(2, 0) && !bit(2, 1),
+            paper_present: !bit(2, 2) && !bit(2, 3),
+            panel_switch_pressed: bit(3, 6),
+        })
+    }
+
+    /// Check if an error occurred
+    pub fn has_error(&self) -> bool {
+        self.recoverable_error || self.autocutter_error || self.unrecoverable_error || self.auto_recoverable_error
+    }
+}
+
+impl fmt::Display for AsbStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", if self.online { "online" } else { "offline" })?;
+        let flags = [
+            (
+                self.drawer_kick_out_connector_pin3_low,
+                "drawer kick-out connector pin 3 low",
+            ),
+            (self.cover_open, "cover open"),
+            (
+                self.paper_fed_by_paper_feed_button,
+                "paper fed by the paper feed button",
+            ),
+            (self.recoverable_error, "recoverable error"),
+            (self.autocutter_error, "autocutter error"),
+            (self.unrecoverable_error, "unrecoverable error"),
+            (self.auto_recoverable_error, "auto-recoverable error"),
+            (!self.paper_present, "paper end"),
+            (self.paper_present && !self.paper_adequate, "paper near end"),
+            (self.panel_switch_pressed, "panel switch pressed"),
+        ];
+        for (_, description) in flags.iter().filter(|(set, _)| *set) {
+            write!(f, ", {description}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Data sent by the printer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrinterResponse {
+    /// Automatic status back packet (`GS a`)
+    AutomaticStatus(AsbStatus),
+    /// Real-time status byte (`DLE EOT`, 0xx1xx10b)
+    RealTimeStatus(u8),
+    /// Other one-byte response (`GS r`, `GS I`, etc.)
+    Byte(u8),
+    /// Block response starting with a `_` or `7` header and ending with NUL (`GS I`, `GS ( H`, etc.)
+    Block(Vec<u8>),
+}
+
+/// Parser separating the printer responses of a byte stream
+#[derive(Debug, Default, Clone)]
+pub struct PrinterResponseParser {
+    buffer: Vec<u8>,
+}
+
+impl PrinterResponseParser {
+    /// Create a new `PrinterResponseParser`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a received byte, returns the response it completes
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let mut parser = PrinterResponseParser::new();
+    /// let responses = [0b0001_0010, 0b0001_0000, 0, 0, 0, b'_', b'A', 0]
+    ///     .into_iter()
+    ///     .filter_map(|byte| parser.push(byte).transpose())
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    ///
+    /// assert_eq!(responses.len(), 3);
+    /// assert_eq!(responses[0], PrinterResponse::RealTimeStatus(0b0001_0010));
+    /// assert!(matches!(responses[1], PrinterResponse::AutomaticStatus(_)));
+    /// assert_eq!(responses[2], PrinterResponse::Block(vec![b'_', b'A', 0]));
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn push(&mut self, byte: u8) -> Result<Option<PrinterResponse>, PrinterError> {
+        let Some(&first) = self.buffer.first() else {
+            return Ok(match byte {
+                byte if AsbStatus::is_first_byte(byte) || Self::is_block_header(byte) => {
+                    self.buffer.push(byte);
+                    None
+                }
+                byte if byte & 0b1001_0011 == 0b0001_0010 => Some(PrinterResponse::RealTimeStatus(byte)),
+                byte => Some(PrinterResponse::Byte(byte)),
+            });
+        };
+
+        self.buffer.push(byte);
+        if Self::is_block_header(first) {
+            if byte == 0 {
+                return Ok(Some(PrinterResponse::Block(std::mem::take(&mut self.buffer))));
+            }
+            if self.buffer.len() >= MAX_BLOCK_RESPONSE_LENGTH {
+                self.buffer.clear();
+                return Err(PrinterError::InvalidResponse("block response is too long".to_owned()));
+            }
+        } else if self.buffer.len() == ASB_PACKET_LENGTH {
+            let packet = std::mem::take(&mut self.buffer);
+            return Ok(Some(PrinterResponse::AutomaticStatus(AsbStatus::parse(&[
+                packet[0], packet[1], packet[2], packet[3],
+            ])?)));
+        }
+
+        Ok(None)
+    }
+
+    /// Check if a response is being received
+    pub fn is_pending(&self) -> bool {
+        !self.buffer.is_empty()
+    }
+
+    /// Check if the byte starts a block response
+    fn is_block_header(byte: u8) -> bool {
+        byte == b'_' || byte == b'7'
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asb_events() {
+        assert_eq!(u8::from(AsbEvents::default()), 0);
+        assert_eq!(u8::from(AsbEvents::all()), 0b0100_1111);
+        let events = AsbEvents {
+            online: true,
+            paper_sensor: true,
+            ..Default::default()
+        };
+        assert_eq!(u8::from(events), 0b0000_1010);
+    }
+
+    #[test]
+    fn test_asb_status_parse() {
+        let status = AsbStatus::parse(&[0b0011_1000, 0b0110_0100, 0b0000_1100, 0b0100_0000]).unwrap();
+        assert_eq!(
+            status,
+            AsbStatus {
+                drawer_kick_out_connector_pin3_low: true,
+                online: false,
+                cover_open: true,
+                paper_fed_by_paper_feed_button: false,
+                recoverable_error: true,
+                autocutter_error: false,
+                unrecoverable_error: true,
+                auto_recoverable_error: true,
+                paper_adequate: true,
+                paper_present: false,
+                panel_switch_pressed: true,
+            }
+        );
+        assert!(status.has_error());
+        assert_eq!(
+            status.to_string(),
+            "offline, drawer kick-out connector pin 3 low, cover open, recoverable error, unrecoverable error, \
+             auto-recoverable error, paper end, panel switch pressed"
+        );
+
+        let status = AsbStatus::parse(&[0b0001_0100, 0, 0, 0]).unwrap();
+        assert!(!status.has_error());
+        assert_eq!(status.to_string(), "online");
+
+        assert!(AsbStatus::parse(&[0b0001_0100, 0b0001_0000, 0, 0]).is_err());
+        assert!(AsbStatus::parse(&[0b0001_0100, 0, 0, 0b1000_0000]).is_err());
+    }
+
+    #[test]
+    fn test_printer_response_parser() {
+        let mut parser = PrinterResponseParser::new();
+        assert_eq!(
+            parser.push(0b0001_0110).unwrap(),
+            Some(PrinterResponse::RealTimeStatus(0b0001_0110))
+        );
+        assert_eq!(
+            parser.push(0b0000_0011).unwrap(),
+            Some(PrinterResponse::Byte(0b0000_0011))
+        );
+
+        assert_eq!(parser.push(0b0001_0100).unwrap(), None);
+        assert!(parser.is_pending());
+        assert_eq!(parser.push(0).unwrap(), None);
+        assert_eq!(parser.push(0b0000_0011).unwrap(), None);
+        let response = parser.push(0).unwrap().unwrap();
+        assert!(
+            matches!(response, PrinterResponse::AutomaticStatus(status) if status.online && !status.paper_adequate)
+        );
+        assert!(!parser.is_pending());
+
+        assert_eq!(parser.push(b'_').unwrap(), None);
+        assert_eq!(parser.push(b'T').unwrap(), None);
+        assert_eq!(parser.push(b'M').unwrap(), None);
+        assert_eq!(
+            parser.push(0).unwrap(),
+            Some(PrinterResponse::Block(vec![b'_', b'T', b'M', 0]))
+        );
+
+        assert_eq!(parser.push(0b0001_0100).unwrap(), None);
+        assert!(parser.push(0b1000_0000).is_ok());
+        assert!(parser.push(0).is_ok());
+        assert!(parser.push(0).is_err());
+        assert!(!parser.is_pending());
+    }
+}
@@ -2,8 +2,10 @@
 
 #![cfg(feature = "graphics")]
 
+use super::dithering::Dithering;
+use super::types::JustifyMode;
 use crate::errors::{PrinterError, Result};
-use image::{DynamicImage, GenericImage, GenericImageView, Rgba};
+use image::{imageops, imageops::FilterType, DynamicImage, GenericImage, GenericImageView, GrayImage, Luma, Rgba};
 use std::fmt;
 
 /// BitImage size
@@ -38,7 +40,207 @@ impl From<&BitImageSize> for u8 {
     }
 }
 
+/// Default raster band height in dots
+const DEFAULT_BAND_HEIGHT: u16 = 256;
+
+/// Image resize filter
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ResizeFilter {
+    /// Nearest neighbour (fast, jagged edges)
+    Nearest,
+    /// Linear filter
+    #[default]
+    Triangle,
+    /// Cubic filter
+    CatmullRom,
+    /// Lanczos with a window of 3 (sharpest, slowest)
+    Lanczos3,
+}
+
+impl From<ResizeFilter> for FilterType {
+    fn from(filter: ResizeFilter) -> Self {
+        match filter {
+            ResizeFilter::Nearest => FilterType::Nearest,
+            ResizeFilter::Triangle => FilterType::Triangle,
+            ResizeFilter::CatmullRom => FilterType::CatmullRom,
+            ResizeFilter::Lanczos3 => FilterType::Lanczos3,
+        }
+    }
+}
+
+impl fmt::Display for ResizeFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResizeFilter::Nearest => write!(f, "Nearest"),
+            ResizeFilter::Triangle => write!(f, "Triangle"),
+            ResizeFilter::CatmullRom => write!(f, "Catmull-Rom"),
+            ResizeFilter::Lanczos3 => write!(f, "Lanczos3"),
+        }
+    }
+}
+
+/// Image rotation (clockwise)
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ImageRotation {
+    #[default]
+    None,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+impl ImageRotation {
+    /// Rotate an image
+    fn apply(&self, img: DynamicImage) -> DynamicImage {
+        match self {
+            ImageRotation::None => img,
+            ImageRotation::Rotate90 => img.rotate90(),
+            ImageRotation::Rotate180 => img.rotate180(),
+            ImageRotation::Rotate270 => img.rotate270(),
+        }
+    }
+}
+
+impl fmt::Display for ImageRotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageRotation::None => write!(f, "No rotation"),
+            ImageRotation::Rotate90 => write!(f, "Rotation 90°"),
+            ImageRotation::Rotate180 => write!(f, "Rotation 180°"),
+            ImageRotation::Rotate270 => write!(f, "Rotation 270°"),
+        }
+    }
+}
+
+/// Lightest gray level kept when trimming margins (lighter pixels are considered white)
+const TRIM_WHITE_LEVEL: u8 = 250;
+
+/// Remove the white and transparent margins around an image
+///
+/// Blank images are kept unchanged.
+fn trim(img: DynamicImage) -> DynamicImage {
+    let rgba = img.to_rgba8();
+    let is_blank = |x: u32, y: u32| {
+        let Rgba([r, g, b, a]) = *rgba.get_pixel(x, y);
+        a == 0 || (r >= TRIM_WHITE_LEVEL && g >= TRIM_WHITE_LEVEL && b >= TRIM_WHITE_LEVEL)
+    };
+
+    let (width, height) = rgba.dimensions();
+    let mut rows = (0..height).filter(|&y| (0..width).any(|x| !is_blank(x, y)));
+    let Some(top) = rows.next() else {
+        return img;
+    };
+    let bottom = rows.next_back().unwrap_or(top);
+
+    let mut columns = (0..width).filter(|&x| (top..=bottom).any(|y| !is_blank(x, y)));
+    let Some(left) = columns.next() else {
+        return img;
+    };
+    let right = columns.next_back().unwrap_or(left);
+
+    img.crop_imm(left, top, right - left + 1, bottom - top + 1)
+}
+
+/// Surround an image with white padding, then with a black border
+fn frame(img: GrayImage, padding: u32, border: u32) -> GrayImage {
+    if padding == 0 && border == 0 {
+        return img;
+    }
+
+    let offset = padding + border;
+    let (width, height) = (img.width() + 2 * offset, img.height() + 2 * offset);
+    let mut framed = GrayImage::from_fn(width, height, |x, y| {
+        let in_border = x < border || y < border || x >= width - border || y >= height - border;
+        Luma([if in_border { 0 } else { 255 }])
+    });
+    imageops::replace(&mut framed, &img, i64::from(offset), i64::from(offset));
+    framed
+}
+
+/// Place an image on a white background of `width` dots, with an alignment
+///
+/// Images wider than `width` are kept unchanged.
+fn align(img: GrayImage, alignment: JustifyMode, width: u32) -> GrayImage {
+    if img.width() >= width {
+        return img;
+    }
+
+    let x = match alignment {
+        JustifyMode::LEFT => 0,
+        JustifyMode::CENTER => (width - img.width()) / 2,
+        JustifyMode::RIGHT => width - img.width(),
+    };
+    let mut aligned = GrayImage::from_pixel(width, img.height(), Luma([255]));
+    imageops::replace(&mut aligned, &img, i64::from(x), 0);
+    aligned
+}
+
+/// Downscale an image to fit in max width and max height, keeping the aspect ratio
+///
+/// Images are never upscaled.
+pub(crate) fn downscale(
+    img: DynamicImage,
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+    filter: ResizeFilter,
+) -> DynamicImage {
+    let max_width = max_width.unwrap_or(u32::MAX);
+    let max_height = max_height.unwrap_or(u32::MAX);
+
+    if img.width() > max_width || img.height() > max_height {
+        img.resize(max_width, max_height, filter.into())
+    } else {
+        img
+    }
+}
+
+/// BitImage mode
+///
+/// Column formats (`ESC *`) are printed in bands of 8 or 24 dots,
+/// for printers without raster image support.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum BitImageMode {
+    /// Raster format (`GS v 0`)
+    #[default]
+    Raster,
+    /// Column format, 8-dot single-density (`ESC * 0`)
+    Column8DotSingleDensity,
+    /// Column format, 8-dot double-density (`ESC * 1`)
+    Column8DotDoubleDensity,
+    /// Column format, 24-dot single-density (`ESC * 32`)
+    Column24DotSingleDensity,
+    /// Column format, 24-dot double-density (`ESC * 33`)
+    Column24DotDoubleDensity,
+}
+
+impl BitImageMode {
+    /// Get `ESC *` mode (m) and band height in dots for column formats
+    pub(crate) fn column_format(&self) -> Option<(u8, u8)> {
+        match self {
+            BitImageMode::Raster => None,
+            BitImageMode::Column8DotSingleDensity => Some((0, 8)),
+            BitImageMode::Column8DotDoubleDensity => Some((1, 8)),
+            BitImageMode::Column24DotSingleDensity => Some((32, 24)),
+            BitImageMode::Column24DotDoubleDensity => Some((33, 24)),
+        }
+    }
+}
+
+impl fmt::Display for BitImageMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitImageMode::Raster => write!(f, "Raster"),
+            BitImageMode::Column8DotSingleDensity => write!(f, "Column 8-dot single-density"),
+            BitImageMode::Column8DotDoubleDensity => write!(f, "Column 8-dot double-density"),
+            BitImageMode::Column24DotSingleDensity => write!(f, "Column 24-dot single-density"),
+            BitImageMode::Column24DotDoubleDensity => write!(f, "Column 24-dot double-density"),
+        }
+    }
+}
+
 /// Bit image option
+///
+/// By default, images wider than the printer paper width are downscaled to it with a linear filter.
 #[derive(Debug)]
 pub struct BitImageOption {
     /// Image max width
@@ -47,44 +249,342 @@ pub struct BitImageOption {
     max_height: Option<u32>,
     /// Image size
     size: BitImageSize,
+    /// Image mode (printer options mode if not set)
+    mode: Option<BitImageMode>,
+    /// Dithering
+    dithering: Dithering,
+    /// Resize filter
+    resize_filter: ResizeFilter,
+    /// Raster band height in dots (whole image if not set)
+    band_height: Option<u16>,
+    /// Limit the width to the printer paper width
+    fit_to_paper_width: bool,
+    /// Printer paper width in dots
+    paper_width: Option<u32>,
+    /// Rotation
+    rotation: ImageRotation,
+    /// Mirror the image horizontally
+    mirror: bool,
+    /// Invert black and white
+    invert: bool,
+    /// Remove white and transparent margins
+    trim: bool,
+    /// White padding around the image in dots
+    padding: u32,
+    /// Black border width around the padding in dots
+    border: u32,
+    /// Horizontal alignment within the paper width
+    alignment: Option<JustifyMode>,
 }
 
 impl Default for BitImageOption {
     fn default() -> Self {
         Self {
-            max_width: Some(512),
+            max_width: None,
             max_height: Some(512),
             size: BitImageSize::Normal,
+            mode: None,
+            dithering: Dithering::default(),
+            resize_filter: ResizeFilter::default(),
+            band_height: Some(DEFAULT_BAND_HEIGHT),
+            fit_to_paper_width: true,
+            paper_width: None,
+            rotation: ImageRotation::default(),
+            mirror: false,
+            invert: false,
+            trim: false,
+            padding: 0,
+            border: 0,
+            alignment: None,
         }
     }
 }
 
 impl BitImageOption {
     /// Create new `BitImageOption`
+    ///
+    /// Images larger than `max_width` or `max_height` are downscaled, keeping the aspect ratio.
+    /// The width is also limited to the paper width when the image is printed with a [`Printer`](crate::printer::Printer).
     pub fn new(max_width: Option<u32>, max_height: Option<u32>, size: BitImageSize) -> Result<Self> {
-        if let Some(max_width) = max_width {
-            if max_width % 8 != 0 {
-                return Err(PrinterError::Input(
-                    "bit image max width must be a multiple of 8".to_owned(),
-                ));
-            }
-        }
-        if let Some(max_height) = max_height {
-            if max_height % 8 != 0 {
-                return Err(PrinterError::Input(
-                    "bit image max height must be a multiple of 8".to_owned(),
-                ));
-            }
+        if max_width == Some(0) || max_height == Some(0) {
+            return Err(PrinterError::Input(
+                "bit image max width and max height must be greater than 0".to_owned(),
+            ));
         }
 
         Ok(Self {
             max_width,
             max_height,
             size,
+            mode: None,
+            dithering: Dithering::default(),
+            resize_filter: ResizeFilter::default(),
+            band_height: Some(DEFAULT_BAND_HEIGHT),
+            fit_to_paper_width: true,
+            paper_width: None,
+            rotation: ImageRotation::default(),
+            mirror: false,
+            invert: false,
+            trim: false,
+            padding: 0,
+            border: 0,
+            alignment: None,
         })
     }
+
+    /// Set the [mode](BitImageMode), overriding the printer options one
+    ///
+    /// The size is ignored by column formats.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = BitImageOption::default().mode(BitImageMode::Column24DotDoubleDensity);
+    ///
+    /// assert_eq!(option.get_mode(), Some(BitImageMode::Column24DotDoubleDensity));
+    /// ```
+    pub fn mode(mut self, mode: BitImageMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Get the [mode](BitImageMode)
+    pub fn get_mode(&self) -> Option<BitImageMode> {
+        self.mode
+    }
+
+    /// Set the [dithering](Dithering) (threshold at 128 by default)
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = BitImageOption::default().dithering(Dithering::new(DitheringAlgorithm::Atkinson));
+    ///
+    /// assert_eq!(option.get_dithering().algorithm(), DitheringAlgorithm::Atkinson);
+    /// ```
+    pub fn dithering(mut self, dithering: Dithering) -> Self {
+        self.dithering = dithering;
+        self
+    }
+
+    /// Get the [dithering](Dithering)
+    pub fn get_dithering(&self) -> Dithering {
+        self.dithering
+    }
+
+    /// Set the [resize filter](ResizeFilter) used to downscale the image (Triangle by default)
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = BitImageOption::default().resize_filter(ResizeFilter::Lanczos3);
+    ///
+    /// assert_eq!(option.get_resize_filter(), ResizeFilter::Lanczos3);
+    /// ```
+    pub fn resize_filter(mut self, filter: ResizeFilter) -> Self {
+        self.resize_filter = filter;
+        self
+    }
+
+    /// Get the [resize filter](ResizeFilter)
+    pub fn get_resize_filter(&self) -> ResizeFilter {
+        self.resize_filter
+    }
+
+    /// Set the raster band height in dots (256 by default)
+    ///
+    /// Tall raster images are split in bands, one command each, to avoid overflowing the printer receive buffer.
+    /// `None` sends the whole image in one command.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = BitImageOption::default().band_height(Some(128))?;
+    ///
+    /// assert_eq!(option.get_band_height(), Some(128));
+    /// assert!(BitImageOption::default().band_height(Some(0)).is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn band_height(mut self, band_height: Option<u16>) -> Result<Self> {
+        if band_height == Some(0) {
+            return Err(PrinterError::Input(
+                "bit image band height must be greater than 0".to_owned(),
+            ));
+        }
+        self.band_height = band_height;
+        Ok(self)
+    }
+
+    /// Get the raster band height in dots
+    pub fn get_band_height(&self) -> Option<u16> {
+        self.band_height
+    }
+
+    /// Downscale the image to the paper width of the printer (enabled by default)
+    ///
+    /// The paper width is set with [`PrinterOptions::dots_per_line`](crate::printer_options::PrinterOptions::dots_per_line),
+    /// the smallest of the paper width and `max_width` is used. Without a printer, only `max_width` applies.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// assert!(BitImageOption::default().get_fit_to_paper_width());
+    ///
+    /// let option = BitImageOption::new(Some(1024), None, BitImageSize::Normal)?.fit_to_paper_width(false);
+    /// assert!(!option.get_fit_to_paper_width());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn fit_to_paper_width(mut self, fit: bool) -> Self {
+        self.fit_to_paper_width = fit;
+        self
+    }
+
+    /// Is the image downscaled to the paper width?
+    pub fn get_fit_to_paper_width(&self) -> bool {
+        self.fit_to_paper_width
+    }
+
+    /// Set the [rotation](ImageRotation), applied before resizing
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = BitImageOption::default().rotate(ImageRotation::Rotate90);
+    ///
+    /// assert_eq!(option.get_rotation(), ImageRotation::Rotate90);
+    /// ```
+    pub fn rotate(mut self, rotation: ImageRotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Get the [rotation](ImageRotation)
+    pub fn get_rotation(&self) -> ImageRotation {
+        self.rotation
+    }
+
+    /// Mirror the image horizontally (after the rotation)
+    pub fn mirror(mut self) -> Self {
+        self.mirror = true;
+        self
+    }
+
+    /// Is the image mirrored?
+    pub fn get_mirror(&self) -> bool {
+        self.mirror
+    }
+
+    /// Invert black and white dots (the padding and the border are not inverted)
+    pub fn invert(mut self) -> Self {
+        self.invert = true;
+        self
+    }
+
+    /// Is the image inverted?
+    pub fn get_invert(&self) -> bool {
+        self.invert
+    }
+
+    /// Remove the white and transparent margins around the image before resizing
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = BitImageOption::default().trim();
+    ///
+    /// assert!(option.get_trim());
+    /// ```
+    pub fn trim(mut self) -> Self {
+        self.trim = true;
+        self
+    }
+
+    /// Are the margins removed?
+    pub fn get_trim(&self) -> bool {
+        self.trim
+    }
+
+    /// Set the white padding around the image in dots
+    ///
+    /// The padding and the border are included in `max_width` and `max_height`.
+    pub fn padding(mut self, dots: u32) -> Self {
+        self.padding = dots;
+        self
+    }
+
+    /// Get the white padding in dots
+    pub fn get_padding(&self) -> u32 {
+        self.padding
+    }
+
+    /// Set the black border width around the padding in dots
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = BitImageOption::default().padding(8).border(2);
+    ///
+    /// assert_eq!((option.get_padding(), option.get_border()), (8, 2));
+    /// ```
+    pub fn border(mut self, dots: u32) -> Self {
+        self.border = dots;
+        self
+    }
+
+    /// Get the black border width in dots
+    pub fn get_border(&self) -> u32 {
+        self.border
+    }
+
+    /// Align the image horizontally within the paper width
+    ///
+    /// The image is placed on a white background as wide as the paper
+    /// ([`PrinterOptions::dots_per_line`](crate::printer_options::PrinterOptions::dots_per_line)),
+    /// or as `max_width` when the image is not printed with a [`Printer`](crate::printer::Printer).
+    /// Unlike `justify`, it works with every printer.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = BitImageOption::default().alignment(JustifyMode::CENTER);
+    ///
+    /// assert_eq!(option.get_alignment(), Some(JustifyMode::CENTER));
+    /// ```
+    pub fn alignment(mut self, alignment: JustifyMode) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    /// Get the horizontal alignment
+    pub fn get_alignment(&self) -> Option<JustifyMode> {
+        self.alignment
+    }
+
+    /// Set the paper width and limit the max width to it if needed
+    pub(crate) fn paper_width(mut self, dots: u16) -> Self {
+        let dots = u32::from(dots);
+        self.paper_width = Some(dots);
+        if self.fit_to_paper_width {
+            self.max_width = Some(self.max_width.map_or(dots, |max_width| max_width.min(dots)));
+        }
+        self
+    }
 }
 
+/// Bit image
+///
+/// The image is downscaled, converted to black and white and ready to be printed,
+/// its raster and column data can be inspected before printing.
+///
+/// ```
+/// use escpos::utils::*;
+///
+/// let bit_image = BitImage::new("./resources/images/small.jpg", BitImageOption::default())?;
+///
+/// assert_eq!(bit_image.width()?, 16);
+/// assert_eq!(bit_image.raster_data()?.len(), 2 * 16);
+/// # Ok::<(), escpos::errors::PrinterError>(())
+/// ```
 #[derive(Debug)]
 pub struct BitImage {
     path: String,
@@ -96,52 +596,100 @@ impl BitImage {
     /// Create a new image
     pub fn new(path: &str, option: BitImageOption) -> Result<Self> {
         let img = image::open(path)?;
-        Self::from_dynamic_image(img, option, path)
+        Self::from_image(img, option, path)
     }
 
     /// Create a new image from bytes
     pub fn from_bytes(bytes: &[u8], option: BitImageOption) -> Result<Self> {
         let img = image::load_from_memory(bytes)?;
-        Self::from_dynamic_image(img, option, "")
+        Self::from_image(img, option, "")
     }
 
     /// Create a new image from `DynamicImage`
-    fn from_dynamic_image(img: DynamicImage, option: BitImageOption, path: &str) -> Result<Self> {
-        // Resize image with max width and max height constraints and convert to grayscale
-        let mut img = match (option.max_width, option.max_height) {
-            (Some(max_width), None) => {
-                if img.width() > max_width {
-                    img.resize(max_width, max_width, image::imageops::Nearest)
-                } else {
-                    img
-                }
-            }
-            (None, Some(max_height)) => {
-                if img.height() > max_height {
-                    img.resize(max_height, max_height, image::imageops::Nearest)
-                } else {
-                    img
-                }
-            }
-            (Some(max_width), Some(max_height)) => {
-                if img.width() > max_width || img.height() > max_height {
-                    img.resize(max_width, max_height, image::imageops::Nearest)
-                } else {
-                    img
-                }
-            }
-            _ => img,
-        };
+    pub fn from_dynamic_image(img: DynamicImage, option: BitImageOption) -> Result<Self> {
+        Self::from_image(img, option, "")
+    }
+
+    /// Create a new image from `GrayImage`
+    pub fn from_gray_image(img: GrayImage, option: BitImageOption) -> Result<Self> {
+        Self::from_image(DynamicImage::ImageLuma8(img), option, "")
+    }
+
+    /// Create a new image from a 1-bit packed buffer
+    ///
+    /// Each line is `width / 8` bytes (rounded up), the most significant bit is the left dot
+    /// and a set bit is a black dot.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// // 10 x 2 dots: 2 bytes per line
+    /// let data = [0b1000_0000, 0b0100_0000, 0xFF, 0b1100_0000];
+    /// let bit_image = BitImage::from_packed_bits(&data, 10, 2, BitImageOption::default())?;
+    ///
+    /// assert_eq!(bit_image.raster_data()?, data);
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn from_packed_bits(data: &[u8], width: u32, height: u32, option: BitImageOption) -> Result<Self> {
+        let width_bytes = width.div_ceil(8) as usize;
+        if width == 0 || height == 0 || data.len() != width_bytes * height as usize {
+            return Err(PrinterError::Input(format!(
+                "invalid packed bit image data length for {width} x {height} dots: {}",
+                data.len()
+            )));
+        }
+
+        let img = GrayImage::from_fn(width, height, |x, y| {
+            let byte = data[y as usize * width_bytes + x as usize / 8];
+            Luma([if byte & (0x80 >> (x % 8)) != 0 { 0 } else { 255 }])
+        });
+
+        Self::from_gray_image(img, option)
+    }
+
+    /// Create a new image from `DynamicImage` with its path
+    ///
+    /// The image is rotated, mirrored, trimmed, downscaled, converted to black and white,
+    /// inverted, framed and aligned, in this order.
+    fn from_image(img: DynamicImage, option: BitImageOption, path: &str) -> Result<Self> {
+        let mut img = option.rotation.apply(img);
+        if option.mirror {
+            img = img.fliph();
+        }
+        if option.trim {
+            img = trim(img);
+        }
+
+        // Downscale image with max width and max height constraints, keeping room for the frame
+        let frame_size = 2 * (option.padding + option.border);
+        let max_size = |max: Option<u32>| max.map(|max| max.saturating_sub(frame_size).max(1));
+        let mut img = downscale(
+            img,
+            max_size(option.max_width),
+            max_size(option.max_height),
+            option.resize_filter,
+        );
 
         // Remove alpha canal
         Self::remove_alpha(&mut img);
 
-        // Make gray scale
-        img = img.grayscale();
+        // Make gray scale and convert to black and white
+        let mut gray = option.dithering.apply(&img.to_luma8());
+        if option.invert {
+            imageops::invert(&mut gray);
+        }
+        gray = frame(gray, option.padding, option.border);
+
+        if let Some(alignment) = option.alignment {
+            let width = option.paper_width.or(option.max_width).ok_or_else(|| {
+                PrinterError::Input("bit image alignment requires a paper width or a max width".to_owned())
+            })?;
+            gray = align(gray, alignment, width);
+        }
 
         Ok(Self {
             path: path.to_string(),
-            image: img,
+            image: DynamicImage::ImageLuma8(gray),
             option,
         })
     }
@@ -165,13 +713,13 @@ impl BitImage {
         }
     }
 
-    /// Get image width
-    fn width(&self) -> Result<u16> {
+    /// Get image width in dots
+    pub fn width(&self) -> Result<u16> {
         Ok(u16::try_from(self.image.width())?)
     }
 
-    /// Get image height
-    fn height(&self) -> Result<u16> {
+    /// Get image height in dots
+    pub fn height(&self) -> Result<u16> {
         Ok(u16::try_from(self.image.height())?)
     }
 
@@ -201,6 +749,11 @@ impl BitImage {
         &self.option.size
     }
 
+    /// Get mode (printer options mode if not set)
+    pub fn mode(&self) -> Option<BitImageMode> {
+        self.option.mode
+    }
+
     /// Get image width in bytes
     pub fn with_bytes_u8(&self) -> Result<Vec<u8>> {
         let width = self.width_bytes()?;
@@ -214,13 +767,12 @@ impl BitImage {
 
     /// Get image height
     pub fn height_u8(&self) -> Result<Vec<u8>> {
-        let height = self.height()?;
-        let yh = height / 256;
-        let yl = height
-            .checked_add_signed(-256 * i16::try_from(yh)?)
-            .ok_or(PrinterError::Input("Bit image height invalid data".to_owned()))?;
+        Ok(Self::dots_u8(self.height()?))
+    }
 
-        Ok(vec![u8::try_from(yl)?, u8::try_from(yh)?])
+    /// Get (nL, nH) of a number of dots
+    fn dots_u8(dots: u16) -> Vec<u8> {
+        dots.to_le_bytes().to_vec()
     }
 
     /// Is the pixel black?
@@ -229,26 +781,21 @@ impl BitImage {
     }
 
     /// Get image raster data
+    ///
+    /// Each line is padded with white dots to a multiple of 8 dots.
     pub fn raster_data(&self) -> Result<Vec<u8>> {
         let width = self.width()?;
         let height = self.height()?;
-        let mut data = Vec::new();
+        let mut data = Vec::with_capacity(usize::from(self.width_bytes()?) * usize::from(height));
 
         for y in 0..height {
             for x in (0..width).step_by(8) {
                 let mut byte = 0;
 
-                // Processing 8 bits per byte
+                // Processing 8 bits per byte, the most significant bit is the left dot
                 for bit in 0..8 {
                     let x_offset = x + bit;
-
-                    // Breaking the loop if x_offset exceeds the width
-                    if x_offset >= width {
-                        break;
-                    }
-
-                    // Shift byte to the left, adding the pixel value at the end
-                    byte = (byte << 1) | u8::from(self.is_pixel_black(x_offset, y));
+                    byte = (byte << 1) | u8::from(x_offset < width && self.is_pixel_black(x_offset, y));
                 }
 
                 data.push(byte);
@@ -257,4 +804,377 @@ impl BitImage {
 
         Ok(data)
     }
+
+    /// Get image raster data, split in bands of the option band height
+    ///
+    /// Returns the (yL, yH) height and the raster data of each band.
+    pub fn raster_bands(&self) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let height = self.height()?;
+        let data = self.raster_data()?;
+        let band_height = self.option.band_height.unwrap_or(height).max(1);
+        let line_bytes = usize::from(self.width_bytes()?);
+
+        Ok((0..height)
+            .step_by(usize::from(band_height))
+            .map(|top| {
+                let rows = band_height.min(height - top);
+                let start = usize::from(top) * line_bytes;
+                let end = start + usize::from(rows) * line_bytes;
+                (Self::dots_u8(rows), data[start..end].to_vec())
+            })
+            .collect())
+    }
+
+    /// Get image column data, split in bands of `band_height` dots (8 or 24)
+    ///
+    /// Each column is `band_height / 8` bytes from top to bottom, the most significant bit is the top dot.
+    pub fn column_data(&self, band_height: u8) -> Result<Vec<Vec<u8>>> {
+        if band_height == 0 || !band_height.is_multiple_of(8) {
+            return Err(PrinterError::Input(format!(
+                "bit image band height must be a multiple of 8: {band_height}"
+            )));
+        }
+
+        // Rows are counted in `usize`, the rows of the last band can go past `u16::MAX`
+        let width = self.width()?;
+        let height = usize::from(self.height()?);
+        let band_height = usize::from(band_height);
+
+        Ok((0..height)
+            .step_by(band_height)
+            .map(|top| {
+                let mut band = Vec::with_capacity(usize::from(width) * (band_height / 8));
+                for x in 0..width {
+                    for y in (top..top + band_height).step_by(8) {
+                        let mut byte = 0;
+                        for y in y..y + 8 {
+                            let black =
+                                u16::try_from(y).is_ok_and(|y| usize::from(y) < height && self.is_pixel_black(x, y));
+                            byte = (byte << 1) | u8::from(black);
+                        }
+                        band.push(byte);
+                    }
+                }
+                band
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GrayImage, Luma};
+
+    #[test]
+    fn test_bit_image_option_new() {
+        assert!(BitImageOption::new(Some(100), Some(30), BitImageSize::Normal).is_ok());
+        assert!(BitImageOption::new(Some(0), None, BitImageSize::Normal).is_err());
+        assert!(BitImageOption::new(None, Some(0), BitImageSize::Normal).is_err());
+    }
+
+    #[test]
+    fn test_bit_image_option_paper_width() {
+        let option = BitImageOption::new(None, None, BitImageSize::Normal)
+            .unwrap()
+            .fit_to_paper_width(false)
+            .paper_width(384);
+        assert_eq!(option.max_width, None);
+
+        let option = BitImageOption::new(None, None, BitImageSize::Normal)
+            .unwrap()
+            .paper_width(384);
+        assert_eq!(option.max_width, Some(384));
+
+        let option = BitImageOption::new(Some(200), None, BitImageSize::Normal)
+            .unwrap()
+            .paper_width(384);
+        assert_eq!(option.max_width, Some(200));
+
+        // Default option: paper width and linear filter
+        let option = BitImageOption::default();
+        assert_eq!((option.max_width, option.resize_filter), (None, ResizeFilter::Triangle));
+        assert_eq!(option.paper_width(576).max_width, Some(576));
+    }
+
+    #[test]
+    fn test_downscale() {
+        let img = image::open("./resources/images/rust-logo-small.png").unwrap();
+        assert_eq!(
+            downscale(img.clone(), None, None, ResizeFilter::Nearest).dimensions(),
+            (200, 200)
+        );
+        assert_eq!(
+            downscale(img.clone(), Some(100), None, ResizeFilter::Lanczos3).dimensions(),
+            (100, 100)
+        );
+        assert_eq!(
+            downscale(img.clone(), None, Some(50), ResizeFilter::CatmullRom).dimensions(),
+            (50, 50)
+        );
+        assert_eq!(
+            downscale(img.clone(), Some(150), Some(30), ResizeFilter::Triangle).dimensions(),
+            (30, 30)
+        );
+        // Never upscaled
+        assert_eq!(
+            downscale(img.clone(), Some(400), Some(400), ResizeFilter::Lanczos3).dimensions(),
+            (200, 200)
+        );
+
+        let wide = DynamicImage::new_luma8(400, 100);
+        assert_eq!(
+            downscale(wide, Some(384), None, ResizeFilter::Nearest).dimensions(),
+            (384, 96)
+        );
+    }
+
+    #[test]
+    fn test_raster_data_padding() {
+        // 10 x 2 dots: first and last columns are black
+        let img = GrayImage::from_fn(10, 2, |x, _| Luma([if x == 0 || x == 9 { 0 } else { 255 }]));
+        let bit_image =
+            BitImage::from_gray_image(img, BitImageOption::new(Some(10), None, BitImageSize::Normal).unwrap()).unwrap();
+
+        assert_eq!(bit_image.width_bytes().unwrap(), 2);
+        assert_eq!(
+            bit_image.raster_data().unwrap(),
+            vec![0b1000_0000, 0b0100_0000, 0b1000_0000, 0b0100_0000]
+        );
+    }
+
+    #[test]
+    fn test_bit_image_from_packed_bits() {
+        let data = [0b1010_1010, 0b0101_0101];
+        let bit_image = BitImage::from_packed_bits(&data, 8, 2, BitImageOption::default()).unwrap();
+        assert_eq!((bit_image.width().unwrap(), bit_image.height().unwrap()), (8, 2));
+        assert_eq!(bit_image.raster_data().unwrap(), data);
+        assert_eq!(
+            bit_image.column_data(8).unwrap(),
+            vec![vec![128, 64, 128, 64, 128, 64, 128, 64]]
+        );
+
+        // Padding bits are ignored
+        let bit_image = BitImage::from_packed_bits(&[0xFF, 0xFF], 12, 1, BitImageOption::default()).unwrap();
+        assert_eq!(bit_image.raster_data().unwrap(), vec![0xFF, 0xF0]);
+
+        assert!(BitImage::from_packed_bits(&data, 8, 3, BitImageOption::default()).is_err());
+        assert!(BitImage::from_packed_bits(&data, 9, 2, BitImageOption::default()).is_err());
+        assert!(BitImage::from_packed_bits(&[], 0, 0, BitImageOption::default()).is_err());
+    }
+
+    #[test]
+    fn test_column_data_large_image() {
+        // 3000 x 30 dots: a band of 3000 columns of 3 bytes does not fit in `u16`, last dot is black
+        let mut data = vec![0; 375 * 30];
+        data[375 * 30 - 1] = 0b0000_0001;
+        let option = BitImageOption::new(Some(3000), Some(30), BitImageSize::Normal).unwrap();
+        let bit_image = BitImage::from_packed_bits(&data, 3000, 30, option).unwrap();
+        let bands = bit_image.column_data(24).unwrap();
+
+        assert_eq!(bands.len(), 2);
+        assert_eq!(bands[0].len(), 9000);
+        assert!(bands[0].iter().all(|&byte| byte == 0));
+        assert_eq!(bands[1][8997..], [0b0000_0100, 0, 0]);
+    }
+
+    #[test]
+    fn test_bit_image_from_dynamic_image() {
+        let img = image::open("./resources/images/small.jpg").unwrap();
+        let option = || BitImageOption::new(None, None, BitImageSize::Normal).unwrap();
+        let from_path = BitImage::new("./resources/images/small.jpg", option()).unwrap();
+
+        let bit_image = BitImage::from_dynamic_image(img.clone(), option()).unwrap();
+        assert_eq!(bit_image.raster_data().unwrap(), from_path.raster_data().unwrap());
+
+        let bit_image = BitImage::from_gray_image(img.to_luma8(), option()).unwrap();
+        assert_eq!(bit_image.raster_data().unwrap(), from_path.raster_data().unwrap());
+    }
+
+    #[test]
+    fn test_bit_image_raster_bands() {
+        // 8 x 5 dots, one line per row index
+        let data = [1, 2, 3, 4, 5];
+        let option = || BitImageOption::default();
+
+        let bit_image = BitImage::from_packed_bits(&data, 8, 5, option()).unwrap();
+        assert_eq!(bit_image.raster_bands().unwrap(), vec![(vec![5, 0], data.to_vec())]);
+
+        let bit_image = BitImage::from_packed_bits(&data, 8, 5, option().band_height(Some(2)).unwrap()).unwrap();
+        assert_eq!(
+            bit_image.raster_bands().unwrap(),
+            vec![
+                (vec![2, 0], vec![1, 2]),
+                (vec![2, 0], vec![3, 4]),
+                (vec![1, 0], vec![5])
+            ]
+        );
+
+        let bit_image = BitImage::from_packed_bits(&data, 8, 5, option().band_height(None).unwrap()).unwrap();
+        assert_eq!(bit_image.raster_bands().unwrap(), vec![(vec![5, 0], data.to_vec())]);
+    }
+
+    #[test]
+    fn test_bit_image_raster_bands_tall() {
+        // 16 x 600 dots: bands of 256, 256 and 88 dots
+        let data = vec![0xAA; 2 * 600];
+        let option = BitImageOption::new(None, None, BitImageSize::Normal).unwrap();
+        let bit_image = BitImage::from_packed_bits(&data, 16, 600, option).unwrap();
+        let bands = bit_image.raster_bands().unwrap();
+
+        assert_eq!(
+            bands.iter().map(|(height, _)| height.clone()).collect::<Vec<_>>(),
+            vec![vec![0, 1], vec![0, 1], vec![88, 0]]
+        );
+        assert_eq!(
+            bands.iter().map(|(_, data)| data.len()).collect::<Vec<_>>(),
+            vec![512, 512, 176]
+        );
+        assert_eq!(bit_image.height_u8().unwrap(), vec![88, 2]);
+    }
+
+    /// Dots of a bit image, row by row (`true` for black)
+    fn dots(bit_image: &BitImage) -> Vec<Vec<bool>> {
+        let (width, height) = (bit_image.width().unwrap(), bit_image.height().unwrap());
+        (0..height)
+            .map(|y| (0..width).map(|x| bit_image.is_pixel_black(x, y)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_bit_image_rotate_and_mirror() {
+        // 3 x 2 dots, top left dot is black
+        let data = [0b1000_0000, 0];
+        let option = || BitImageOption::new(None, None, BitImageSize::Normal).unwrap();
+
+        let bit_image = BitImage::from_packed_bits(&data, 3, 2, option().rotate(ImageRotation::Rotate90)).unwrap();
+        assert_eq!(
+            dots(&bit_image),
+            vec![vec![false, true], vec![false, false], vec![false, false]]
+        );
+
+        let bit_image = BitImage::from_packed_bits(&data, 3, 2, option().rotate(ImageRotation::Rotate180)).unwrap();
+        assert_eq!(
+            dots(&bit_image),
+            vec![vec![false, false, false], vec![false, false, true]]
+        );
+
+        let bit_image = BitImage::from_packed_bits(&data, 3, 2, option().rotate(ImageRotation::Rotate270)).unwrap();
+        assert_eq!(
+            dots(&bit_image),
+            vec![vec![false, false], vec![false, false], vec![true, false]]
+        );
+
+        let bit_image = BitImage::from_packed_bits(&data, 3, 2, option().mirror()).unwrap();
+        assert_eq!(
+            dots(&bit_image),
+            vec![vec![false, false, true], vec![false, false, false]]
+        );
+
+        let bit_image =
+            BitImage::from_packed_bits(&data, 3, 2, option().rotate(ImageRotation::Rotate90).mirror()).unwrap();
+        assert_eq!(
+            dots(&bit_image),
+            vec![vec![true, false], vec![false, false], vec![false, false]]
+        );
+    }
+
+    #[test]
+    fn test_bit_image_invert() {
+        let data = [0b1010_0000];
+        let option = BitImageOption::new(None, None, BitImageSize::Normal).unwrap().invert();
+        let bit_image = BitImage::from_packed_bits(&data, 4, 1, option).unwrap();
+        assert_eq!(bit_image.raster_data().unwrap(), vec![0b0101_0000]);
+    }
+
+    #[test]
+    fn test_bit_image_trim() {
+        // Black 2 x 3 rectangle at (3, 1) on a white 8 x 6 image
+        let img = GrayImage::from_fn(8, 6, |x, y| {
+            Luma([if (3..5).contains(&x) && (1..4).contains(&y) {
+                0
+            } else {
+                255
+            }])
+        });
+        let option = BitImageOption::new(None, None, BitImageSize::Normal).unwrap().trim();
+        let bit_image = BitImage::from_gray_image(img, option).unwrap();
+        assert_eq!((bit_image.width().unwrap(), bit_image.height().unwrap()), (2, 3));
+
+        // Transparent margins
+        let img = image::RgbaImage::from_fn(6, 4, |x, _| {
+            if x == 2 {
+                Rgba([0, 0, 0, 255])
+            } else {
+                Rgba([0, 0, 0, 0])
+            }
+        });
+        let option = BitImageOption::new(None, None, BitImageSize::Normal).unwrap().trim();
+        let bit_image = BitImage::from_dynamic_image(DynamicImage::ImageRgba8(img), option).unwrap();
+        assert_eq!((bit_image.width().unwrap(), bit_image.height().unwrap()), (1, 4));
+
+        // Blank images are kept
+        let img = GrayImage::from_pixel(8, 2, Luma([255]));
+        let option = BitImageOption::new(None, None, BitImageSize::Normal).unwrap().trim();
+        let bit_image = BitImage::from_gray_image(img, option).unwrap();
+        assert_eq!((bit_image.width().unwrap(), bit_image.height().unwrap()), (8, 2));
+    }
+
+    #[test]
+    fn test_bit_image_padding_and_border() {
+        let option = BitImageOption::new(None, None, BitImageSize::Normal)
+            .unwrap()
+            .padding(1)
+            .border(1);
+        let bit_image = BitImage::from_packed_bits(&[0b1000_0000], 1, 1, option).unwrap();
+        assert_eq!(
+            dots(&bit_image),
+            vec![
+                vec![true, true, true, true, true],
+                vec![true, false, false, false, true],
+                vec![true, false, true, false, true],
+                vec![true, false, false, false, true],
+                vec![true, true, true, true, true],
+            ]
+        );
+
+        // The frame is included in the max size
+        let option = BitImageOption::new(Some(20), None, BitImageSize::Normal)
+            .unwrap()
+            .padding(4)
+            .border(1);
+        let img = GrayImage::from_pixel(40, 40, Luma([0]));
+        let bit_image = BitImage::from_gray_image(img, option).unwrap();
+        assert_eq!((bit_image.width().unwrap(), bit_image.height().unwrap()), (20, 20));
+    }
+
+    #[test]
+    fn test_bit_image_alignment() {
+        let option = || {
+            BitImageOption::new(Some(16), None, BitImageSize::Normal)
+                .unwrap()
+                .invert()
+        };
+        // 4 x 1 white dots, inverted to black
+        let data = [0];
+
+        let bit_image = BitImage::from_packed_bits(&data, 4, 1, option().alignment(JustifyMode::LEFT)).unwrap();
+        assert_eq!(bit_image.raster_data().unwrap(), vec![0xF0, 0x00]);
+
+        let bit_image = BitImage::from_packed_bits(&data, 4, 1, option().alignment(JustifyMode::CENTER)).unwrap();
+        assert_eq!(bit_image.raster_data().unwrap(), vec![0x03, 0xC0]);
+
+        let bit_image = BitImage::from_packed_bits(&data, 4, 1, option().alignment(JustifyMode::RIGHT)).unwrap();
+        assert_eq!(bit_image.raster_data().unwrap(), vec![0x00, 0x0F]);
+
+        // The paper width is used first
+        let bit_image =
+            BitImage::from_packed_bits(&data, 4, 1, option().alignment(JustifyMode::RIGHT).paper_width(8)).unwrap();
+        assert_eq!(bit_image.raster_data().unwrap(), vec![0x0F]);
+
+        let option = BitImageOption::new(None, None, BitImageSize::Normal)
+            .unwrap()
+            .alignment(JustifyMode::CENTER);
+        assert!(BitImage::from_packed_bits(&data, 4, 1, option).is_err());
+    }
 }
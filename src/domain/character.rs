@@ -21,7 +21,7 @@ impl fmt::Display for UnderlineMode {
 }
 
 /// Text font
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Font {
     A,
     B,
@@ -38,6 +38,25 @@ impl fmt::Display for Font {
     }
 }
 
+/// Print color
+///
+/// Used with two-color paper (black and red on most models)
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Color {
+    #[default]
+    Black,
+    Red,
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Black => write!(f, "black"),
+            Color::Red => write!(f, "red"),
+        }
+    }
+}
+
 /// Character page code
 #[derive(Debug, Default, Copy, Clone, PartialEq)]
 pub enum PageCode {
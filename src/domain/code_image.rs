@@ -0,0 +1,2090 @@
+//! 2D codes rendered to images, for printers ignoring `GS ( k`
+//!
+//! QR codes are encoded with the `qrcode` crate, DataMatrix symbols (ECC 200, ASCII encodation) and Aztec codes
+//! by this module. PDF417 and MaxiCode are not rendered as images.
+//! Each module is drawn as a square of dots, without resizing.
+
+#![cfg(feature = "codes_2d_image")]
+
+use super::codes::{
+    Aztec, AztecMode, DataMatrix, DataMatrixType, QRCode, QRCodeCorrectionLevel, QRCodeModel, QRCodeOption,
+    QRCodeStructuredAppend,
+};
+use crate::errors::{PrinterError, Result};
+use image::{GrayImage, Luma};
+use qrcode::{bits::Bits, canvas::Canvas, ec, types::QrError, types::Version, Color, EcLevel, QrCode};
+use std::fmt;
+
+/// Quiet zone of QR codes in modules
+const QRCODE_QUIET_ZONE: u32 = 4;
+/// Quiet zone of Micro QR codes in modules
+const MICRO_QRCODE_QUIET_ZONE: u32 = 2;
+/// Structured append mode indicator
+const QRCODE_STRUCTURED_APPEND_MODE: u8 = 0b0011;
+/// QR code padding codewords
+const QRCODE_PADS: [u8; 2] = [0xEC, 0x11];
+/// Quiet zone of DataMatrix symbols in modules
+const DATA_MATRIX_QUIET_ZONE: u32 = 1;
+
+/// DataMatrix padding codeword
+const DATA_MATRIX_PAD: u8 = 129;
+/// DataMatrix upper shift codeword (extended ASCII)
+const DATA_MATRIX_UPPER_SHIFT: u8 = 235;
+/// DataMatrix ECI codeword
+const DATA_MATRIX_ECI: u8 = 241;
+
+/// Quiet zone of Aztec codes in modules (not required by the specification)
+const AZTEC_QUIET_ZONE: u32 = 1;
+/// Aztec punctuation shift code (Upper, Lower and Digit modes)
+const AZTEC_PUNCTUATION_SHIFT: u32 = 0;
+/// Aztec binary shift code (Upper and Lower modes)
+const AZTEC_BINARY_SHIFT: u32 = 31;
+/// Maximum length of an Aztec binary shift
+const AZTEC_BINARY_SHIFT_MAX: usize = 2047 + 31;
+/// Galois field polynomials of Aztec codewords, by word size
+const AZTEC_POLYNOMIALS: [(usize, u32); 5] = [(4, 0x13), (6, 0x43), (8, 0x12D), (10, 0x409), (12, 0x1069)];
+
+/// 2D code rendering
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Code2DRendering {
+    /// `GS ( k` commands, the symbol is encoded by the printer
+    #[default]
+    Command,
+    /// The symbol is encoded by the crate and printed as a bit image (QR code, DataMatrix and Aztec code),
+    /// PDF417 and MaxiCode are printed with commands
+    Image,
+}
+
+impl fmt::Display for Code2DRendering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Code2DRendering::Command => write!(f, "2D code printed with commands"),
+            Code2DRendering::Image => write!(f, "2D code printed as image"),
+        }
+    }
+}
+
+/// Modules of a symbol (`true` is dark), quiet zone excluded
+#[derive(Debug, Clone, PartialEq)]
+struct ModuleMatrix {
+    columns: u32,
+    rows: u32,
+    modules: Vec<bool>,
+}
+
+impl ModuleMatrix {
+    fn new(columns: u32, rows: u32) -> Self {
+        Self {
+            columns,
+            rows,
+            modules: vec![false; (columns * rows) as usize],
+        }
+    }
+
+    fn get(&self, column: u32, row: u32) -> bool {
+        self.modules[(row * self.columns + column) as usize]
+    }
+
+    fn set(&mut self, column: u32, row: u32, dark: bool) {
+        self.modules[(row * self.columns + column) as usize] = dark;
+    }
+
+    /// Draw the modules as squares of `module_size` dots, surrounded by a white quiet zone
+    fn render(&self, module_size: u32, quiet_zone: u32) -> GrayImage {
+        let margin = quiet_zone * module_size;
+        GrayImage::from_fn(
+            (self.columns + 2 * quiet_zone) * module_size,
+            (self.rows + 2 * quiet_zone) * module_size,
+            |x, y| {
+                let inside = (margin..margin + self.columns * module_size).contains(&x)
+                    && (margin..margin + self.rows * module_size).contains(&y);
+                let dark = inside && self.get((x - margin) / module_size, (y - margin) / module_size);
+                Luma([if dark { 0 } else { 255 }])
+            },
+        )
+    }
+}
+
+/// 2D code rendered to a grayscale image
+///
+/// ```
+/// use escpos::utils::*;
+///
+/// let option = QRCodeOption::new(QRCodeModel::Model2, 3, QRCodeCorrectionLevel::M);
+/// let image = CodeImage::from_qrcode(&QRCode::new("HELLO", Some(option))?)?;
+///
+/// // Version 1: 21 modules and a quiet zone of 4 modules on each side, 3 dots per module
+/// assert_eq!(image.modules(), (21, 21));
+/// assert_eq!(image.image().width(), 87);
+/// # Ok::<(), escpos::errors::PrinterError>(())
+/// ```
+#[derive(Debug)]
+pub struct CodeImage {
+    image: GrayImage,
+    columns: u32,
+    rows: u32,
+}
+
+impl CodeImage {
+    /// Render a QR code (Model 1 is rendered as Model 2, read by all scanners)
+    ///
+    /// The module size is the option size, the ECI and the structured append header are encoded
+    /// in the symbol. Micro QR codes support neither.
+    pub fn from_qrcode(qrcode: &QRCode) -> Result<Self> {
+        let level = ec_level(qrcode.option.correction_level());
+        let (version, colors) = match qrcode.option.model() {
+            QRCodeModel::Micro => Self::micro_qrcode_colors(qrcode, level)?,
+            _ => (1..=40)
+                .find_map(|version| Self::qrcode_colors(qrcode, Version::Normal(version), level).ok())
+                .ok_or_else(|| PrinterError::Input(format!("cannot encode QR code: {}", QrError::DataTooLong)))?,
+        };
+
+        let width = version.width() as u32;
+        let matrix = ModuleMatrix {
+            columns: width,
+            rows: width,
+            modules: colors.into_iter().map(|color| color == Color::Dark).collect(),
+        };
+        let quiet_zone = match qrcode.option.model() {
+            QRCodeModel::Micro => MICRO_QRCODE_QUIET_ZONE,
+            _ => QRCODE_QUIET_ZONE,
+        };
+
+        Ok(Self::new(&matrix, qrcode.module_size().into(), quiet_zone))
+    }
+
+    /// Render a QR code fitting a paper width, quiet zone included
+    ///
+    /// The module size is selected if automatic.
+    pub(crate) fn from_qrcode_for_paper(qrcode: QRCode, paper_width: u32) -> Result<Self> {
+        let (qrcode, quiet_zone) = match qrcode.option.model() {
+            QRCodeModel::Micro => (qrcode, MICRO_QRCODE_QUIET_ZONE),
+            _ => {
+                let option = &qrcode.option;
+                let mut model2_option =
+                    QRCodeOption::new(QRCodeModel::Model2, option.size(), option.correction_level());
+                if option.is_auto_size() {
+                    model2_option = model2_option.auto_size();
+                }
+                let qrcode = QRCode {
+                    option: model2_option,
+                    ..qrcode
+                };
+                (qrcode, QRCODE_QUIET_ZONE)
+            }
+        };
+
+        Self::from_qrcode(&qrcode.fit_paper_width_with_quiet_zone(paper_width, quiet_zone)?)
+    }
+
+    /// Encode a Model 2 QR code in a version
+    fn qrcode_colors(qrcode: &QRCode, version: Version, level: EcLevel) -> Result<(Version, Vec<Color>)> {
+        let encode = || -> std::result::Result<Vec<u8>, QrError> {
+            let mut bits = Bits::new(version);
+            if let Some(eci) = qrcode.eci {
+                bits.push_eci_designator(eci.value())?;
+            }
+            bits.push_optimal_data(&qrcode.data)?;
+            match qrcode.structured_append {
+                Some(header) => qrcode_structured_append_data(header, bits, level),
+                None => {
+                    bits.push_terminator(level)?;
+                    Ok(bits.into_bytes())
+                }
+            }
+        };
+        let data = encode().map_err(|e| PrinterError::Input(format!("cannot encode QR code: {e}")))?;
+
+        let (data, error_correction) = ec::construct_codewords(&data, version, level)
+            .map_err(|e| PrinterError::Input(format!("cannot encode QR code: {e}")))?;
+        let mut canvas = Canvas::new(version, level);
+        canvas.draw_all_functional_patterns();
+        canvas.draw_data(&data, &error_correction);
+
+        Ok((version, canvas.apply_best_mask().into_colors()))
+    }
+
+    /// Encode a Micro QR code in the smallest version
+    fn micro_qrcode_colors(qrcode: &QRCode, level: EcLevel) -> Result<(Version, Vec<Color>)> {
+        if qrcode.eci.is_some() || qrcode.structured_append.is_some() {
+            return Err(PrinterError::Input(
+                "Micro QR code supports neither ECI nor structured append".to_owned(),
+            ));
+        }
+
+        let mut error = QrError::DataTooLong;
+        for version in qrcode.version()?..=4 {
+            match QrCode::with_version(&qrcode.data, Version::Micro(version.into()), level) {
+                Ok(code) => return Ok((code.version(), code.to_colors())),
+                Err(e) => error = e,
+            }
+        }
+        Err(PrinterError::Input(format!("cannot encode Micro QR code: {error}")))
+    }
+
+    /// Render a DataMatrix (ECC 200) symbol
+    ///
+    /// The module size is the option size, the ECI is encoded in the symbol.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = DataMatrixOption::new(DataMatrixType::Square(0), 4)?;
+    /// let image = CodeImage::from_data_matrix(&DataMatrix::new("123456", option))?;
+    ///
+    /// // 3 codewords: 10 x 10 modules and a quiet zone of 1 module on each side
+    /// assert_eq!(image.modules(), (10, 10));
+    /// assert_eq!(image.image().width(), 48);
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn from_data_matrix(code: &DataMatrix) -> Result<Self> {
+        let codewords = data_matrix_codewords(&code.data, code.eci.map(|eci| eci.value()))?;
+        let symbol = DataMatrixSymbol::select(code.option.code_type(), codewords.len())?;
+        let matrix = symbol.matrix(&symbol.with_error_correction(&codewords));
+
+        Ok(Self::new(&matrix, code.option.size().into(), DATA_MATRIX_QUIET_ZONE))
+    }
+
+    /// Render an Aztec code
+    ///
+    /// The module size is the option size, the ECI is encoded in the symbol.
+    /// The number of layers is the smallest one (Compact 1 - 4 or Full-Range 4 - 32) if the option mode has 0 layer.
+    /// The error correction codewords are at least the option correction level (percentage) of the symbol
+    /// codewords plus 3.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = AztecOption::new(AztecMode::Compact(0), 3, 23)?;
+    /// let image = CodeImage::from_aztec(&Aztec::new("Code 2D!", option))?;
+    ///
+    /// // Compact Aztec code of 1 layer: 15 x 15 modules and a quiet zone of 1 module on each side
+    /// assert_eq!(image.modules(), (15, 15));
+    /// assert_eq!(image.image().width(), 51);
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn from_aztec(code: &Aztec) -> Result<Self> {
+        let bits = aztec_bits(&code.data, code.eci.map(|eci| eci.value()))?;
+        let (symbol, words) = AztecSymbol::select(code.option.mode(), code.option.correction_level(), &bits)?;
+        let matrix = symbol.matrix(&words);
+
+        Ok(Self::new(&matrix, code.option.size().into(), AZTEC_QUIET_ZONE))
+    }
+
+    fn new(matrix: &ModuleMatrix, module_size: u32, quiet_zone: u32) -> Self {
+        Self {
+            image: matrix.render(module_size, quiet_zone),
+            columns: matrix.columns,
+            rows: matrix.rows,
+        }
+    }
+
+    /// Get image (black modules on white background)
+    pub fn image(&self) -> &GrayImage {
+        &self.image
+    }
+
+    /// Get image
+    pub fn into_image(self) -> GrayImage {
+        self.image
+    }
+
+    /// Get the number of modules (columns, rows), quiet zone excluded
+    pub fn modules(&self) -> (u32, u32) {
+        (self.columns, self.rows)
+    }
+}
+
+/// Prepend the structured append header to encoded data, then add the terminator and the padding
+fn qrcode_structured_append_data(
+    header: QRCodeStructuredAppend,
+    bits: Bits,
+    level: EcLevel,
+) -> std::result::Result<Vec<u8>, QrError> {
+    let max_length = bits.max_len(level)?;
+    let length = bits.len();
+    let content = bits.into_bytes();
+
+    let mut stream = Vec::with_capacity(max_length);
+    let mut push = |length: usize, value: u8| {
+        stream.extend((0..length).rev().map(|bit| value >> bit & 1 == 1));
+    };
+    push(4, QRCODE_STRUCTURED_APPEND_MODE);
+    push(4, header.index);
+    push(4, header.total.saturating_sub(1));
+    push(8, header.parity);
+    (0..length).for_each(|bit| push(1, content[bit / 8] >> (7 - bit % 8)));
+    if stream.len() > max_length {
+        return Err(QrError::DataTooLong);
+    }
+    // Terminator
+    let terminator = (max_length - stream.len()).min(4);
+    stream.extend(std::iter::repeat_n(false, terminator));
+
+    let mut data: Vec<u8> = stream
+        .chunks(8)
+        .map(|bits| {
+            bits.iter()
+                .enumerate()
+                .fold(0, |byte, (i, bit)| byte | u8::from(*bit) << (7 - i))
+        })
+        .collect();
+    let pads = max_length / 8 - data.len();
+    data.extend(QRCODE_PADS.into_iter().cycle().take(pads));
+
+    Ok(data)
+}
+
+/// Get the `qrcode` crate error correction level
+fn ec_level(level: QRCodeCorrectionLevel) -> EcLevel {
+    match level {
+        QRCodeCorrectionLevel::L => EcLevel::L,
+        QRCodeCorrectionLevel::M => EcLevel::M,
+        QRCodeCorrectionLevel::Q => EcLevel::Q,
+        QRCodeCorrectionLevel::H => EcLevel::H,
+    }
+}
+
+/// Encode DataMatrix data codewords (ASCII encodation), without padding
+fn data_matrix_codewords(data: &[u8], eci: Option<u32>) -> Result<Vec<u8>> {
+    let mut codewords = Vec::with_capacity(data.len() + 2);
+
+    if let Some(eci) = eci {
+        // ECI 0 - 126 are encoded in one codeword
+        let eci = u8::try_from(eci)
+            .ok()
+            .filter(|eci| *eci < 127)
+            .ok_or_else(|| PrinterError::Input(format!("unsupported DataMatrix ECI: {eci}")))?;
+        codewords.extend([DATA_MATRIX_ECI, eci + 1]);
+    }
+
+    let mut index = 0;
+    while index < data.len() {
+        match (data[index], data.get(index + 1)) {
+            (d1, Some(d2)) if d1.is_ascii_digit() && d2.is_ascii_digit() => {
+                codewords.push(130 + (d1 - b'0') * 10 + (d2 - b'0'));
+                index += 1;
+            }
+            (c, _) if c < 128 => codewords.push(c + 1),
+            (c, _) => codewords.extend([DATA_MATRIX_UPPER_SHIFT, c - 127]),
+        }
+        index += 1;
+    }
+
+    Ok(codewords)
+}
+
+/// DataMatrix ECC 200 symbol size
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DataMatrixSymbol {
+    rows: u8,
+    columns: u8,
+    /// Number of data regions (vertically, horizontally)
+    regions: (u8, u8),
+    data_codewords: u16,
+    error_codewords: u16,
+    /// Number of interleaved Reed-Solomon blocks
+    blocks: u16,
+}
+
+impl DataMatrixSymbol {
+    /// Square and rectangular symbols, from the smallest
+    #[rustfmt::skip]
+    const SYMBOLS: [DataMatrixSymbol; 30] = [
+        Self::new(10, 10, (1, 1), 3, 5, 1),
+        Self::new(12, 12, (1, 1), 5, 7, 1),
+        Self::new(8, 18, (1, 1), 5, 7, 1),
+        Self::new(14, 14, (1, 1), 8, 10, 1),
+        Self::new(8, 32, (1, 2), 10, 11, 1),
+        Self::new(16, 16, (1, 1), 12, 12, 1),
+        Self::new(12, 26, (1, 1), 16, 14, 1),
+        Self::new(18, 18, (1, 1), 18, 14, 1),
+        Self::new(20, 20, (1, 1), 22, 18, 1),
+        Self::new(12, 36, (1, 2), 22, 18, 1),
+        Self::new(22, 22, (1, 1), 30, 20, 1),
+        Self::new(16, 36, (1, 2), 32, 24, 1),
+        Self::new(24, 24, (1, 1), 36, 24, 1),
+        Self::new(26, 26, (1, 1), 44, 28, 1),
+        Self::new(16, 48, (1, 2), 49, 28, 1),
+        Self::new(32, 32, (2, 2), 62, 36, 1),
+        Self::new(36, 36, (2, 2), 86, 42, 1),
+        Self::new(40, 40, (2, 2), 114, 48, 1),
+        Self::new(44, 44, (2, 2), 144, 56, 1),
+        Self::new(48, 48, (2, 2), 174, 68, 1),
+        Self::new(52, 52, (2, 2), 204, 84, 2),
+        Self::new(64, 64, (4, 4), 280, 112, 2),
+        Self::new(72, 72, (4, 4), 368, 144, 4),
+        Self::new(80, 80, (4, 4), 456, 192, 4),
+        Self::new(88, 88, (4, 4), 576, 224, 4),
+        Self::new(96, 96, (4, 4), 696, 272, 4),
+        Self::new(104, 104, (4, 4), 816, 336, 6),
+        Self::new(120, 120, (6, 6), 1050, 408, 6),
+        Self::new(132, 132, (6, 6), 1304, 496, 8),
+        Self::new(144, 144, (6, 6), 1558, 620, 10),
+    ];
+
+    const fn new(
+        rows: u8,
+        columns: u8,
+        regions: (u8, u8),
+        data_codewords: u16,
+        error_codewords: u16,
+        blocks: u16,
+    ) -> Self {
+        Self {
+            rows,
+            columns,
+            regions,
+            data_codewords,
+            error_codewords,
+            blocks,
+        }
+    }
+
+    /// Select the smallest symbol of a type holding the data codewords
+    ///
+    /// A number of rows or columns equal to 0 is selected automatically.
+    fn select(code_type: DataMatrixType, codewords: usize) -> Result<Self> {
+        let (square, rows, columns) = match code_type {
+            DataMatrixType::Square(d) => (true, d, d),
+            DataMatrixType::Rectangle(d1, d2) => (false, d1, d2),
+        };
+
+        Self::SYMBOLS
+            .into_iter()
+            .filter(|symbol| (symbol.rows == symbol.columns) == square)
+            .filter(|symbol| rows == 0 || symbol.rows == rows)
+            .filter(|symbol| columns == 0 || symbol.columns == columns)
+            .find(|symbol| usize::from(symbol.data_codewords) >= codewords)
+            .ok_or_else(|| {
+                PrinterError::Input(format!(
+                    "DataMatrix data is too long for {code_type}: {codewords} codewords"
+                ))
+            })
+    }
+
+    /// Get the size of the data regions (rows, columns)
+    fn region_size(&self) -> (u32, u32) {
+        (
+            u32::from(self.rows / self.regions.0 - 2),
+            u32::from(self.columns / self.regions.1 - 2),
+        )
+    }
+
+    /// Pad the data codewords and append the interleaved error correction codewords
+    fn with_error_correction(&self, data: &[u8]) -> Vec<u8> {
+        let data_len = usize::from(self.data_codewords);
+        let mut codewords = data.to_vec();
+        for position in data.len() + 1..=data_len {
+            let pad = if position == data.len() + 1 {
+                DATA_MATRIX_PAD
+            } else {
+                // 253-state randomizing
+                let pad = usize::from(DATA_MATRIX_PAD) + (149 * position) % 253 + 1;
+                (if pad > 254 { pad - 254 } else { pad }) as u8
+            };
+            codewords.push(pad);
+        }
+
+        let blocks = usize::from(self.blocks);
+        let block_error_len = usize::from(self.error_codewords) / blocks;
+        let generator = reed_solomon_generator(block_error_len);
+        codewords.resize(data_len + usize::from(self.error_codewords), 0);
+
+        for block in 0..blocks {
+            let block_data: Vec<u8> = codewords[..data_len]
+                .iter()
+                .skip(block)
+                .step_by(blocks)
+                .copied()
+                .collect();
+            for (index, error) in reed_solomon_remainder(&block_data, &generator).into_iter().enumerate() {
+                codewords[data_len + block + index * blocks] = error;
+            }
+        }
+
+        codewords
+    }
+
+    /// Place the codewords and the finder patterns in the symbol
+    fn matrix(&self, codewords: &[u8]) -> ModuleMatrix {
+        let (region_rows, region_columns) = self.region_size();
+        let placement = DataMatrixPlacement::new(
+            region_rows * u32::from(self.regions.0),
+            region_columns * u32::from(self.regions.1),
+        );
+        let mut matrix = ModuleMatrix::new(self.columns.into(), self.rows.into());
+
+        for row in 0..u32::from(self.rows) {
+            for column in 0..u32::from(self.columns) {
+                let (region_row, region_column) = (row % (region_rows + 2), column % (region_columns + 2));
+                let dark = if region_column == 0 || region_row == region_rows + 1 {
+                    // Solid L finder pattern (left and bottom)
+                    true
+                } else if region_row == 0 {
+                    // Alternating timing pattern (top and right)
+                    region_column % 2 == 0
+                } else if region_column == region_columns + 1 {
+                    region_row % 2 == 1
+                } else {
+                    let mapping_row = row / (region_rows + 2) * region_rows + region_row - 1;
+                    let mapping_column = column / (region_columns + 2) * region_columns + region_column - 1;
+                    placement.is_dark(mapping_column, mapping_row, codewords)
+                };
+                matrix.set(column, row, dark);
+            }
+        }
+
+        matrix
+    }
+}
+
+/// Module of the DataMatrix mapping matrix
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DataMatrixModule {
+    Empty,
+    /// Codeword index and bit (0 is the most significant bit)
+    Bit(usize, u8),
+    /// Unused corner module
+    Fixed(bool),
+}
+
+/// DataMatrix codeword placement in the mapping matrix (data regions without finder patterns)
+struct DataMatrixPlacement {
+    rows: i32,
+    columns: i32,
+    modules: Vec<DataMatrixModule>,
+}
+
+impl DataMatrixPlacement {
+    fn new(rows: u32, columns: u32) -> Self {
+        let mut placement = Self {
+            rows: rows as i32,
+            columns: columns as i32,
+            modules: vec![DataMatrixModule::Empty; (rows * columns) as usize],
+        };
+        placement.place();
+        placement
+    }
+
+    /// Is the module dark for the codewords?
+    fn is_dark(&self, column: u32, row: u32, codewords: &[u8]) -> bool {
+        match self.modules[(row as i32 * self.columns + column as i32) as usize] {
+            DataMatrixModule::Bit(index, bit) => codewords[index] & (0x80 >> bit) != 0,
+            DataMatrixModule::Fixed(dark) => dark,
+            DataMatrixModule::Empty => false,
+        }
+    }
+
+    fn index(&self, row: i32, column: i32) -> usize {
+        (row * self.columns + column) as usize
+    }
+
+    /// Place a codeword bit, wrapping around the edges
+    fn module(&mut self, mut row: i32, mut column: i32, codeword: usize, bit: u8) {
+        if row < 0 {
+            row += self.rows;
+            column += 4 - ((self.rows + 4) % 8);
+        }
+        if column < 0 {
+            column += self.columns;
+            row += 4 - ((self.columns + 4) % 8);
+        }
+        let index = self.index(row, column);
+        self.modules[index] = DataMatrixModule::Bit(codeword, bit);
+    }
+
+    /// Place the 8 bits of a codeword in the standard "utah" shape
+    fn utah(&mut self, row: i32, column: i32, codeword: usize) {
+        let shape = [
+            (-2, -2),
+            (-2, -1),
+            (-1, -2),
+            (-1, -1),
+            (-1, 0),
+            (0, -2),
+            (0, -1),
+            (0, 0),
+        ];
+        for (bit, (r, c)) in shape.into_iter().enumerate() {
+            self.module(row + r, column + c, codeword, bit as u8);
+        }
+    }
+
+    /// Place the 8 bits of a codeword in one of the 4 corner shapes
+    fn corner(&mut self, corner: u8, codeword: usize) {
+        let (rows, columns) = (self.rows, self.columns);
+        let shape = match corner {
+            1 => [
+                (rows - 1, 0),
+                (rows - 1, 1),
+                (rows - 1, 2),
+                (0, columns - 2),
+                (0, columns - 1),
+                (1, columns - 1),
+                (2, columns - 1),
+                (3, columns - 1),
+            ],
+            2 => [
+                (rows - 3, 0),
+                (rows - 2, 0),
+                (rows - 1, 0),
+                (0, columns - 4),
+                (0, columns - 3),
+                (0, columns - 2),
+                (0, columns - 1),
+                (1, columns - 1),
+            ],
+            3 => [
+                (rows - 3, 0),
+                (rows - 2, 0),
+                (rows - 1, 0),
+                (0, columns - 2),
+                (0, columns - 1),
+                (1, columns - 1),
+                (2, columns - 1),
+                (3, columns - 1),
+            ],
+            _ => [
+                (rows - 1, 0),
+                (rows - 1, columns - 1),
+                (0, columns - 3),
+                (0, columns - 2),
+                (0, columns - 1),
+                (1, columns - 3),
+                (1, columns - 2),
+                (1, columns - 1),
+            ],
+        };
+        for (bit, (r, c)) in shape.into_iter().enumerate() {
+            self.module(r, c, codeword, bit as u8);
+        }
+    }
+
+    fn is_empty(&self, row: i32, column: i32) -> bool {
+        self.modules[self.index(row, column)] == DataMatrixModule::Empty
+    }
+
+    /// Place the codewords diagonally (ISO/IEC 16022 annex F)
+    fn place(&mut self) {
+        let (rows, columns) = (self.rows, self.columns);
+        let mut codeword = 0;
+        let (mut row, mut column) = (4, 0);
+
+        loop {
+            let corner = match (row, column) {
+                (r, 0) if r == rows => Some(1),
+                (r, 0) if r == rows - 2 && columns % 4 != 0 => Some(2),
+                (r, 0) if r == rows - 2 && columns % 8 == 4 => Some(3),
+                (r, 2) if r == rows + 4 && columns % 8 == 0 => Some(4),
+                _ => None,
+            };
+            if let Some(corner) = corner {
+                self.corner(corner, codeword);
+                codeword += 1;
+            }
+
+            // Up and right
+            loop {
+                if row < rows && column >= 0 && self.is_empty(row, column) {
+                    self.utah(row, column, codeword);
+                    codeword += 1;
+                }
+                row -= 2;
+                column += 2;
+                if row < 0 || column >= columns {
+                    break;
+                }
+            }
+            row += 1;
+            column += 3;
+
+            // Down and left
+            loop {
+                if row >= 0 && column < columns && self.is_empty(row, column) {
+                    self.utah(row, column, codeword);
+                    codeword += 1;
+                }
+                row += 2;
+                column -= 2;
+                if row >= rows || column < 0 {
+                    break;
+                }
+            }
+            row += 3;
+            column += 1;
+
+            if row >= rows && column >= columns {
+                break;
+            }
+        }
+
+        // Unused bottom right corner
+        if self.is_empty(rows - 1, columns - 1) {
+            for (r, c, dark) in [(1, 1, true), (1, 2, false), (2, 1, false), (2, 2, true)] {
+                let index = self.index(rows - r, columns - c);
+                self.modules[index] = DataMatrixModule::Fixed(dark);
+            }
+        }
+    }
+}
+
+/// Aztec text mode of the encoder (Mixed and Punctuation are only used with shifts)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AztecTextMode {
+    Upper,
+    Lower,
+    Digit,
+}
+
+impl AztecTextMode {
+    /// Number of bits of a code
+    fn code_length(self) -> usize {
+        match self {
+            AztecTextMode::Digit => 4,
+            _ => 5,
+        }
+    }
+
+    /// Get the code of a character in the mode
+    fn code(self, byte: u8) -> Option<u32> {
+        let code = match (self, byte) {
+            (_, b' ') => 1,
+            (AztecTextMode::Upper, b'A'..=b'Z') => byte - b'A' + 2,
+            (AztecTextMode::Lower, b'a'..=b'z') => byte - b'a' + 2,
+            (AztecTextMode::Digit, b'0'..=b'9') => byte - b'0' + 2,
+            (AztecTextMode::Digit, b',') => 12,
+            (AztecTextMode::Digit, b'.') => 13,
+            _ => return None,
+        };
+        Some(code.into())
+    }
+
+    /// Get the latch codes (length, code) to another mode
+    fn latch(self, to: AztecTextMode) -> &'static [(usize, u32)] {
+        match (self, to) {
+            (AztecTextMode::Upper, AztecTextMode::Lower) => &[(5, 28)],
+            (AztecTextMode::Upper | AztecTextMode::Lower, AztecTextMode::Digit) => &[(5, 30)],
+            (AztecTextMode::Lower, AztecTextMode::Upper) => &[(5, 30), (4, 14)],
+            (AztecTextMode::Digit, AztecTextMode::Upper) => &[(4, 14)],
+            (AztecTextMode::Digit, AztecTextMode::Lower) => &[(4, 14), (5, 28)],
+            _ => &[],
+        }
+    }
+}
+
+/// Get the code of a character in the Aztec Punctuation mode
+fn aztec_punctuation_code(byte: u8) -> Option<u32> {
+    let code = match byte {
+        b'\r' => 1,
+        b'!'..=b'/' => byte - b'!' + 6,
+        b':'..=b'?' => byte - b':' + 21,
+        b'[' => 27,
+        b']' => 28,
+        b'{' => 29,
+        b'}' => 30,
+        _ => return None,
+    };
+    Some(code.into())
+}
+
+/// Encode Aztec data bits (Upper, Lower and Digit modes, punctuation shifts and binary shifts)
+fn aztec_bits(data: &[u8], eci: Option<u32>) -> Result<Vec<bool>> {
+    fn push(bits: &mut Vec<bool>, length: usize, value: u32) {
+        bits.extend((0..length).rev().map(|bit| value >> bit & 1 == 1));
+    }
+    let is_text = |byte: u8| {
+        [AztecTextMode::Upper, AztecTextMode::Lower, AztecTextMode::Digit]
+            .into_iter()
+            .any(|mode| mode.code(byte).is_some())
+            || aztec_punctuation_code(byte).is_some()
+    };
+
+    let mut bits = Vec::with_capacity(data.len() * 8);
+    let mut mode = AztecTextMode::Upper;
+
+    if let Some(eci) = eci {
+        // FLG(n) in Punctuation mode, followed by the n digits of the ECI
+        let digits = eci.to_string();
+        if digits.len() > 6 {
+            return Err(PrinterError::Input(format!("unsupported Aztec ECI: {eci}")));
+        }
+        push(&mut bits, 5, AZTEC_PUNCTUATION_SHIFT);
+        push(&mut bits, 5, 0);
+        push(&mut bits, 3, digits.len() as u32);
+        for digit in digits.bytes() {
+            push(&mut bits, 4, u32::from(digit - b'0') + 2);
+        }
+    }
+
+    let mut index = 0;
+    while index < data.len() {
+        let byte = data[index];
+        let target = [mode, AztecTextMode::Upper, AztecTextMode::Lower, AztecTextMode::Digit]
+            .into_iter()
+            .find(|mode| mode.code(byte).is_some());
+
+        if let Some(target) = target {
+            for (length, code) in mode.latch(target) {
+                push(&mut bits, *length, *code);
+            }
+            mode = target;
+            push(&mut bits, mode.code_length(), mode.code(byte).unwrap_or_default());
+        } else if let Some(code) = aztec_punctuation_code(byte) {
+            push(&mut bits, mode.code_length(), AZTEC_PUNCTUATION_SHIFT);
+            push(&mut bits, 5, code);
+        } else {
+            // Binary shift of the following bytes which cannot be encoded as text
+            let length = data[index..]
+                .iter()
+                .take(AZTEC_BINARY_SHIFT_MAX)
+                .position(|byte| is_text(*byte))
+                .unwrap_or(data.len() - index)
+                .min(AZTEC_BINARY_SHIFT_MAX);
+            if mode == AztecTextMode::Digit {
+                for (length, code) in mode.latch(AztecTextMode::Upper) {
+                    push(&mut bits, *length, *code);
+                }
+                mode = AztecTextMode::Upper;
+            }
+            push(&mut bits, 5, AZTEC_BINARY_SHIFT);
+            if length <= 31 {
+                push(&mut bits, 5, length as u32);
+            } else {
+                push(&mut bits, 5, 0);
+                push(&mut bits, 11, (length - 31) as u32);
+            }
+            for byte in &data[index..index + length] {
+                push(&mut bits, 8, (*byte).into());
+            }
+            index += length;
+            continue;
+        }
+        index += 1;
+    }
+
+    Ok(bits)
+}
+
+/// Split bits in codewords, stuffing a bit in codewords whose first bits are all 0 or all 1
+///
+/// The last codeword is padded with 1.
+fn aztec_stuff_bits(bits: &[bool], word_size: usize) -> Vec<u32> {
+    let mask = (1 << word_size) - 2;
+    let mut words = Vec::with_capacity(bits.len() / word_size + 1);
+    let mut index = 0;
+
+    while index < bits.len() {
+        let word = (0..word_size).fold(0, |word, bit| {
+            word << 1 | u32::from(bits.get(index + bit).copied().unwrap_or(true))
+        });
+        if word & mask == mask {
+            words.push(word & mask);
+            index += word_size - 1;
+        } else if word & mask == 0 {
+            words.push(word | 1);
+            index += word_size - 1;
+        } else {
+            words.push(word);
+            index += word_size;
+        }
+    }
+
+    words
+}
+
+/// Aztec symbol size
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AztecSymbol {
+    compact: bool,
+    layers: u32,
+}
+
+impl AztecSymbol {
+    /// Get the number of bits of a codeword
+    fn word_size(&self) -> usize {
+        match self.layers {
+            1..=2 => 6,
+            3..=8 => 8,
+            9..=22 => 10,
+            _ => 12,
+        }
+    }
+
+    /// Get the number of bits of the data layers
+    fn total_bits(&self) -> usize {
+        let layer_bits = if self.compact { 88 } else { 112 };
+        ((layer_bits + 16 * self.layers) * self.layers) as usize
+    }
+
+    /// Get the maximum number of data codewords (mode message limit)
+    fn max_data_words(&self) -> usize {
+        if self.compact {
+            64
+        } else {
+            2048
+        }
+    }
+
+    /// Get the number of modules on each side, without the reference grid
+    fn base_size(&self) -> u32 {
+        if self.compact {
+            11 + 4 * self.layers
+        } else {
+            14 + 4 * self.layers
+        }
+    }
+
+    /// Get the number of modules on each side
+    fn size(&self) -> u32 {
+        let base_size = self.base_size();
+        if self.compact {
+            base_size
+        } else {
+            // Reference grid lines every 16 modules from the center
+            base_size + 1 + 2 * ((base_size / 2 - 1) / 15)
+        }
+    }
+
+    /// Select the smallest symbol of a mode holding the data bits, returns the symbol and its stuffed data codewords
+    ///
+    /// The number of error correction codewords is at least `correction_level`% of the codewords plus 3.
+    fn select(mode: AztecMode, correction_level: u8, bits: &[bool]) -> Result<(Self, Vec<u32>)> {
+        let (mode_type, layers): (u8, u8) = mode.try_into()?;
+        let (compact, layers) = match (mode_type, layers) {
+            (1, 0) => (true, 1..=4),
+            (0, 0) => (false, 4..=32),
+            (compact, layers) => (compact == 1, layers..=layers),
+        };
+
+        layers
+            .map(|layers| Self {
+                compact,
+                layers: layers.into(),
+            })
+            .find_map(|symbol| {
+                let words = aztec_stuff_bits(bits, symbol.word_size());
+                let total_words = symbol.total_bits() / symbol.word_size();
+                let error_words = total_words * usize::from(correction_level) / 100 + 3;
+                (words.len() <= symbol.max_data_words() && words.len() + error_words <= total_words)
+                    .then_some((symbol, words))
+            })
+            .ok_or_else(|| PrinterError::Input(format!("Aztec code data is too long for {mode}: {} bits", bits.len())))
+    }
+
+    /// Get the mode message bits (number of layers and of data codewords, with error correction)
+    fn mode_message(&self, data_words: usize) -> Vec<bool> {
+        let (value, words, error_words) = if self.compact {
+            ((self.layers - 1) << 6 | (data_words as u32 - 1), 2, 5)
+        } else {
+            ((self.layers - 1) << 11 | (data_words as u32 - 1), 4, 6)
+        };
+        let data: Vec<u32> = (0..words).rev().map(|word| value >> (4 * word) & 0xF).collect();
+        let field = GaloisField::new(4, 0x13);
+
+        data.iter()
+            .chain(&field.reed_solomon_remainder(&data, error_words))
+            .flat_map(|word| (0..4).rev().map(move |bit| word >> bit & 1 == 1))
+            .collect()
+    }
+
+    /// Get the data layers bits: padding, data and error correction codewords
+    fn message(&self, words: &[u32]) -> Vec<bool> {
+        let word_size = self.word_size();
+        let total_words = self.total_bits() / word_size;
+        let polynomial = AZTEC_POLYNOMIALS
+            .iter()
+            .find_map(|(size, polynomial)| (*size == word_size).then_some(*polynomial))
+            .unwrap_or_default();
+        let field = GaloisField::new(word_size, polynomial);
+        let error_words = field.reed_solomon_remainder(words, total_words - words.len());
+
+        let mut bits = vec![false; self.total_bits() % word_size];
+        for word in words.iter().chain(&error_words) {
+            bits.extend((0..word_size).rev().map(|bit| word >> bit & 1 == 1));
+        }
+        bits
+    }
+
+    /// Place the bullseye, the mode message, the reference grid and the data layers in the symbol
+    fn matrix(&self, words: &[u32]) -> ModuleMatrix {
+        let (base_size, size) = (self.base_size(), self.size());
+        let center = size / 2;
+        let mut matrix = ModuleMatrix::new(size, size);
+
+        // Positions of the base symbol modules, skipping the reference grid lines
+        let positions: Vec<u32> = if self.compact {
+            (0..base_size).collect()
+        } else {
+            let base_center = base_size / 2;
+            (0..base_size)
+                .map(|index| {
+                    if index < base_center {
+                        let offset = base_center - index - 1;
+                        center - (offset + offset / 15) - 1
+                    } else {
+                        let offset = index - base_center;
+                        center + (offset + offset / 15) + 1
+                    }
+                })
+                .collect()
+        };
+
+        // Data layers, from the outermost, in dominoes of 2 modules clockwise from the top left corner
+        let message = self.message(words);
+        let mut offset = 0;
+        for layer in 0..self.layers {
+            let row_size = ((self.layers - layer) * 4 + if self.compact { 9 } else { 12 }) as usize;
+            let (low, high) = ((layer * 2) as usize, (base_size - 1 - layer * 2) as usize);
+            for j in 0..row_size {
+                for k in 0..2 {
+                    let modules = [
+                        (low + k, low + j),
+                        (low + j, high - k),
+                        (high - k, high - j),
+                        (high - j, low + k),
+                    ];
+                    for (side, (column, row)) in modules.into_iter().enumerate() {
+                        if message[offset + side * row_size * 2 + j * 2 + k] {
+                            matrix.set(positions[column], positions[row], true);
+                        }
+                    }
+                }
+            }
+            offset += row_size * 8;
+        }
+
+        // Mode message, clockwise around the bullseye
+        let mode_message = self.mode_message(words.len());
+        let (ring, side) = if self.compact { (5, 7) } else { (7, 10) };
+        for i in 0..side {
+            let offset = if self.compact {
+                center - 3 + i
+            } else {
+                center - 5 + i + i / 5
+            };
+            let modules = [
+                (offset, center - ring),
+                (center + ring, offset),
+                (2 * center - offset, center + ring),
+                (center - ring, 2 * center - offset),
+            ];
+            for (index, (column, row)) in modules.into_iter().enumerate() {
+                if mode_message[index * side as usize + i as usize] {
+                    matrix.set(column, row, true);
+                }
+            }
+        }
+
+        // Bullseye: dark square rings at even distances of the center, and orientation marks
+        let bullseye = ring - 1;
+        for row in center - bullseye..=center + bullseye {
+            for column in center - bullseye..=center + bullseye {
+                let distance = row.abs_diff(center).max(column.abs_diff(center));
+                matrix.set(column, row, distance % 2 == 0);
+            }
+        }
+        for (column, row) in [
+            (center - ring, center - ring),
+            (center - ring + 1, center - ring),
+            (center - ring, center - ring + 1),
+            (center + ring, center - ring),
+            (center + ring, center - ring + 1),
+            (center + ring, center + ring - 1),
+        ] {
+            matrix.set(column, row, true);
+        }
+
+        // Reference grid: alternating lines every 16 modules from the center
+        if !self.compact {
+            for line in (0..=center).step_by(16) {
+                for index in (center % 2..size).step_by(2) {
+                    for (column, row) in [
+                        (center - line, index),
+                        (center + line, index),
+                        (index, center - line),
+                        (index, center + line),
+                    ] {
+                        matrix.set(column, row, true);
+                    }
+                }
+            }
+        }
+
+        matrix
+    }
+}
+
+/// Galois field GF(2^m) for the Aztec Reed-Solomon codes
+struct GaloisField {
+    exp: Vec<u32>,
+    log: Vec<u32>,
+}
+
+impl GaloisField {
+    fn new(bits: usize, polynomial: u32) -> Self {
+        let size = 1 << bits;
+        let (mut exp, mut log) = (vec![0; size], vec![0; size]);
+        let mut value = 1;
+        for (power, exp) in exp.iter_mut().enumerate() {
+            *exp = value;
+            value <<= 1;
+            if value >= size as u32 {
+                value ^= polynomial;
+            }
+            log[*exp as usize] = power as u32;
+        }
+        Self { exp, log }
+    }
+
+    fn multiply(&self, a: u32, b: u32) -> u32 {
+        if a == 0 || b == 0 {
+            return 0;
+        }
+        let order = self.exp.len() - 1;
+        self.exp[(self.log[a as usize] as usize + self.log[b as usize] as usize) % order]
+    }
+
+    /// Get the error correction codewords (generator roots a^1 to a^n)
+    fn reed_solomon_remainder(&self, data: &[u32], error_words: usize) -> Vec<u32> {
+        let mut generator = vec![1];
+        for power in 1..=error_words {
+            let root = self.exp[power % (self.exp.len() - 1)];
+            let mut next = vec![0; generator.len() + 1];
+            for (index, coefficient) in generator.iter().enumerate() {
+                next[index] ^= coefficient;
+                next[index + 1] ^= self.multiply(*coefficient, root);
+            }
+            generator = next;
+        }
+
+        let mut remainder = vec![0; error_words];
+        for word in data {
+            let factor = word ^ remainder.first().copied().unwrap_or_default();
+            remainder.rotate_left(1);
+            if let Some(last) = remainder.last_mut() {
+                *last = 0;
+            }
+            for (value, coefficient) in remainder.iter_mut().zip(&generator[1..]) {
+                *value ^= self.multiply(*coefficient, factor);
+            }
+        }
+        remainder
+    }
+}
+
+/// Multiply in GF(256) with the DataMatrix polynomial (x^8 + x^5 + x^3 + x^2 + 1)
+fn gf256_multiply(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= 0x2D;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// Get the Reed-Solomon generator polynomial coefficients (roots 2^1 to 2^n), highest degree first
+fn reed_solomon_generator(degree: usize) -> Vec<u8> {
+    let mut generator = vec![1u8];
+    let mut root = 1u8;
+    for _ in 0..degree {
+        root = gf256_multiply(root, 2);
+        let mut next = vec![0u8; generator.len() + 1];
+        for (index, coefficient) in generator.iter().enumerate() {
+            next[index] ^= coefficient;
+            next[index + 1] ^= gf256_multiply(*coefficient, root);
+        }
+        generator = next;
+    }
+    generator
+}
+
+/// Get the error correction codewords (remainder of the data by the generator)
+fn reed_solomon_remainder(data: &[u8], generator: &[u8]) -> Vec<u8> {
+    let mut remainder = vec![0u8; generator.len() - 1];
+    for byte in data {
+        let factor = byte ^ remainder[0];
+        remainder.rotate_left(1);
+        *remainder.last_mut().unwrap_or(&mut 0) = 0;
+        for (value, coefficient) in remainder.iter_mut().zip(&generator[1..]) {
+            *value ^= gf256_multiply(*coefficient, factor);
+        }
+    }
+    remainder
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::codes::{AztecOption, DataMatrixOption, Eci, QRCodeOption};
+
+    /// Build a module matrix from rows of `#` (dark) and `.` (light) modules
+    fn fixture(rows: &[&str]) -> ModuleMatrix {
+        let mut matrix = ModuleMatrix::new(rows[0].len() as u32, rows.len() as u32);
+        for (row, modules) in rows.iter().enumerate() {
+            for (column, module) in modules.chars().enumerate() {
+                matrix.set(column as u32, row as u32, module == '#');
+            }
+        }
+        matrix
+    }
+
+    /// Read the modules back from an image, checking each module is a uniform square
+    fn read_modules(image: &GrayImage, module_size: u32, quiet_zone: u32) -> ModuleMatrix {
+        assert_eq!(image.width() % module_size, 0);
+        let columns = image.width() / module_size - 2 * quiet_zone;
+        let rows = image.height() / module_size - 2 * quiet_zone;
+        let mut matrix = ModuleMatrix::new(columns, rows);
+
+        for y in 0..image.height() {
+            for x in 0..image.width() {
+                let (column, row) = (x / module_size, y / module_size);
+                let dark = image.get_pixel(x, y).0[0] == 0;
+                let in_quiet_zone = column < quiet_zone
+                    || row < quiet_zone
+                    || column >= columns + quiet_zone
+                    || row >= rows + quiet_zone;
+                if in_quiet_zone {
+                    assert!(!dark, "dark dot in the quiet zone at ({x}, {y})");
+                } else if x % module_size == 0 && y % module_size == 0 {
+                    matrix.set(column - quiet_zone, row - quiet_zone, dark);
+                } else {
+                    assert_eq!(matrix.get(column - quiet_zone, row - quiet_zone), dark, "({x}, {y})");
+                }
+            }
+        }
+
+        matrix
+    }
+
+    /// Decode a DataMatrix symbol: check the finder patterns and the error correction,
+    /// then decode the ASCII encodation
+    fn decode_data_matrix(matrix: &ModuleMatrix) -> (Vec<u8>, Option<u8>) {
+        let symbol = DataMatrixSymbol::SYMBOLS
+            .into_iter()
+            .find(|symbol| u32::from(symbol.columns) == matrix.columns && u32::from(symbol.rows) == matrix.rows)
+            .expect("unknown symbol size");
+        let (region_rows, region_columns) = symbol.region_size();
+
+        // Finder patterns
+        for row in 0..matrix.rows {
+            for column in 0..matrix.columns {
+                let (r, c) = (row % (region_rows + 2), column % (region_columns + 2));
+                if c == 0 || r == region_rows + 1 {
+                    assert!(matrix.get(column, row));
+                } else if r == 0 {
+                    assert_eq!(matrix.get(column, row), c % 2 == 0);
+                } else if c == region_columns + 1 {
+                    assert_eq!(matrix.get(column, row), r % 2 == 1);
+                }
+            }
+        }
+
+        // Codewords
+        let placement = DataMatrixPlacement::new(
+            region_rows * u32::from(symbol.regions.0),
+            region_columns * u32::from(symbol.regions.1),
+        );
+        let total = usize::from(symbol.data_codewords + symbol.error_codewords);
+        let mut codewords = vec![0u8; total];
+        for row in 0..matrix.rows {
+            for column in 0..matrix.columns {
+                let (r, c) = (row % (region_rows + 2), column % (region_columns + 2));
+                if r == 0 || c == 0 || r == region_rows + 1 || c == region_columns + 1 {
+                    continue;
+                }
+                let mapping_row = row / (region_rows + 2) * region_rows + r - 1;
+                let mapping_column = column / (region_columns + 2) * region_columns + c - 1;
+                let index = (mapping_row * placement.columns as u32 + mapping_column) as usize;
+                if let DataMatrixModule::Bit(codeword, bit) = placement.modules[index] {
+                    if matrix.get(column, row) {
+                        codewords[codeword] |= 0x80 >> bit;
+                    }
+                }
+            }
+        }
+
+        // Error correction: the syndromes of each block must be 0
+        let blocks = usize::from(symbol.blocks);
+        let data_len = usize::from(symbol.data_codewords);
+        for block in 0..blocks {
+            let block_codewords: Vec<u8> = codewords[..data_len]
+                .iter()
+                .skip(block)
+                .step_by(blocks)
+                .chain(codewords[data_len..].iter().skip(block).step_by(blocks))
+                .copied()
+                .collect();
+            let mut root = 1u8;
+            for _ in 0..usize::from(symbol.error_codewords) / blocks {
+                root = gf256_multiply(root, 2);
+                let syndrome = block_codewords
+                    .iter()
+                    .fold(0u8, |value, codeword| gf256_multiply(value, root) ^ codeword);
+                assert_eq!(syndrome, 0, "block {block}");
+            }
+        }
+
+        // ASCII encodation
+        let (mut data, mut eci) = (Vec::new(), None);
+        let mut codewords = codewords[..data_len].iter();
+        while let Some(&codeword) = codewords.next() {
+            match codeword {
+                DATA_MATRIX_PAD => break,
+                DATA_MATRIX_ECI => eci = codewords.next().map(|value| value - 1),
+                DATA_MATRIX_UPPER_SHIFT => data.push(codewords.next().unwrap() + 127),
+                1..=128 => data.push(codeword - 1),
+                130..=229 => data.extend(format!("{:02}", codeword - 130).bytes()),
+                _ => panic!("unexpected codeword {codeword}"),
+            }
+        }
+
+        (data, eci)
+    }
+
+    /// Decode an Aztec symbol: check the finder pattern, the reference grid and the error correction,
+    /// then decode the data
+    ///
+    /// The geometry is read from the matrix as described in ISO/IEC 24778, without the encoder tables.
+    fn decode_aztec(matrix: &ModuleMatrix, compact: bool) -> (Vec<u8>, Option<u32>) {
+        const UPPER: [&str; 32] = [
+            "CTRL_PS", " ", "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P", "Q", "R",
+            "S", "T", "U", "V", "W", "X", "Y", "Z", "CTRL_LL", "CTRL_ML", "CTRL_DL", "CTRL_BS",
+        ];
+        const LOWER: [&str; 32] = [
+            "CTRL_PS", " ", "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p", "q", "r",
+            "s", "t", "u", "v", "w", "x", "y", "z", "CTRL_US", "CTRL_ML", "CTRL_DL", "CTRL_BS",
+        ];
+        const MIXED: [&str; 32] = [
+            "CTRL_PS", " ", "\x01", "\x02", "\x03", "\x04", "\x05", "\x06", "\x07", "\x08", "\t", "\n", "\x0B", "\x0C",
+            "\r", "\x1B", "\x1C", "\x1D", "\x1E", "\x1F", "@", "\\", "^", "_", "`", "|", "~", "\x7F", "CTRL_LL",
+            "CTRL_UL", "CTRL_PL", "CTRL_BS",
+        ];
+        const PUNCTUATION: [&str; 32] = [
+            "FLG(n)", "\r", "\r\n", ". ", ", ", ": ", "!", "\"", "#", "$", "%", "&", "'", "(", ")", "*", "+", ",", "-",
+            ".", "/", ":", ";", "<", "=", ">", "?", "[", "]", "{", "}", "CTRL_UL",
+        ];
+        const DIGIT: [&str; 16] = [
+            "CTRL_PS", " ", "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", ",", ".", "CTRL_UL", "CTRL_US",
+        ];
+        const BINARY: usize = 5;
+
+        fn read(bits: &[bool], index: usize, length: usize) -> u32 {
+            bits[index..index + length]
+                .iter()
+                .fold(0, |value, bit| value << 1 | u32::from(*bit))
+        }
+        fn multiply(mut a: u32, mut b: u32, bits: usize, polynomial: u32) -> u32 {
+            let mut product = 0;
+            while b > 0 {
+                if b & 1 == 1 {
+                    product ^= a;
+                }
+                b >>= 1;
+                a <<= 1;
+                if a >> bits & 1 == 1 {
+                    a ^= polynomial;
+                }
+            }
+            product
+        }
+        // The syndromes of the codewords must be 0 (generator roots a^1 to a^n)
+        fn check_syndromes(words: &[u32], error_words: usize, bits: usize, polynomial: u32) {
+            let mut root = 1;
+            for power in 1..=error_words {
+                root = multiply(root, 2, bits, polynomial);
+                let syndrome = words
+                    .iter()
+                    .fold(0, |value, word| multiply(value, root, bits, polynomial) ^ word);
+                assert_eq!(syndrome, 0, "syndrome {power}");
+            }
+        }
+
+        let size = matrix.columns;
+        assert_eq!(matrix.rows, size);
+        let center = size / 2;
+        let ring = if compact { 5 } else { 7 };
+        let is_grid = |index: u32| !compact && index.abs_diff(center).is_multiple_of(16);
+
+        // Bullseye
+        for row in center - ring + 1..center + ring {
+            for column in center - ring + 1..center + ring {
+                let distance = row.abs_diff(center).max(column.abs_diff(center));
+                assert_eq!(matrix.get(column, row), distance % 2 == 0, "bullseye ({column}, {row})");
+            }
+        }
+
+        // Ring around the bullseye, clockwise from the top left corner
+        let side = 2 * ring;
+        let (low, high) = (center - ring, center + ring);
+        let around: Vec<(u32, u32)> = (0..4 * side)
+            .map(|index| {
+                let step = index % side;
+                match index / side {
+                    0 => (low + step, low),
+                    1 => (high, low + step),
+                    2 => (high - step, high),
+                    _ => (low, high - step),
+                }
+            })
+            .collect();
+
+        // Orientation marks: 3, 2, 1 and 0 dark modules at the corners
+        let marks: Vec<usize> = (0..4)
+            .map(|corner| {
+                let index = (corner * side) as usize;
+                [index + around.len() - 1, index, index + 1]
+                    .into_iter()
+                    .filter(|index| {
+                        let (column, row) = around[index % around.len()];
+                        matrix.get(column, row)
+                    })
+                    .count()
+            })
+            .collect();
+        assert_eq!(marks, [3, 2, 1, 0]);
+
+        // Reference grid, outside the bullseye and the mode message
+        for row in 0..size {
+            for column in 0..size {
+                if (is_grid(row) || is_grid(column)) && row.abs_diff(center).max(column.abs_diff(center)) > ring {
+                    let offset = if is_grid(row) { column } else { row }.abs_diff(center);
+                    assert_eq!(matrix.get(column, row), offset % 2 == 0, "grid ({column}, {row})");
+                }
+            }
+        }
+
+        // Mode message: number of layers and of data codewords
+        let mode_message: Vec<bool> = around
+            .iter()
+            .enumerate()
+            .filter(|(index, (column, row))| {
+                (2..=side - 2).contains(&(*index as u32 % side)) && !is_grid(*column) && !is_grid(*row)
+            })
+            .map(|(_, (column, row))| matrix.get(*column, *row))
+            .collect();
+        let mode_words: Vec<u32> = (0..mode_message.len())
+            .step_by(4)
+            .map(|index| read(&mode_message, index, 4))
+            .collect();
+        let (layers, data_words) = if compact {
+            assert_eq!(mode_words.len(), 7);
+            check_syndromes(&mode_words, 5, 4, 0x13);
+            let value = mode_words[0] << 4 | mode_words[1];
+            ((value >> 6) + 1, (value & 0x3F) as usize + 1)
+        } else {
+            assert_eq!(mode_words.len(), 10);
+            check_syndromes(&mode_words, 6, 4, 0x13);
+            let value = mode_words[..4].iter().fold(0, |value, word| value << 4 | word);
+            ((value >> 11) + 1, (value & 0x7FF) as usize + 1)
+        };
+
+        // Data layers from the outermost: dominoes (outer module, inner module) counterclockwise
+        // from the top left corner, the reference grid lines are skipped
+        let positions: Vec<u32> = (0..size).filter(|index| !is_grid(*index)).collect();
+        assert_eq!(positions.len() as u32, if compact { 11 } else { 14 } + 4 * layers);
+        let mut bits = Vec::new();
+        for layer in 0..layers as i32 {
+            let (low, high) = (2 * layer, positions.len() as i32 - 1 - 2 * layer);
+            let (mut column, mut row) = (low, low);
+            for (direction, inward) in [
+                ((0, 1), (1, 0)),
+                ((1, 0), (0, -1)),
+                ((0, -1), (-1, 0)),
+                ((-1, 0), (0, 1)),
+            ] {
+                for step in 0..high - low {
+                    if step < high - low - 1 {
+                        for (column, row) in [(column, row), (column + inward.0, row + inward.1)] {
+                            bits.push(matrix.get(positions[column as usize], positions[row as usize]));
+                        }
+                    }
+                    column += direction.0;
+                    row += direction.1;
+                }
+            }
+        }
+
+        // Codewords, after the padding bits
+        let (word_size, polynomial) = match layers {
+            1..=2 => (6, 0x43),
+            3..=8 => (8, 0x12D),
+            9..=22 => (10, 0x409),
+            _ => (12, 0x1069),
+        };
+        let padding = bits.len() % word_size;
+        assert!(bits[..padding].iter().all(|bit| !bit));
+        let words: Vec<u32> = (padding..bits.len())
+            .step_by(word_size)
+            .map(|index| read(&bits, index, word_size))
+            .collect();
+        check_syndromes(&words, words.len() - data_words, word_size, polynomial);
+
+        // Stuffed bits
+        let mask = (1 << word_size) - 1;
+        let mut bits = Vec::new();
+        for word in &words[..data_words] {
+            assert!(*word != 0 && *word != mask, "invalid codeword {word}");
+            let length = if *word == 1 || *word == mask - 1 {
+                word_size - 1
+            } else {
+                word_size
+            };
+            bits.extend((0..word_size).rev().take(length).map(|bit| word >> bit & 1 == 1));
+        }
+
+        // Modes, shifts and latches
+        let tables: [&[&str]; 5] = [&UPPER, &LOWER, &MIXED, &PUNCTUATION, &DIGIT];
+        let table = |mode: u8| match mode {
+            b'U' => 0,
+            b'L' => 1,
+            b'M' => 2,
+            b'P' => 3,
+            b'D' => 4,
+            _ => BINARY,
+        };
+        let (mut data, mut eci) = (Vec::new(), None);
+        let (mut latch, mut shift) = (0, 0);
+        let mut index = 0;
+        while index < bits.len() {
+            let remaining = bits.len() - index;
+            if shift == BINARY {
+                if remaining < 5 {
+                    break;
+                }
+                let mut length = read(&bits, index, 5) as usize;
+                index += 5;
+                if length == 0 {
+                    if remaining < 16 {
+                        break;
+                    }
+                    length = read(&bits, index, 11) as usize + 31;
+                    index += 11;
+                }
+                for _ in 0..length {
+                    if bits.len() - index < 8 {
+                        index = bits.len();
+                        break;
+                    }
+                    data.push(read(&bits, index, 8) as u8);
+                    index += 8;
+                }
+                shift = latch;
+                continue;
+            }
+
+            let length = if shift == 4 { 4 } else { 5 };
+            if remaining < length {
+                break;
+            }
+            let value = tables[shift][read(&bits, index, length) as usize];
+            index += length;
+            if value == "FLG(n)" {
+                let digits = read(&bits, index, 3) as usize;
+                index += 3;
+                assert!((1..=6).contains(&digits), "FLG({digits})");
+                let mut designator = 0;
+                for _ in 0..digits {
+                    let digit = read(&bits, index, 4).checked_sub(2).filter(|digit| *digit <= 9);
+                    designator = designator * 10 + digit.expect("ECI digit");
+                    index += 4;
+                }
+                eci = Some(designator);
+                shift = latch;
+            } else if let Some(control) = value.strip_prefix("CTRL_") {
+                latch = shift;
+                shift = table(control.as_bytes()[0]);
+                if control.as_bytes()[1] == b'L' {
+                    latch = shift;
+                }
+            } else {
+                data.extend(value.bytes());
+                shift = latch;
+            }
+        }
+
+        (data, eci)
+    }
+
+    /// Read the data codewords of a version 1-L QR code: the mask is the one giving valid error correction
+    fn read_qrcode_version_1(matrix: &ModuleMatrix) -> Vec<u8> {
+        fn multiply(mut a: u8, mut b: u8) -> u8 {
+            let mut product = 0;
+            while b > 0 {
+                if b & 1 == 1 {
+                    product ^= a;
+                }
+                b >>= 1;
+                a = if a & 0x80 == 0 { a << 1 } else { a << 1 ^ 0x1D };
+            }
+            product
+        }
+        let masks: [fn(u32, u32) -> bool; 8] = [
+            |i, j| (i + j) % 2 == 0,
+            |i, _| i % 2 == 0,
+            |_, j| j % 3 == 0,
+            |i, j| (i + j) % 3 == 0,
+            |i, j| (i / 2 + j / 3) % 2 == 0,
+            |i, j| (i * j) % 2 + (i * j) % 3 == 0,
+            |i, j| ((i * j) % 2 + (i * j) % 3) % 2 == 0,
+            |i, j| ((i + j) % 2 + (i * j) % 3) % 2 == 0,
+        ];
+        // Finder patterns, separators, format information and timing patterns
+        let is_function = |row: u32, column: u32| {
+            (row <= 8 && (column <= 8 || column >= 13)) || (row >= 13 && column <= 8) || row == 6 || column == 6
+        };
+        assert_eq!((matrix.columns, matrix.rows), (21, 21));
+
+        masks
+            .iter()
+            .find_map(|mask| {
+                // Pairs of columns from the right (the vertical timing pattern is skipped),
+                // upwards then downwards
+                let mut bits = Vec::with_capacity(26 * 8);
+                for (pair, column) in [20, 18, 16, 14, 12, 10, 8, 5, 3, 1].into_iter().enumerate() {
+                    let upward = pair % 2 == 0;
+                    for index in 0..21 {
+                        let row = if upward { 20 - index } else { index };
+                        for column in [column, column - 1] {
+                            if !is_function(row, column) {
+                                bits.push(matrix.get(column, row) ^ mask(row, column));
+                            }
+                        }
+                    }
+                }
+                let codewords: Vec<u8> = bits
+                    .chunks(8)
+                    .map(|byte| byte.iter().fold(0, |value, bit| value << 1 | u8::from(*bit)))
+                    .collect();
+
+                // 7 error correction codewords, generator roots a^0 to a^6
+                let mut root = 1;
+                let valid = (0..7).all(|_| {
+                    let syndrome = codewords
+                        .iter()
+                        .fold(0, |value, codeword| multiply(value, root) ^ codeword);
+                    root = multiply(root, 2);
+                    syndrome == 0
+                });
+                valid.then(|| codewords[..19].to_vec())
+            })
+            .expect("no mask with valid error correction")
+    }
+
+    #[test]
+    fn test_reed_solomon() {
+        // ISO/IEC 16022 annex O example: "123456" in a 10 x 10 symbol
+        let symbol = DataMatrixSymbol::select(DataMatrixType::Square(0), 3).unwrap();
+        assert_eq!(
+            symbol.with_error_correction(&[142, 164, 186]),
+            vec![142, 164, 186, 114, 25, 5, 88, 102]
+        );
+    }
+
+    #[test]
+    fn test_data_matrix_codewords() {
+        assert_eq!(data_matrix_codewords(b"123456", None).unwrap(), vec![142, 164, 186]);
+        assert_eq!(data_matrix_codewords(b"A1", None).unwrap(), vec![66, 50]);
+        assert_eq!(data_matrix_codewords(&[0xE9], Some(3)).unwrap(), vec![241, 4, 235, 106]);
+        assert!(data_matrix_codewords(b"A", Some(899)).is_err());
+    }
+
+    #[test]
+    fn test_data_matrix_placement() {
+        for symbol in DataMatrixSymbol::SYMBOLS {
+            let (region_rows, region_columns) = symbol.region_size();
+            let placement = DataMatrixPlacement::new(
+                region_rows * u32::from(symbol.regions.0),
+                region_columns * u32::from(symbol.regions.1),
+            );
+            let total = usize::from(symbol.data_codewords + symbol.error_codewords);
+
+            // Each bit of each codeword is placed once, the 4 unused modules are fixed
+            let mut bits = vec![0u8; total];
+            let mut fixed = 0;
+            for module in &placement.modules {
+                match *module {
+                    DataMatrixModule::Bit(codeword, bit) => bits[codeword] |= 1 << bit,
+                    DataMatrixModule::Fixed(_) => fixed += 1,
+                    DataMatrixModule::Empty => panic!("empty module in {}x{}", symbol.rows, symbol.columns),
+                }
+            }
+            assert!(
+                bits.iter().all(|bits| *bits == 0xFF),
+                "{}x{}",
+                symbol.rows,
+                symbol.columns
+            );
+            assert_eq!(placement.modules.len(), total * 8 + fixed);
+            assert!(fixed == 0 || fixed == 4);
+        }
+    }
+
+    #[test]
+    fn test_data_matrix_symbol_select() {
+        let select = |code_type, codewords| {
+            DataMatrixSymbol::select(code_type, codewords).map(|symbol| (symbol.rows, symbol.columns))
+        };
+        assert_eq!(select(DataMatrixType::Square(0), 3).unwrap(), (10, 10));
+        assert_eq!(select(DataMatrixType::Square(0), 4).unwrap(), (12, 12));
+        assert_eq!(select(DataMatrixType::Square(24), 4).unwrap(), (24, 24));
+        assert_eq!(select(DataMatrixType::Square(0), 1558).unwrap(), (144, 144));
+        assert!(select(DataMatrixType::Square(0), 1559).is_err());
+        assert!(select(DataMatrixType::Square(10), 4).is_err());
+        assert_eq!(select(DataMatrixType::Rectangle(0, 0), 6).unwrap(), (8, 32));
+        assert_eq!(select(DataMatrixType::Rectangle(12, 0), 6).unwrap(), (12, 26));
+        assert_eq!(select(DataMatrixType::Rectangle(16, 48), 1).unwrap(), (16, 48));
+    }
+
+    #[test]
+    fn test_data_matrix_image_decode() {
+        let cases: Vec<(Vec<u8>, DataMatrixType)> = vec![
+            (b"123456".to_vec(), DataMatrixType::Square(0)),
+            (b"Hello, World!".to_vec(), DataMatrixType::Square(0)),
+            ((0..=255).collect(), DataMatrixType::Square(0)),
+            (b"0123456789".repeat(100), DataMatrixType::Square(0)),
+            ("escpos-rs ".repeat(150).into_bytes(), DataMatrixType::Square(0)),
+            (b"ABC".to_vec(), DataMatrixType::Rectangle(8, 18)),
+            (b"Rectangle".to_vec(), DataMatrixType::Rectangle(0, 0)),
+            (
+                b"Rectangle 16 x 48 with data".to_vec(),
+                DataMatrixType::Rectangle(16, 48),
+            ),
+            (b"Symbol 144".to_vec(), DataMatrixType::Square(144)),
+        ];
+
+        for (data, code_type) in cases {
+            for module_size in [2, 3] {
+                let option = DataMatrixOption::new(code_type, module_size).unwrap();
+                let image = CodeImage::from_data_matrix(&DataMatrix::from_bytes(&data, option)).unwrap();
+                let matrix = read_modules(image.image(), module_size.into(), DATA_MATRIX_QUIET_ZONE);
+                assert_eq!(matrix.columns, image.modules().0);
+                assert_eq!(decode_data_matrix(&matrix), (data.clone(), None), "{code_type}");
+            }
+        }
+
+        let option = DataMatrixOption::new(DataMatrixType::Square(0), 2).unwrap();
+        let code = DataMatrix::with_eci("Crème", Eci::Latin1, option).unwrap();
+        let image = CodeImage::from_data_matrix(&code).unwrap();
+        let matrix = read_modules(image.image(), 2, DATA_MATRIX_QUIET_ZONE);
+        assert_eq!(decode_data_matrix(&matrix), (code.data, Some(3)));
+    }
+
+    #[test]
+    fn test_data_matrix_image_fixtures() {
+        // ISO/IEC 16022 annex O example (codewords 142 164 186 114 25 5 88 102)
+        let square = [
+            "#.#.#.#.#.",
+            "##..#.##.#",
+            "##.....#..",
+            "##...###.#",
+            "##....#...",
+            "#.....####",
+            "###.##....",
+            "####.##..#",
+            "#..###.#..",
+            "##########",
+        ];
+        // 2 data regions, codewords 83 102 100 117 98 111 104 109 102 129 and 11 error correction codewords
+        let rectangle = [
+            "#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.",
+            "#.##..##..###..##..#.##....#.#.#",
+            "##...##.##.###..#.##.##....##...",
+            "###.#...##.#.#.###.#.......#####",
+            "#..#.#.#.###....#.#..#####....#.",
+            "###.#.#...###.#####....####.##.#",
+            "#.####...##..#..#.#.####.#.#.#..",
+            "################################",
+        ];
+
+        for (data, code_type, expected) in [
+            ("123456", DataMatrixType::Square(0), &square[..]),
+            ("Rectangle", DataMatrixType::Rectangle(8, 32), &rectangle[..]),
+        ] {
+            let option = DataMatrixOption::new(code_type, 3).unwrap();
+            let image = CodeImage::from_data_matrix(&DataMatrix::new(data, option)).unwrap();
+            assert_eq!(
+                read_modules(image.image(), 3, DATA_MATRIX_QUIET_ZONE),
+                fixture(expected),
+                "{data}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_qrcode_image() {
+        // Modules encoded by Project Nayuki's QR Code generator library
+        let hello_world = [
+            "#######.##....#######",
+            "#.....#.#..#..#.....#",
+            "#.###.#.#..##.#.###.#",
+            "#.###.#.#.....#.###.#",
+            "#.###.#.#.#...#.###.#",
+            "#.....#...#...#.....#",
+            "#######.#.#.#.#######",
+            "........#............",
+            ".##.#.##....#.#.#####",
+            ".#......####....#...#",
+            "..##.###.##...#.##...",
+            ".##.##.#..##.#.#.###.",
+            "#...#.#.#.###.###.#.#",
+            "........##.#..#...#.#",
+            "#######.#.#....#.##..",
+            "#.....#..#.##.##.#...",
+            "#.###.#.#.#...#######",
+            "#.###.#..#.#.#.#...#.",
+            "#.###.#.#..#.###.#..#",
+            "#.....#.#.####...#.##",
+            "#######....#.###....#",
+        ];
+        let url = [
+            "#######.....#.#####.#..#..#.#.#######",
+            "#.....#....##.##..#......#..#.#.....#",
+            "#.###.#.#...#..###.#.##..#.#..#.###.#",
+            "#.###.#.####..##.##....###.##.#.###.#",
+            "#.###.#..#....#.##..##.#..#...#.###.#",
+            "#.....#...#.##..##.##..#.#....#.....#",
+            "#######.#.#.#.#.#.#.#.#.#.#.#.#######",
+            "............#..#..####.#.#.##........",
+            "...##.##...#...##.##...#.........##..",
+            "##.##..#..##.######..#######...##.#..",
+            ".##...##.######.#####....#.#####.##.#",
+            "#..#.#..#.###..##.#.####..##.##...##.",
+            ".###..#..##..###.##..##.#...#.##.#.##",
+            ".###.#.#..#.......#.#.#.#.####..#.#..",
+            "#..######.#..####.###.....####.######",
+            ".....#....#.##..######.##......#.##.#",
+            "...#.##.#.#####..#..###.##....#...#.#",
+            "#..#.#.#.###.#.##.....##...#.#.....#.",
+            "###.###.###....#..##..#.####.###.##.#",
+            "###..#...###.####....##..#.##..###..#",
+            ".###..#..#.#.###.#.##.###.#.###.#..#.",
+            "###.....#.###..##.##.#...####..##..#.",
+            "###..###.##..#...#####.....#.##.#...#",
+            ".#.....###...###..#....##.###..#.##.#",
+            "..###.##.#....####..#.#.#...#.#..#..#",
+            "#..##..####..#.####.....##.###..###..",
+            "####.######.#...#.###.....###.#....##",
+            "#.##.#.....#...####.##.##...##.######",
+            "#..#.##..#.###..#..#...###.##########",
+            "........#...###.##..#.#.....#...###..",
+            "#######.#.###..#.##...##....#.#.###.#",
+            "#.....#....#.#...#.....#.####...##.##",
+            "#.###.#.#.#.........#.#...#######....",
+            "#.###.#.##.##.#.##.#####.####.#..##..",
+            "#.###.#...##..####.##.####.....##..##",
+            "#.....#...#.##.####.#.#.#....###.####",
+            "#######..#..##.###....###...#...##..#",
+        ];
+
+        for (data, level, size, expected) in [
+            ("HELLO WORLD", QRCodeCorrectionLevel::Q, 1, &hello_world[..]),
+            (
+                "https://github.com/fabienbellanger/escpos-rs",
+                QRCodeCorrectionLevel::H,
+                3,
+                &url[..],
+            ),
+        ] {
+            let option = QRCodeOption::new(QRCodeModel::Model2, size, level);
+            let qrcode = QRCode::new(data, Some(option)).unwrap();
+            let image = CodeImage::from_qrcode(&qrcode).unwrap();
+
+            let module_size = u32::from(qrcode.module_size());
+            let matrix = read_modules(image.image(), module_size, QRCODE_QUIET_ZONE);
+            assert_eq!(matrix.columns, qrcode.modules().unwrap());
+            assert_eq!(matrix, fixture(expected), "{data}");
+            // Finder pattern
+            assert!((0..7).all(|i| matrix.get(i, 0) && matrix.get(0, i) && matrix.get(6, i)));
+            assert!(!matrix.get(1, 1) && matrix.get(2, 2));
+        }
+
+        let qrcode = QRCode::with_eci("Crème", Eci::Latin1, None).unwrap();
+        assert_eq!(CodeImage::from_qrcode(&qrcode).unwrap().modules(), (21, 21));
+    }
+
+    #[test]
+    fn test_micro_qrcode_image() {
+        use crate::domain::codes::QRCodeMode;
+
+        // The data of the maximum length is encoded by the `qrcode` crate in the same version
+        for version in 1..=4 {
+            for level in [
+                QRCodeCorrectionLevel::L,
+                QRCodeCorrectionLevel::M,
+                QRCodeCorrectionLevel::Q,
+            ] {
+                for (mode, c) in [
+                    (QRCodeMode::Numeric, b'7'),
+                    (QRCodeMode::Alphanumeric, b'Z'),
+                    (QRCodeMode::Byte, b'z'),
+                ] {
+                    let Ok(capacity @ 1..) = mode.micro_capacity(version, level) else {
+                        continue;
+                    };
+                    let option = QRCodeOption::new(QRCodeModel::Micro, 2, level);
+                    let qrcode = QRCode::from_bytes(&vec![c; capacity], Some(option)).unwrap();
+                    assert_eq!(qrcode.version().unwrap(), version);
+
+                    let image = CodeImage::from_qrcode(&qrcode).unwrap();
+                    let modules = 9 + 2 * u32::from(version);
+                    assert_eq!(image.modules(), (modules, modules), "M{version} {level:?} {mode}");
+                    let matrix = read_modules(image.image(), 2, MICRO_QRCODE_QUIET_ZONE);
+                    // Single finder pattern
+                    assert!((0..7).all(|i| matrix.get(i, 0) && matrix.get(0, i) && matrix.get(6, i)));
+                }
+            }
+        }
+
+        let option = QRCodeOption::new(QRCodeModel::Micro, 3, QRCodeCorrectionLevel::L);
+        let qrcode = QRCode::with_eci("1", Eci::Latin1, Some(option)).unwrap();
+        assert!(CodeImage::from_qrcode(&qrcode).is_err());
+    }
+
+    #[test]
+    fn test_qrcode_structured_append_image() {
+        let header = QRCodeStructuredAppend {
+            index: 1,
+            total: 3,
+            parity: 0x5A,
+        };
+        let mut bits = Bits::new(Version::Normal(1));
+        bits.push_byte_data(b"ab").unwrap();
+        let data = qrcode_structured_append_data(header, bits, EcLevel::L).unwrap();
+
+        // Header (0011, 0001, 0010, 01011010), byte mode (0100), count (00000010), "ab",
+        // terminator and padding
+        assert_eq!(data.len(), 19);
+        assert_eq!(data[..7], [0x31, 0x25, 0xA4, 0x02, 0x61, 0x62, 0x00]);
+        assert_eq!(data[7..11], [0xEC, 0x11, 0xEC, 0x11]);
+
+        let mut bits = Bits::new(Version::Normal(1));
+        bits.push_byte_data(&[b'a'; 15]).unwrap();
+        assert!(qrcode_structured_append_data(header, bits, EcLevel::L).is_ok());
+        let mut bits = Bits::new(Version::Normal(1));
+        bits.push_byte_data(&[b'a'; 16]).unwrap();
+        assert!(qrcode_structured_append_data(header, bits, EcLevel::L).is_err());
+
+        // 7 symbols of 15 bytes (version 1-L) at most, each with the header and its part of the data
+        let data: Vec<u8> = (0..100).map(|index| b'a' + index % 26).collect();
+        let parity = data.iter().fold(0, |parity, byte| parity ^ byte);
+        let option = QRCodeOption::new(QRCodeModel::Model2, 2, QRCodeCorrectionLevel::L);
+        let qrcodes = QRCode::structured_append(&data, 1, option).unwrap();
+        assert_eq!(qrcodes.len(), 7);
+
+        let mut decoded = Vec::new();
+        for (index, qrcode) in qrcodes.iter().enumerate() {
+            let image = CodeImage::from_qrcode(qrcode).unwrap();
+            assert_eq!(image.modules(), (21, 21));
+            let codewords = read_qrcode_version_1(&read_modules(image.image(), 2, QRCODE_QUIET_ZONE));
+
+            // Structured append mode (0011), index, total - 1, parity, then byte mode (0100) and count
+            assert_eq!(codewords[0], 0x30 | index as u8, "symbol {index}");
+            assert_eq!(codewords[1], 0x60 | parity >> 4);
+            assert_eq!(codewords[2], parity << 4 | 0b0100);
+            let count = usize::from(codewords[3]);
+            decoded.extend_from_slice(&codewords[4..4 + count]);
+        }
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn test_aztec_stuff_bits() {
+        let bits = |bits: &str| -> Vec<bool> { bits.chars().filter(|c| *c != ' ').map(|c| c == 'X').collect() };
+        let words = |size: usize, bits: Vec<bool>| -> Vec<u32> {
+            bits.chunks(size)
+                .map(|word| word.iter().fold(0, |value, bit| value << 1 | u32::from(*bit)))
+                .collect()
+        };
+
+        for (size, data, expected) in [
+            (5, ".X.X. X.X.X .X.X.", ".X.X. X.X.X .X.X."),
+            (5, ".X.X. ..... .X.X", ".X.X. ....X ..X.X"),
+            (3, "XX. ... ... ..X XXX .X. ..", "XX. ..X ..X ..X ..X .XX XX. .X. ..X"),
+        ] {
+            assert_eq!(
+                aztec_stuff_bits(&bits(data), size),
+                words(size, bits(expected)),
+                "{data}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_aztec_bits() {
+        // A (Upper), L/L, b, D/L, 1
+        let expected: Vec<bool> = "00010 11100 00011 11110 0011"
+            .chars()
+            .filter(|c| *c != ' ')
+            .map(|c| c == '1')
+            .collect();
+        assert_eq!(aztec_bits(b"Ab1", None).unwrap(), expected);
+
+        // P/S FLG(1) 3, then A
+        let expected: Vec<bool> = "00000 00000 001 0101 00010"
+            .chars()
+            .filter(|c| *c != ' ')
+            .map(|c| c == '1')
+            .collect();
+        assert_eq!(aztec_bits(b"A", Some(3)).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_aztec_mode_message() {
+        for (compact, layers, words, expected) in [
+            (true, 2, 29, ".X.XXX.....XXX....X.XX..XX.X"),
+            (true, 4, 64, "XXXXXXXX.X.....X..XX.X..XX.."),
+            (false, 21, 660, "X.X...X.X..X..XX.XXX..X...XXX..X.....XXX"),
+        ] {
+            let symbol = AztecSymbol { compact, layers };
+            let expected: Vec<bool> = expected.chars().map(|c| c == 'X').collect();
+            assert_eq!(symbol.mode_message(words), expected);
+        }
+    }
+
+    #[test]
+    fn test_aztec_image() {
+        let binary: Vec<u8> = (0..=255).collect();
+        let text = "escpos-rs ".repeat(60);
+        let digits = "0123456789".repeat(300);
+
+        for (data, mode, compact, modules) in [
+            (&b"Code 2D!"[..], AztecMode::Compact(0), true, Some(15)),
+            (b"1234", AztecMode::Compact(4), true, Some(27)),
+            (
+                b"Hello, World! 2024-10-18 escpos.rs",
+                AztecMode::FullRange(0),
+                false,
+                None,
+            ),
+            (&binary, AztecMode::FullRange(0), false, None),
+            (text.as_bytes(), AztecMode::FullRange(0), false, None),
+            (b"ABC", AztecMode::FullRange(12), false, Some(67)),
+            // Reference grid lines at 16, 32, 48 and 64 modules from the center
+            (digits.as_bytes(), AztecMode::FullRange(0), false, Some(135)),
+        ] {
+            let option = AztecOption::new(mode, 2, 23).unwrap();
+            let image = CodeImage::from_aztec(&Aztec::from_bytes(data, option)).unwrap();
+            let matrix = read_modules(image.image(), 2, AZTEC_QUIET_ZONE);
+            if let Some(modules) = modules {
+                assert_eq!(image.modules(), (modules, modules));
+            }
+            assert_eq!(decode_aztec(&matrix, compact), (data.to_vec(), None), "{mode}");
+        }
+
+        let option = AztecOption::new(AztecMode::Compact(0), 2, 23).unwrap();
+        let image = CodeImage::from_aztec(&Aztec::with_eci("Crème", Eci::Latin1, option).unwrap()).unwrap();
+        let matrix = read_modules(image.image(), 2, AZTEC_QUIET_ZONE);
+        assert_eq!(decode_aztec(&matrix, true), (b"Cr\xE8me".to_vec(), Some(3)));
+
+        let option = AztecOption::new(AztecMode::Compact(1), 2, 23).unwrap();
+        assert!(CodeImage::from_aztec(&Aztec::new(&"A".repeat(20), option)).is_err());
+    }
+}
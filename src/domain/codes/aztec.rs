@@ -1,5 +1,6 @@
 //! Aztec code
 
+use super::eci::Eci;
 use crate::errors::{PrinterError, Result};
 use std::fmt;
 
@@ -107,18 +108,45 @@ impl AztecOption {
 /// Aztec code
 #[derive(Debug)]
 pub struct Aztec {
-    pub data: String,
+    pub data: Vec<u8>,
     pub option: AztecOption,
+    /// Character set of text data
+    pub eci: Option<Eci>,
 }
 
 impl Aztec {
-    /// Create a new `Aztec`
+    /// Create a new `Aztec` (UTF-8 text)
     pub fn new(data: &str, option: AztecOption) -> Self {
+        Self::from_bytes(data.as_bytes(), option)
+    }
+
+    /// Create a new `Aztec` from binary data
+    pub fn from_bytes(data: &[u8], option: AztecOption) -> Self {
         Self {
-            data: data.to_string(),
+            data: data.to_vec(),
             option,
+            eci: None,
         }
     }
+
+    /// Create a new `Aztec` with text encoded in a character set
+    ///
+    /// The ECI designator is encoded in the symbol, Aztec code is printed as image (`codes_2d_image` feature).
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let code = Aztec::with_eci("Café", Eci::Latin1, AztecOption::default())?;
+    ///
+    /// assert_eq!(code.data, vec![b'C', b'a', b'f', 0xE9]);
+    /// assert!(Aztec::with_eci("€", Eci::Latin1, AztecOption::default()).is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn with_eci(data: &str, eci: Eci, option: AztecOption) -> Result<Self> {
+        let mut code = Self::from_bytes(&eci.encode(data)?, option);
+        code.eci = Some(eci);
+        Ok(code)
+    }
 }
 
 #[cfg(test)]
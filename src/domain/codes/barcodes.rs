@@ -3,6 +3,7 @@
 #![cfg(feature = "barcodes")]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+use super::gs1::{gs1_check_digit, GS1Data, GS1_FNC1};
 use crate::errors::{PrinterError, Result};
 use std::fmt;
 
@@ -10,13 +11,27 @@ const CODE39_VALID_CHARS: [char; 44] = [
     '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '$', '%', '*', '+', '-', '.', '/', 'A', 'B', 'C', 'D', 'E', 'F',
     'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', ' ',
 ];
+/// Code 39 characters in the order of their modulo 43 check value
+const CODE39_CHECK_CHARS: [char; 43] = [
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M',
+    'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '-', '.', ' ', '$', '/', '+', '%',
+];
 const CODABAR_VALID_CHARS: [char; 24] = [
     '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'a', 'b', 'c', 'd', '$', '+', '-', '.', '/',
     ':',
 ];
 
-/// Barcode system (function A used)
-#[derive(Debug, Clone, Copy)]
+/// Maximum data length of a barcode printed with function B (`GS k m n`)
+const BARCODE_FUNCTION_B_MAX_LENGTH: usize = 255;
+/// Maximum module width accepted by `GS w` (2 - 6 on most printers, up to 68 on some)
+pub(crate) const BARCODE_MAX_MODULE_WIDTH: u8 = 68;
+
+/// Barcode system
+///
+/// `CODE93`, `CODE128`, `GS1CODE128` and `GS1DataBar` are only available with [function B](BarcodeFunction::B).
+///
+/// GS1 systems data are Application Identifiers in parentheses (see [`GS1Data`]).
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum BarcodeSystem {
     UPCA,
     UPCE,
@@ -25,19 +40,152 @@ pub enum BarcodeSystem {
     CODE39,
     ITF,
     CODABAR,
+    CODE93,
+    CODE128(Code128CodeSet),
+    /// GS1-128 (CODE128 starting with FNC1)
+    GS1CODE128,
+    /// Linear GS1 DataBar
+    GS1DataBar(GS1DataBarType),
+}
+
+impl BarcodeSystem {
+    /// Get the `GS k` m value of a function
+    ///
+    /// Returns `None` if the system is not available with the function.
+    pub(crate) fn m(&self, function: BarcodeFunction) -> Option<u8> {
+        let function_a = match self {
+            BarcodeSystem::UPCA => Some(0),
+            BarcodeSystem::UPCE => Some(1),
+            BarcodeSystem::EAN13 => Some(2),
+            BarcodeSystem::EAN8 => Some(3),
+            BarcodeSystem::CODE39 => Some(4),
+            BarcodeSystem::ITF => Some(5),
+            BarcodeSystem::CODABAR => Some(6),
+            _ => None,
+        };
+
+        match (function, self) {
+            (BarcodeFunction::A, _) => function_a,
+            (BarcodeFunction::B, BarcodeSystem::CODE93) => Some(72),
+            (BarcodeFunction::B, BarcodeSystem::CODE128(_) | BarcodeSystem::GS1CODE128) => Some(73),
+            (BarcodeFunction::B, BarcodeSystem::GS1DataBar(code_type)) => Some((*code_type).into()),
+            (BarcodeFunction::B, _) => function_a.map(|m| m + 65),
+        }
+    }
+
+    /// Get the default function (A when available)
+    pub(crate) fn default_function(&self) -> BarcodeFunction {
+        match self.m(BarcodeFunction::A) {
+            Some(_) => BarcodeFunction::A,
+            None => BarcodeFunction::B,
+        }
+    }
+
+    /// Get the data sent to the printer
+    pub(crate) fn encode(&self, data: &str) -> Result<Vec<u8>> {
+        match self {
+            BarcodeSystem::CODE128(code_set) => code128_encode(data, *code_set),
+            BarcodeSystem::GS1CODE128 => {
+                // Separators are the GS character, which is not a valid GS1 data character
+                let gs1 = GS1Data::parse(data)?.concatenated("\x1D");
+                let inputs: Vec<Code128Input> = std::iter::once(Code128Input::Fnc1)
+                    .chain(gs1.bytes().map(|c| match c {
+                        0x1D => Code128Input::Fnc1,
+                        c => Code128Input::Char(c),
+                    }))
+                    .collect();
+                code128_encode_inputs(&inputs, Code128CodeSet::Auto, data)
+            }
+            BarcodeSystem::GS1DataBar(code_type) => code_type.encode(&GS1Data::parse(data)?),
+            _ => Ok(data.as_bytes().to_vec()),
+        }
+    }
+
+    /// Estimate the barcode width in dots for a module width, quiet zones excluded
+    ///
+    /// Wide elements (CODE39, ITF, CODABAR) are estimated with a 2.5:1 ratio.
+    /// Returns `None` if the width cannot be estimated (GS1 DataBar Expanded or invalid data).
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// // 95 modules of 3 dots
+    /// assert_eq!(BarcodeSystem::EAN13.estimated_width("0124032587466", 3), Some(285));
+    /// // Start, 4 symbols, check and stop symbols
+    /// assert_eq!(BarcodeSystem::CODE128(Code128CodeSet::B).estimated_width("ab12", 2), Some(158));
+    /// ```
+    pub fn estimated_width(&self, data: &str, module_width: u8) -> Option<u32> {
+        let narrow = u32::from(module_width);
+        let wide = (5 * narrow).div_ceil(2);
+
+        let dots = match self {
+            BarcodeSystem::UPCA | BarcodeSystem::EAN13 => 95 * narrow,
+            BarcodeSystem::UPCE => 51 * narrow,
+            BarcodeSystem::EAN8 => 67 * narrow,
+            BarcodeSystem::CODE39 => {
+                // 6 narrow and 3 wide elements, characters are separated by a narrow gap
+                let stars = if data.starts_with('*') { 0 } else { 2 };
+                let characters = (data.chars().count() + stars) as u32;
+                characters * (7 * narrow + 3 * wide) - narrow
+            }
+            BarcodeSystem::ITF => {
+                // 6 narrow and 4 wide elements per digit pair, start (4 narrow) and stop (2 narrow, 1 wide)
+                let pairs = (data.len() / 2) as u32;
+                pairs * (6 * narrow + 4 * wide) + 6 * narrow + wide
+            }
+            BarcodeSystem::CODABAR => {
+                // Start and stop characters (A - D, 3 wide elements) are counted when missing
+                let stops = if data.starts_with(['A', 'B', 'C', 'D', 'a', 'b', 'c', 'd']) {
+                    0
+                } else {
+                    2
+                };
+                let elements: u32 = data
+                    .chars()
+                    .map(|c| match c {
+                        '0'..='9' | '-' | '$' => 5 * narrow + 2 * wide,
+                        _ => 4 * narrow + 3 * wide,
+                    })
+                    .sum();
+                let characters = data.chars().count() as u32 + stops;
+                elements + stops * (4 * narrow + 3 * wide) + (characters - 1) * narrow
+            }
+            BarcodeSystem::CODE93 => {
+                // Characters outside the 43 basic characters are encoded with a shift character
+                let characters: u32 = data
+                    .chars()
+                    .map(|c| if CODE39_CHECK_CHARS.contains(&c) { 1 } else { 2 })
+                    .sum();
+                // Start, 2 check characters and stop of 9 modules, termination bar
+                ((characters + 4) * 9 + 1) * narrow
+            }
+            BarcodeSystem::CODE128(_) | BarcodeSystem::GS1CODE128 => {
+                // Start and code set changes are sent as `{A`, `{B` or `{C`, `{` is escaped as `{{`
+                let encoded = self.encode(data).ok()?;
+                let mut symbols = 0;
+                let mut bytes = encoded.iter();
+                while let Some(byte) = bytes.next() {
+                    if *byte == b'{' {
+                        bytes.next();
+                    }
+                    symbols += 1;
+                }
+                // Check symbol (11 modules) and stop symbol (13 modules)
+                (symbols * 11 + 24) * narrow
+            }
+            BarcodeSystem::GS1DataBar(GS1DataBarType::Omnidirectional | GS1DataBarType::Truncated) => 96 * narrow,
+            BarcodeSystem::GS1DataBar(GS1DataBarType::Limited) => 79 * narrow,
+            BarcodeSystem::GS1DataBar(GS1DataBarType::Expanded) => return None,
+        };
+
+        Some(dots)
+    }
 }
 
 impl From<BarcodeSystem> for u8 {
+    /// `GS k` m value of the default function
     fn from(value: BarcodeSystem) -> Self {
-        match value {
-            BarcodeSystem::UPCA => 0,
-            BarcodeSystem::UPCE => 1,
-            BarcodeSystem::EAN13 => 2,
-            BarcodeSystem::EAN8 => 3,
-            BarcodeSystem::CODE39 => 4,
-            BarcodeSystem::ITF => 5,
-            BarcodeSystem::CODABAR => 6,
-        }
+        value.m(value.default_function()).unwrap_or_default()
     }
 }
 
@@ -51,8 +199,266 @@ impl fmt::Display for BarcodeSystem {
             BarcodeSystem::CODE39 => write!(f, "CODE39"),
             BarcodeSystem::ITF => write!(f, "ITF"),
             BarcodeSystem::CODABAR => write!(f, "CODABAR"),
+            BarcodeSystem::CODE93 => write!(f, "CODE93"),
+            BarcodeSystem::CODE128(_) => write!(f, "CODE128"),
+            BarcodeSystem::GS1CODE128 => write!(f, "GS1-128"),
+            BarcodeSystem::GS1DataBar(code_type) => write!(f, "{code_type}"),
+        }
+    }
+}
+
+/// Linear GS1 DataBar type
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum GS1DataBarType {
+    /// GTIN only
+    #[default]
+    Omnidirectional,
+    /// GTIN only, reduced height
+    Truncated,
+    /// GTIN only, starting with 0 or 1
+    Limited,
+    /// Any Application Identifiers
+    Expanded,
+}
+
+impl GS1DataBarType {
+    /// Get the data sent to the printer
+    ///
+    /// The GTIN is sent without its check digit, the printer adds it with the (01) AI.
+    fn encode(&self, gs1: &GS1Data) -> Result<Vec<u8>> {
+        if *self == GS1DataBarType::Expanded {
+            return Ok(gs1.concatenated(GS1_FNC1).into_bytes());
+        }
+
+        let gtin = gs1
+            .gtin_only()
+            .ok_or_else(|| PrinterError::Input(format!("{self} data must only contain a GTIN (01): {gs1}")))?;
+        if *self == GS1DataBarType::Limited && !gtin.starts_with(['0', '1']) {
+            return Err(PrinterError::Input(format!(
+                "{self} GTIN must start with 0 or 1: {gtin}"
+            )));
+        }
+
+        Ok(gtin.as_bytes().to_vec())
+    }
+}
+
+impl From<GS1DataBarType> for u8 {
+    fn from(value: GS1DataBarType) -> Self {
+        match value {
+            GS1DataBarType::Omnidirectional => 75,
+            GS1DataBarType::Truncated => 76,
+            GS1DataBarType::Limited => 77,
+            GS1DataBarType::Expanded => 78,
+        }
+    }
+}
+
+impl fmt::Display for GS1DataBarType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GS1DataBarType::Omnidirectional => write!(f, "GS1 DataBar Omnidirectional"),
+            GS1DataBarType::Truncated => write!(f, "GS1 DataBar Truncated"),
+            GS1DataBarType::Limited => write!(f, "GS1 DataBar Limited"),
+            GS1DataBarType::Expanded => write!(f, "GS1 DataBar Expanded"),
+        }
+    }
+}
+
+/// Barcode function (`GS k` command form)
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum BarcodeFunction {
+    /// NUL-terminated data (`GS k m d1...dk NUL`, m = 0 - 6)
+    #[default]
+    A,
+    /// Length-prefixed data (`GS k m n d1...dn`, m = 65 - 73)
+    B,
+}
+
+impl fmt::Display for BarcodeFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BarcodeFunction::A => write!(f, "Barcode function A"),
+            BarcodeFunction::B => write!(f, "Barcode function B"),
+        }
+    }
+}
+
+/// CODE128 code set
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Code128CodeSet {
+    /// Switch between code sets to get the shortest barcode
+    #[default]
+    Auto,
+    /// Upper case letters, digits, punctuation and control characters (ASCII 0 - 95)
+    A,
+    /// Upper and lower case letters, digits and punctuation (ASCII 32 - 127)
+    B,
+    /// Pairs of digits
+    C,
+}
+
+impl fmt::Display for Code128CodeSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Code128CodeSet::Auto => write!(f, "CODE128 automatic code set"),
+            Code128CodeSet::A => write!(f, "CODE128 code set A"),
+            Code128CodeSet::B => write!(f, "CODE128 code set B"),
+            Code128CodeSet::C => write!(f, "CODE128 code set C"),
+        }
+    }
+}
+
+impl Code128CodeSet {
+    /// Code sets which can be selected, in order of preference when lengths are equal (B first)
+    const SETS: [Code128CodeSet; 3] = [Code128CodeSet::B, Code128CodeSet::A, Code128CodeSet::C];
+
+    /// Get the slot of a code set in the shortest path tables (A: 0, B: 1, C: 2), unrelated to the `SETS` order
+    fn index(&self) -> usize {
+        match self {
+            Code128CodeSet::A | Code128CodeSet::Auto => 0,
+            Code128CodeSet::B => 1,
+            Code128CodeSet::C => 2,
+        }
+    }
+
+    /// Get the code set selection sent before the data (`{A`, `{B` or `{C`)
+    fn selection(&self) -> [u8; 2] {
+        match self {
+            Code128CodeSet::A | Code128CodeSet::Auto => *b"{A",
+            Code128CodeSet::B => *b"{B",
+            Code128CodeSet::C => *b"{C",
+        }
+    }
+
+    /// Get the number of inputs of `data` encoded by one symbol of the code set, if possible
+    fn symbol_length(&self, data: &[Code128Input]) -> Option<usize> {
+        use Code128Input::*;
+
+        match (self, data) {
+            (_, [Fnc1, ..]) => Some(1),
+            (Code128CodeSet::C, [Char(d1), Char(d2), ..]) if d1.is_ascii_digit() && d2.is_ascii_digit() => Some(2),
+            (Code128CodeSet::A | Code128CodeSet::Auto, [Char(c), ..]) if *c <= 95 => Some(1),
+            (Code128CodeSet::B, [Char(c), ..]) if (32..=127).contains(c) => Some(1),
+            _ => None,
+        }
+    }
+}
+
+/// CODE128 input
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Code128Input {
+    /// ASCII character
+    Char(u8),
+    /// Function 1 character (`{1`), used by GS1-128
+    Fnc1,
+}
+
+/// Encode CODE128 data for `GS k 73`
+///
+/// The data starts with the code set selection, `{` is escaped as `{{` and
+/// code set C digit pairs are sent as one byte (0 - 99).
+/// With [`Code128CodeSet::Auto`], the code sets minimizing the number of symbols are selected.
+fn code128_encode(data: &str, code_set: Code128CodeSet) -> Result<Vec<u8>> {
+    let inputs: Vec<Code128Input> = data.bytes().map(Code128Input::Char).collect();
+    code128_encode_inputs(&inputs, code_set, data)
+}
+
+/// Encode CODE128 inputs for `GS k 73`, `data` is only used in error messages
+pub(crate) fn code128_encode_inputs(inputs: &[Code128Input], code_set: Code128CodeSet, data: &str) -> Result<Vec<u8>> {
+    if inputs.is_empty() {
+        return Err(PrinterError::Input("CODE128 data cannot be empty".to_owned()));
+    }
+    if let Some(position) = inputs
+        .iter()
+        .position(|input| matches!(input, Code128Input::Char(c) if !c.is_ascii()))
+    {
+        return Err(PrinterError::Input(format!(
+            "invalid CODE128 character at position {position}: {data}"
+        )));
+    }
+
+    let sets: Vec<Code128CodeSet> = match code_set {
+        Code128CodeSet::Auto => Code128CodeSet::SETS.to_vec(),
+        code_set => vec![code_set],
+    };
+
+    // Shortest path: `costs[i][set]` is the number of symbols (code set changes included)
+    // to encode `inputs[..i]` ending with `set`, `previous[i][set]` is the previous step
+    let len = inputs.len();
+    let mut costs = vec![[usize::MAX; 3]; len + 1];
+    let mut previous: Vec<[Option<(usize, Code128CodeSet)>; 3]> = vec![[None; 3]; len + 1];
+    for set in &sets {
+        costs[0][set.index()] = 0;
+    }
+
+    for i in 0..len {
+        // Code set changes
+        for from in &sets {
+            for to in &sets {
+                let cost = costs[i][from.index()].saturating_add(1);
+                if cost < costs[i][to.index()] {
+                    costs[i][to.index()] = cost;
+                    previous[i][to.index()] = Some((i, *from));
+                }
+            }
+        }
+
+        for set in &sets {
+            if let Some(length) = set.symbol_length(&inputs[i..]) {
+                let cost = costs[i][set.index()].saturating_add(1);
+                if cost < costs[i + length][set.index()] {
+                    costs[i + length][set.index()] = cost;
+                    previous[i + length][set.index()] = Some((i, *set));
+                }
+            }
+        }
+    }
+
+    let Some(mut set) = sets
+        .iter()
+        .filter(|set| costs[len][set.index()] != usize::MAX)
+        .min_by_key(|set| costs[len][set.index()])
+        .copied()
+    else {
+        let position = (0..len)
+            .rev()
+            .find(|&i| sets.iter().any(|set| costs[i][set.index()] != usize::MAX))
+            .unwrap_or_default();
+        return Err(PrinterError::Input(format!(
+            "invalid {code_set} character at position {position}: {data}"
+        )));
+    };
+
+    // Walk back the path: (position, code set) of each symbol
+    let mut steps = Vec::new();
+    let mut i = len;
+    while let Some((from, from_set)) = previous[i][set.index()] {
+        if from != i {
+            steps.push((from, set));
         }
+        (i, set) = (from, from_set);
     }
+    steps.reverse();
+
+    let mut encoded = Vec::with_capacity(2 * len + 2);
+    let mut current = None;
+    for (position, set) in steps {
+        if current != Some(set) {
+            encoded.extend(set.selection());
+            current = Some(set);
+        }
+        match (set, inputs[position], inputs.get(position + 1)) {
+            (_, Code128Input::Fnc1, _) => encoded.extend(b"{1"),
+            (Code128CodeSet::C, Code128Input::Char(d1), Some(Code128Input::Char(d2))) => {
+                encoded.push((d1 - b'0') * 10 + d2 - b'0')
+            }
+            (_, Code128Input::Char(b'{'), _) => encoded.extend(b"{{"),
+            (_, Code128Input::Char(c), _) => encoded.push(c),
+        }
+    }
+
+    Ok(encoded)
 }
 
 /// Barcode fonts
@@ -134,8 +540,8 @@ impl fmt::Display for BarcodePosition {
     }
 }
 
-/// Barcode width
-#[derive(Debug, Default, Clone, Copy)]
+/// Barcode width (module width in dots)
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub enum BarcodeWidth {
     XS,
     S,
@@ -143,6 +549,8 @@ pub enum BarcodeWidth {
     M,
     L,
     XL,
+    /// Exact module width in dots (1 - 68, most printers accept 2 - 6)
+    Dots(u8),
 }
 
 impl From<BarcodeWidth> for u8 {
@@ -153,11 +561,13 @@ impl From<BarcodeWidth> for u8 {
             BarcodeWidth::M => 3,
             BarcodeWidth::L => 4,
             BarcodeWidth::XL => 5,
+            BarcodeWidth::Dots(dots) => dots,
         }
     }
 }
 
 impl From<&str> for BarcodeWidth {
+    /// Preset name (`"XS"` - `"XL"`) or number of dots
     fn from(value: &str) -> Self {
         match value {
             "XS" => Self::XS,
@@ -165,13 +575,13 @@ impl From<&str> for BarcodeWidth {
             "M" => Self::M,
             "L" => Self::L,
             "XL" => Self::XL,
-            _ => Self::default(),
+            _ => value.parse().map(Self::Dots).unwrap_or_default(),
         }
     }
 }
 
-/// Barcode height
-#[derive(Debug, Default, Clone, Copy)]
+/// Barcode height (in dots)
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub enum BarcodeHeight {
     XS,
     #[default]
@@ -179,6 +589,8 @@ pub enum BarcodeHeight {
     M,
     L,
     XL,
+    /// Exact height in dots (1 - 255)
+    Dots(u8),
 }
 
 impl From<BarcodeHeight> for u8 {
@@ -189,11 +601,13 @@ impl From<BarcodeHeight> for u8 {
             BarcodeHeight::M => 153,
             BarcodeHeight::L => 204,
             BarcodeHeight::XL => 255,
+            BarcodeHeight::Dots(dots) => dots,
         }
     }
 }
 
 impl From<&str> for BarcodeHeight {
+    /// Preset name (`"XS"` - `"XL"`) or number of dots
     fn from(value: &str) -> Self {
         match value {
             "XS" => Self::XS,
@@ -201,7 +615,7 @@ impl From<&str> for BarcodeHeight {
             "M" => Self::M,
             "L" => Self::L,
             "XL" => Self::XL,
-            _ => Self::default(),
+            _ => value.parse().map(Self::Dots).unwrap_or_default(),
         }
     }
 }
@@ -213,6 +627,8 @@ pub struct BarcodeOption {
     height: BarcodeHeight,
     font: BarcodeFont,
     position: BarcodePosition,
+    function: Option<BarcodeFunction>,
+    check_digit: bool,
 }
 
 impl Default for BarcodeOption {
@@ -222,6 +638,8 @@ impl Default for BarcodeOption {
             height: BarcodeHeight::default(),
             font: BarcodeFont::A,
             position: BarcodePosition::Below,
+            function: None,
+            check_digit: false,
         }
     }
 }
@@ -234,9 +652,46 @@ impl BarcodeOption {
             height,
             font,
             position,
+            function: None,
+            check_digit: false,
         }
     }
 
+    /// Use the length-prefixed form of `GS k` (function B, m = 65 - 73)
+    ///
+    /// Function A is used by default for the systems supporting it.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = BarcodeOption::default().function_b();
+    ///
+    /// assert_eq!(option.function(), Some(BarcodeFunction::B));
+    /// ```
+    pub fn function_b(mut self) -> Self {
+        self.function = Some(BarcodeFunction::B);
+        self
+    }
+
+    /// Add the optional check digit: modulo 43 for CODE39, modulo 10 for ITF
+    ///
+    /// ITF data with an odd number of digits gets a check digit, the last digit of even data is verified.
+    /// EAN and UPC check digits are always added (or verified).
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = BarcodeOption::default().check_digit();
+    ///
+    /// assert_eq!(Barcode::new(BarcodeSystem::CODE39, "CODE39", option.clone())?.data, "CODE39W");
+    /// assert_eq!(Barcode::new(BarcodeSystem::ITF, "1234567", option)?.data, "12345670");
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn check_digit(mut self) -> Self {
+        self.check_digit = true;
+        self
+    }
+
     /// Get width
     pub fn width(&self) -> BarcodeWidth {
         self.width
@@ -256,6 +711,16 @@ impl BarcodeOption {
     pub fn position(&self) -> BarcodePosition {
         self.position
     }
+
+    /// Is the optional check digit added?
+    pub fn has_check_digit(&self) -> bool {
+        self.check_digit
+    }
+
+    /// Get function (system default if not set)
+    pub fn function(&self) -> Option<BarcodeFunction> {
+        self.function
+    }
 }
 
 /// Barcode
@@ -268,9 +733,42 @@ pub struct Barcode {
 
 impl Barcode {
     /// Create a new `Barcode`
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// assert!(Barcode::new(BarcodeSystem::CODE128(Code128CodeSet::Auto), "Order-42a", BarcodeOption::default()).is_ok());
+    /// assert!(Barcode::new(BarcodeSystem::CODE128(Code128CodeSet::C), "12345", BarcodeOption::default()).is_err());
+    /// ```
     pub fn new(system: BarcodeSystem, data: &str, option: BarcodeOption) -> Result<Self> {
+        let width = u8::from(option.width);
+        if !(1..=BARCODE_MAX_MODULE_WIDTH).contains(&width) {
+            return Err(PrinterError::Input(format!(
+                "invalid barcode module width (1 - {BARCODE_MAX_MODULE_WIDTH}): {width}"
+            )));
+        }
+        if u8::from(option.height) == 0 {
+            return Err(PrinterError::Input("barcode height cannot be equal to 0".to_owned()));
+        }
+
+        let data = &Self::complete(system, data, &option)?;
         Self::validate(system, data)?;
 
+        let function = option.function.unwrap_or(system.default_function());
+        if function == BarcodeFunction::B {
+            let length = system.encode(data)?.len();
+            if length > BARCODE_FUNCTION_B_MAX_LENGTH {
+                return Err(PrinterError::Input(format!(
+                    "{system} data is too long ({length} bytes, max {BARCODE_FUNCTION_B_MAX_LENGTH})"
+                )));
+            }
+            if system == BarcodeSystem::ITF && !data.len().is_multiple_of(2) {
+                return Err(PrinterError::Input(format!(
+                    "ITF data must have an even number of digits with function B: {data}"
+                )));
+            }
+        }
+
         Ok(Self {
             system,
             data: data.to_string(),
@@ -278,66 +776,279 @@ impl Barcode {
         })
     }
 
+    /// Create a new GS1 `Barcode` ([`BarcodeSystem::GS1CODE128`] or [`BarcodeSystem::GS1DataBar`])
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let data = GS1Data::new().gtin("0950110153000")?.net_weight_kg(1250, 3)?;
+    /// let barcode = Barcode::gs1(BarcodeSystem::GS1CODE128, &data, BarcodeOption::default())?;
+    ///
+    /// assert_eq!(barcode.data, "(01)09501101530003(3103)001250");
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn gs1(system: BarcodeSystem, data: &GS1Data, option: BarcodeOption) -> Result<Self> {
+        if !matches!(system, BarcodeSystem::GS1CODE128 | BarcodeSystem::GS1DataBar(_)) {
+            return Err(PrinterError::Input(format!("{system} is not a GS1 barcode")));
+        }
+        Self::new(system, &data.to_string(), option)
+    }
+
+    /// Estimate the barcode width in dots with the option module width (see [`BarcodeSystem::estimated_width`])
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = BarcodeOption::new(BarcodeWidth::Dots(4), BarcodeHeight::Dots(80), BarcodeFont::A, BarcodePosition::Below);
+    /// let barcode = Barcode::new(BarcodeSystem::EAN8, "9857458", option)?;
+    ///
+    /// assert_eq!(barcode.estimated_width(), Some(268));
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn estimated_width(&self) -> Option<u32> {
+        self.system.estimated_width(&self.data, self.option.width.into())
+    }
+
     /// Validate data
+    ///
+    /// Check digits of full length EAN, UPC and ITF data are verified.
     fn validate(system: BarcodeSystem, data: &str) -> Result<()> {
-        let data_len = data.len();
-        let is_data_all_digits = data.chars().all(|c| c.is_ascii_digit());
+        let invalid = |reason: String| PrinterError::Input(format!("invalid {system} data {data}: {reason}"));
+        let digits_only = || check_digits_only(data).map_err(invalid);
+        let lengths = |lengths: &[usize]| {
+            if lengths.contains(&data.len()) {
+                Ok(())
+            } else {
+                let lengths: Vec<String> = lengths.iter().map(|l| l.to_string()).collect();
+                Err(invalid(format!("{} digits expected", lengths.join(", "))))
+            }
+        };
 
         match system {
-            BarcodeSystem::UPCA => {
-                if is_data_all_digits && [11, 12].contains(&data_len) {
-                    Ok(())
-                } else {
-                    Err(PrinterError::Input(format!("invalid UPC-A data: {data}")))
+            BarcodeSystem::UPCA | BarcodeSystem::EAN8 | BarcodeSystem::EAN13 => {
+                let length = match system {
+                    BarcodeSystem::UPCA => 12,
+                    BarcodeSystem::EAN8 => 8,
+                    _ => 13,
+                };
+                digits_only()?;
+                lengths(&[length - 1, length])?;
+                if data.len() == length {
+                    verify_check_digit(data).map_err(invalid)?;
                 }
+                Ok(())
             }
             BarcodeSystem::UPCE => {
-                if is_data_all_digits && [6, 7, 8, 11, 12].contains(&data_len) && data_len == 6
-                    || data.chars().nth(0) == Some('0')
-                {
-                    Ok(())
-                } else {
-                    Err(PrinterError::Input(format!("invalid UPC-E data: {data}")))
-                }
-            }
-            BarcodeSystem::EAN8 => {
-                if is_data_all_digits && [7, 8].contains(&data_len) {
-                    Ok(())
-                } else {
-                    Err(PrinterError::Input(format!("invalid EAN8 data: {data}")))
+                digits_only()?;
+                lengths(&[6, 7, 8, 11, 12])?;
+                if data.len() > 6 && !data.starts_with('0') {
+                    return Err(invalid("number system at position 0 must be 0".to_owned()));
                 }
-            }
-            BarcodeSystem::EAN13 => {
-                if is_data_all_digits && [12, 13].contains(&data_len) {
-                    Ok(())
-                } else {
-                    Err(PrinterError::Input(format!("invalid EAN13 data: {data}")))
+                match data.len() {
+                    8 => upce_to_upca(data).map(|_| ()),
+                    11 | 12 => upca_to_upce(data).map(|_| ()),
+                    _ => Ok(()),
                 }
             }
             BarcodeSystem::ITF => {
-                if data_len >= 2 && is_data_all_digits {
-                    Ok(())
-                } else {
-                    Err(PrinterError::Input(format!("invalid ITF data: {data}")))
+                digits_only()?;
+                if data.len() < 2 {
+                    return Err(invalid("at least 2 digits expected".to_owned()));
                 }
+                Ok(())
             }
             BarcodeSystem::CODE39 => {
-                if data_len >= 1 && data.chars().all(|c| CODE39_VALID_CHARS.contains(&c)) {
-                    Ok(())
-                } else {
-                    Err(PrinterError::Input(format!("invalid CODE39 data: {data}")))
+                if data.is_empty() {
+                    return Err(invalid("data cannot be empty".to_owned()));
+                }
+                match data.chars().position(|c| !CODE39_VALID_CHARS.contains(&c)) {
+                    Some(position) => Err(invalid(format!("invalid character at position {position}"))),
+                    None => Ok(()),
                 }
             }
             BarcodeSystem::CODABAR => {
                 // (However, d1 = 65 – 68, dk = 65 – 68, d1 = 97 – 100, dk = 97 – 100)
-                if data_len >= 2 && data.chars().all(|c| CODABAR_VALID_CHARS.contains(&c)) {
-                    Ok(())
+                if data.len() < 2 {
+                    return Err(invalid("at least 2 characters expected".to_owned()));
+                }
+                match data.chars().position(|c| !CODABAR_VALID_CHARS.contains(&c)) {
+                    Some(position) => Err(invalid(format!("invalid character at position {position}"))),
+                    None => Ok(()),
+                }
+            }
+            BarcodeSystem::CODE93 => match data.chars().position(|c| !c.is_ascii()) {
+                _ if data.is_empty() => Err(PrinterError::Input("CODE93 data cannot be empty".to_owned())),
+                Some(position) => Err(PrinterError::Input(format!(
+                    "invalid CODE93 character at position {position}: {data}"
+                ))),
+                None => Ok(()),
+            },
+            BarcodeSystem::CODE128(_) | BarcodeSystem::GS1CODE128 | BarcodeSystem::GS1DataBar(_) => {
+                system.encode(data).map(|_| ())
+            }
+        }
+    }
+
+    /// Append the check digit when the data is one digit short
+    /// and the optional check digits enabled in the option, the result is validated afterwards
+    fn complete(system: BarcodeSystem, data: &str, option: &BarcodeOption) -> Result<String> {
+        let invalid = |reason: String| PrinterError::Input(format!("invalid {system} data {data}: {reason}"));
+
+        let data = match (system, data.len()) {
+            (BarcodeSystem::UPCA, 11)
+            | (BarcodeSystem::EAN8, 7)
+            | (BarcodeSystem::EAN13, 12)
+            | (BarcodeSystem::UPCE, 11) => {
+                check_digits_only(data).map_err(invalid)?;
+                format!("{data}{}", gs1_check_digit(data)?)
+            }
+            (BarcodeSystem::UPCE, 7) => upca_to_upce(&upce_to_upca(data)?)?,
+            (BarcodeSystem::ITF, length) if option.check_digit && length > 0 => {
+                check_digits_only(data).map_err(invalid)?;
+                if length.is_multiple_of(2) {
+                    verify_check_digit(data).map_err(invalid)?;
+                    data.to_owned()
                 } else {
-                    Err(PrinterError::Input(format!("invalid CODABAR data: {data}")))
+                    format!("{data}{}", gs1_check_digit(data)?)
                 }
             }
+            (BarcodeSystem::CODE39, _) if option.check_digit => code39_with_check_character(data).map_err(invalid)?,
+            _ => data.to_owned(),
+        };
+
+        Ok(data)
+    }
+}
+
+/// Check that data only contains digits
+fn check_digits_only(data: &str) -> std::result::Result<(), String> {
+    match data.chars().position(|c| !c.is_ascii_digit()) {
+        Some(position) => Err(format!("not a digit at position {position}")),
+        None => Ok(()),
+    }
+}
+
+/// Verify the modulo 10 check digit (last digit)
+fn verify_check_digit(data: &str) -> std::result::Result<(), String> {
+    let position = data.len() - 1;
+    let expected = gs1_check_digit(&data[..position]).map_err(|e| e.to_string())?;
+    if data.as_bytes()[position] - b'0' != expected {
+        return Err(format!("wrong check digit at position {position}, expected {expected}"));
+    }
+    Ok(())
+}
+
+/// Add the Code 39 modulo 43 check character, before the stop character (`*`) if any
+fn code39_with_check_character(data: &str) -> std::result::Result<String, String> {
+    let (start, content, stop) = match (data.strip_prefix('*'), data.strip_suffix('*')) {
+        (Some(_), Some(_)) if data.len() >= 2 => ("*", &data[1..data.len() - 1], "*"),
+        _ => ("", data, ""),
+    };
+
+    let mut sum = 0;
+    for (position, c) in content.chars().enumerate() {
+        let value = CODE39_CHECK_CHARS.iter().position(|&check| check == c).ok_or_else(|| {
+            format!(
+                "invalid character for the check character at position {}",
+                position + start.len()
+            )
+        })?;
+        sum += value;
+    }
+
+    Ok(format!("{start}{content}{}{stop}", CODE39_CHECK_CHARS[sum % 43]))
+}
+
+/// Expand UPC-E data to UPC-A (12 digits with check digit)
+///
+/// `upce` is 6 digits (number system 0), 7 digits (with the number system)
+/// or 8 digits (with the number system and the check digit, which is verified).
+///
+/// ```
+/// use escpos::utils::*;
+///
+/// assert_eq!(upce_to_upca("0425261")?, "042100005264");
+/// assert_eq!(upce_to_upca("04252614")?, "042100005264");
+/// assert!(upce_to_upca("04252615").is_err());
+/// # Ok::<(), escpos::errors::PrinterError>(())
+/// ```
+pub fn upce_to_upca(upce: &str) -> Result<String> {
+    let invalid = |reason: String| PrinterError::Input(format!("invalid UPC-E data {upce}: {reason}"));
+    check_digits_only(upce).map_err(invalid)?;
+
+    let (number_system, digits) = match upce.len() {
+        6 => ("0", upce),
+        7 | 8 => (&upce[..1], &upce[1..7]),
+        _ => return Err(invalid("6, 7 or 8 digits expected".to_owned())),
+    };
+    if !["0", "1"].contains(&number_system) {
+        return Err(invalid("number system at position 0 must be 0 or 1".to_owned()));
+    }
+
+    let d = |range: std::ops::Range<usize>| &digits[range];
+    let upca = match digits.as_bytes()[5] {
+        b'0'..=b'2' => format!("{number_system}{}{}0000{}", d(0..2), d(5..6), d(2..5)),
+        b'3' => format!("{number_system}{}00000{}", d(0..3), d(3..5)),
+        b'4' => format!("{number_system}{}00000{}", d(0..4), d(4..5)),
+        _ => format!("{number_system}{}0000{}", d(0..5), d(5..6)),
+    };
+    let upca = format!("{upca}{}", gs1_check_digit(&upca)?);
+
+    if upce.len() == 8 && upce.as_bytes()[7] != upca.as_bytes()[11] {
+        return Err(invalid(format!(
+            "wrong check digit at position 7, expected {}",
+            upca.as_bytes()[11] as char
+        )));
+    }
+
+    Ok(upca)
+}
+
+/// Compress UPC-A data to UPC-E (8 digits with number system and check digit)
+///
+/// `upca` is 11 digits or 12 digits (with the check digit, which is verified),
+/// only some UPC-A codes with the number system 0 or 1 can be compressed.
+///
+/// ```
+/// use escpos::utils::*;
+///
+/// assert_eq!(upca_to_upce("04210000526")?, "04252614");
+/// assert!(upca_to_upce("04210000126").is_ok());
+/// assert!(upca_to_upce("04211000526").is_err());
+/// # Ok::<(), escpos::errors::PrinterError>(())
+/// ```
+pub fn upca_to_upce(upca: &str) -> Result<String> {
+    let invalid = |reason: String| PrinterError::Input(format!("invalid UPC-A data {upca}: {reason}"));
+    check_digits_only(upca).map_err(invalid)?;
+
+    let check_digit = match upca.len() {
+        11 => gs1_check_digit(upca)?,
+        12 => {
+            verify_check_digit(upca).map_err(invalid)?;
+            upca.as_bytes()[11] - b'0'
         }
+        _ => return Err(invalid("11 or 12 digits expected".to_owned())),
+    };
+
+    let number_system = &upca[..1];
+    if !["0", "1"].contains(&number_system) {
+        return Err(invalid("number system at position 0 must be 0 or 1".to_owned()));
     }
+
+    let (manufacturer, product) = (&upca[1..6], &upca[6..11]);
+    let digits = if manufacturer.ends_with("00") && manufacturer.as_bytes()[2] <= b'2' && product.starts_with("00") {
+        format!("{}{}{}", &manufacturer[..2], &product[2..], &manufacturer[2..3])
+    } else if manufacturer.ends_with("00") && product.starts_with("000") {
+        format!("{}{}3", &manufacturer[..3], &product[3..])
+    } else if manufacturer.ends_with('0') && product.starts_with("0000") {
+        format!("{}{}4", &manufacturer[..4], &product[4..])
+    } else if product.starts_with("0000") && product.as_bytes()[4] >= b'5' {
+        format!("{manufacturer}{}", &product[4..])
+    } else {
+        return Err(invalid("cannot be compressed to UPC-E".to_owned()));
+    };
+
+    Ok(format!("{number_system}{digits}{check_digit}"))
 }
 
 #[cfg(test)]
@@ -367,9 +1078,10 @@ mod tests {
 
     #[test]
     fn test_barcode_validate_upce() {
-        assert!(Barcode::validate(BarcodeSystem::UPCE, "02587965874").is_ok());
-        assert!(Barcode::validate(BarcodeSystem::UPCE, "025879658746").is_ok());
-        assert!(Barcode::validate(BarcodeSystem::UPCE, "02980547").is_ok());
+        assert!(Barcode::validate(BarcodeSystem::UPCE, "04210000526").is_ok());
+        assert!(Barcode::validate(BarcodeSystem::UPCE, "042100005264").is_ok());
+        assert!(Barcode::validate(BarcodeSystem::UPCE, "0425261").is_ok());
+        assert!(Barcode::validate(BarcodeSystem::UPCE, "04252614").is_ok());
         assert!(Barcode::validate(BarcodeSystem::UPCE, "985487").is_ok());
         assert!(Barcode::validate(BarcodeSystem::UPCE, "085487").is_ok());
 
@@ -378,12 +1090,19 @@ mod tests {
         assert!(Barcode::validate(BarcodeSystem::UPCE, "92587965874").is_err());
         assert!(Barcode::validate(BarcodeSystem::UPCE, "925879658746").is_err());
         assert!(Barcode::validate(BarcodeSystem::UPCE, "92980547").is_err());
+        // Not compressible, wrong check digits
+        assert!(Barcode::validate(BarcodeSystem::UPCE, "02587965874").is_err());
+        assert!(Barcode::validate(BarcodeSystem::UPCE, "042100005265").is_err());
+        assert!(Barcode::validate(BarcodeSystem::UPCE, "04252615").is_err());
+        assert!(Barcode::validate(BarcodeSystem::UPCE, "0abc").is_err());
     }
 
     #[test]
     fn test_barcode_validate_ean8() {
         assert!(Barcode::validate(BarcodeSystem::EAN8, "0325874").is_ok());
-        assert!(Barcode::validate(BarcodeSystem::EAN8, "98574587").is_ok());
+        assert!(Barcode::validate(BarcodeSystem::EAN8, "98574582").is_ok());
+
+        assert!(Barcode::validate(BarcodeSystem::EAN8, "98574587").is_err());
 
         assert!(Barcode::validate(BarcodeSystem::EAN8, "5g47u29").is_err());
         assert!(Barcode::validate(BarcodeSystem::EAN8, "980587407").is_err());
@@ -392,7 +1111,9 @@ mod tests {
     #[test]
     fn test_barcode_validate_ean13() {
         assert!(Barcode::validate(BarcodeSystem::EAN13, "012403258746").is_ok());
-        assert!(Barcode::validate(BarcodeSystem::EAN13, "0124032587468").is_ok());
+        assert!(Barcode::validate(BarcodeSystem::EAN13, "0124032587466").is_ok());
+
+        assert!(Barcode::validate(BarcodeSystem::EAN13, "0124032587468").is_err());
 
         assert!(Barcode::validate(BarcodeSystem::EAN13, "01240325874").is_err());
         assert!(Barcode::validate(BarcodeSystem::EAN13, "98058740701009").is_err());
@@ -437,4 +1158,374 @@ mod tests {
         assert!(Barcode::validate(BarcodeSystem::CODABAR, "9805f8740701009").is_err());
         assert!(Barcode::validate(BarcodeSystem::CODABAR, "98f874d0d70s09").is_err());
     }
+
+    #[test]
+    fn test_barcode_system_m() {
+        assert_eq!(BarcodeSystem::UPCA.m(BarcodeFunction::A), Some(0));
+        assert_eq!(BarcodeSystem::UPCA.m(BarcodeFunction::B), Some(65));
+        assert_eq!(BarcodeSystem::CODABAR.m(BarcodeFunction::B), Some(71));
+        assert_eq!(BarcodeSystem::CODE93.m(BarcodeFunction::A), None);
+        assert_eq!(BarcodeSystem::CODE93.m(BarcodeFunction::B), Some(72));
+        assert_eq!(
+            BarcodeSystem::CODE128(Code128CodeSet::C).m(BarcodeFunction::B),
+            Some(73)
+        );
+        assert_eq!(u8::from(BarcodeSystem::ITF), 5);
+        assert_eq!(u8::from(BarcodeSystem::CODE128(Code128CodeSet::Auto)), 73);
+    }
+
+    #[test]
+    fn test_code128_encode_auto() {
+        let encode = |data| code128_encode(data, Code128CodeSet::Auto).unwrap();
+
+        assert_eq!(encode("abc"), b"{Babc");
+        assert_eq!(encode("1234"), vec![b'{', b'C', 12, 34]);
+        // Odd digit run: first digit in code set B
+        assert_eq!(encode("a12345"), vec![b'{', b'B', b'a', b'1', b'{', b'C', 23, 45]);
+        // Short digit runs are not worth a code set change
+        assert_eq!(encode("a12b"), b"{Ba12b");
+        assert_eq!(encode("12ab"), b"{B12ab");
+        assert_eq!(encode("1234ab"), vec![b'{', b'C', 12, 34, b'{', b'B', b'a', b'b']);
+        // Control characters need code set A
+        assert_eq!(encode("AB\tC"), b"{AAB\tC");
+        assert_eq!(encode("a\tb"), b"{Ba{A\t{Bb");
+        assert_eq!(encode("{x"), b"{B{{x");
+        // Code sets A and B give the same length: B is preferred
+        assert_eq!(encode("ABC"), b"{BABC");
+        assert_eq!(encode("A-1 B"), b"{BA-1 B");
+        assert_eq!(encode("12AB"), b"{B12AB");
+        assert_eq!(encode("1234AB"), vec![b'{', b'C', 12, 34, b'{', b'B', b'A', b'B']);
+
+        assert!(code128_encode("", Code128CodeSet::Auto).is_err());
+        assert!(code128_encode("café", Code128CodeSet::Auto).is_err());
+    }
+
+    #[test]
+    fn test_code128_encode_code_set() {
+        assert_eq!(code128_encode("ab", Code128CodeSet::B).unwrap(), b"{Bab");
+        assert_eq!(code128_encode("AB", Code128CodeSet::A).unwrap(), b"{AAB");
+        assert_eq!(
+            code128_encode("012345", Code128CodeSet::C).unwrap(),
+            vec![b'{', b'C', 1, 23, 45]
+        );
+
+        assert!(code128_encode("ab", Code128CodeSet::A).is_err());
+        assert!(code128_encode("a\tb", Code128CodeSet::B).is_err());
+        assert!(code128_encode("12345", Code128CodeSet::C).is_err());
+        assert!(code128_encode("12a4", Code128CodeSet::C).is_err());
+    }
+
+    #[test]
+    fn test_barcode_validate_code93() {
+        assert!(Barcode::validate(BarcodeSystem::CODE93, "abc-12 $").is_ok());
+
+        assert!(Barcode::validate(BarcodeSystem::CODE93, "").is_err());
+        assert!(Barcode::validate(BarcodeSystem::CODE93, "é").is_err());
+    }
+
+    #[test]
+    fn test_barcode_new_function_b() {
+        let option = || BarcodeOption::default().function_b();
+        assert!(Barcode::new(BarcodeSystem::ITF, "0124", option()).is_ok());
+        assert!(Barcode::new(BarcodeSystem::ITF, "012", option()).is_err());
+        assert!(Barcode::new(BarcodeSystem::ITF, "012", BarcodeOption::default()).is_ok());
+
+        assert!(Barcode::new(BarcodeSystem::CODE93, &"A".repeat(255), option()).is_ok());
+        assert!(Barcode::new(BarcodeSystem::CODE93, &"A".repeat(256), option()).is_err());
+        // Code set selection is included in the length
+        let code128 = BarcodeSystem::CODE128(Code128CodeSet::B);
+        assert!(Barcode::new(code128, &"a".repeat(253), BarcodeOption::default()).is_ok());
+        assert!(Barcode::new(code128, &"a".repeat(254), BarcodeOption::default()).is_err());
+    }
+
+    #[test]
+    fn test_gs1_128_encode() {
+        // FNC1, (01) in code set C, (10) with a variable length followed by FNC1, (17) in code set C
+        let encoded = BarcodeSystem::GS1CODE128
+            .encode("(01)09501101530003(10)AB(17)251231")
+            .unwrap();
+        assert_eq!(
+            encoded,
+            vec![
+                b'{', b'C', b'{', b'1', 1, 9, 50, 11, 1, 53, 0, 3, 10, b'{', b'B', b'A', b'B', b'{', b'C', b'{', b'1',
+                17, 25, 12, 31
+            ]
+        );
+
+        assert!(BarcodeSystem::GS1CODE128.encode("0109501101530003").is_err());
+        assert!(BarcodeSystem::GS1CODE128.encode("(01)09501101530004").is_err());
+    }
+
+    #[test]
+    fn test_gs1_databar_encode() {
+        let gtin = "(01)09501101530003";
+        let system = BarcodeSystem::GS1DataBar;
+
+        assert_eq!(
+            system(GS1DataBarType::Omnidirectional).encode(gtin).unwrap(),
+            b"0950110153000"
+        );
+        assert_eq!(
+            system(GS1DataBarType::Truncated).encode("(01)0950110153000").unwrap(),
+            b"0950110153000"
+        );
+        assert_eq!(system(GS1DataBarType::Limited).encode(gtin).unwrap(), b"0950110153000");
+        assert!(system(GS1DataBarType::Limited).encode("(01)29501101530001").is_err());
+        assert!(system(GS1DataBarType::Omnidirectional)
+            .encode("(01)09501101530003(10)AB")
+            .is_err());
+        assert_eq!(
+            system(GS1DataBarType::Expanded)
+                .encode("(01)09501101530003(3103)001250(10)AB(21)1")
+                .unwrap(),
+            b"0109501101530003310300125010AB{1211"
+        );
+
+        assert_eq!(system(GS1DataBarType::Expanded).m(BarcodeFunction::B), Some(78));
+        assert_eq!(system(GS1DataBarType::Omnidirectional).m(BarcodeFunction::A), None);
+    }
+
+    #[test]
+    fn test_barcode_gs1() {
+        let data = GS1Data::new().gtin("0950110153000").unwrap();
+        assert!(Barcode::gs1(BarcodeSystem::GS1CODE128, &data, BarcodeOption::default()).is_ok());
+        assert!(Barcode::gs1(
+            BarcodeSystem::GS1DataBar(GS1DataBarType::Limited),
+            &data,
+            BarcodeOption::default()
+        )
+        .is_ok());
+        assert!(Barcode::gs1(BarcodeSystem::EAN13, &data, BarcodeOption::default()).is_err());
+    }
+
+    #[test]
+    fn test_barcode_check_digit_appended() {
+        let data = |system, data: &str, option: BarcodeOption| Barcode::new(system, data, option).unwrap().data;
+
+        assert_eq!(
+            data(BarcodeSystem::EAN13, "400638133393", BarcodeOption::default()),
+            "4006381333931"
+        );
+        assert_eq!(
+            data(BarcodeSystem::EAN8, "9857458", BarcodeOption::default()),
+            "98574582"
+        );
+        assert_eq!(
+            data(BarcodeSystem::UPCA, "12587965874", BarcodeOption::default()),
+            "125879658746"
+        );
+        assert_eq!(
+            data(BarcodeSystem::UPCE, "0425261", BarcodeOption::default()),
+            "04252614"
+        );
+        assert_eq!(
+            data(BarcodeSystem::UPCE, "04210000526", BarcodeOption::default()),
+            "042100005264"
+        );
+        assert_eq!(data(BarcodeSystem::UPCE, "425261", BarcodeOption::default()), "425261");
+
+        // Optional check digits
+        assert_eq!(data(BarcodeSystem::ITF, "123", BarcodeOption::default()), "123");
+        assert_eq!(
+            data(BarcodeSystem::ITF, "123", BarcodeOption::default().check_digit()),
+            "1236"
+        );
+        assert_eq!(
+            data(BarcodeSystem::ITF, "1236", BarcodeOption::default().check_digit()),
+            "1236"
+        );
+        assert!(Barcode::new(BarcodeSystem::ITF, "1234", BarcodeOption::default().check_digit()).is_err());
+        assert_eq!(
+            data(BarcodeSystem::CODE39, "CODE39", BarcodeOption::default()),
+            "CODE39"
+        );
+        assert_eq!(
+            data(BarcodeSystem::CODE39, "*A-1*", BarcodeOption::default().check_digit()),
+            "*A-14*"
+        );
+        assert!(Barcode::new(BarcodeSystem::CODE39, "A*B", BarcodeOption::default().check_digit()).is_err());
+    }
+
+    #[test]
+    fn test_barcode_error_position() {
+        let error = |system, data| Barcode::validate(system, data).unwrap_err().to_string();
+
+        assert!(error(BarcodeSystem::EAN13, "40063813a393").contains("not a digit at position 8"));
+        assert!(error(BarcodeSystem::EAN13, "4006381333932").contains("wrong check digit at position 12, expected 1"));
+        assert!(error(BarcodeSystem::EAN8, "123").contains("7, 8 digits expected"));
+        assert!(error(BarcodeSystem::UPCE, "1425261").contains("number system at position 0"));
+        assert!(error(BarcodeSystem::CODE39, "AB#").contains("invalid character at position 2"));
+        assert!(error(BarcodeSystem::CODABAR, "A1#B").contains("invalid character at position 2"));
+    }
+
+    #[test]
+    fn test_barcode_option_dots() {
+        assert_eq!(BarcodeWidth::from("XL"), BarcodeWidth::XL);
+        assert_eq!(BarcodeWidth::from("6"), BarcodeWidth::Dots(6));
+        assert_eq!(BarcodeWidth::from("wide"), BarcodeWidth::M);
+        assert_eq!(BarcodeHeight::from("80"), BarcodeHeight::Dots(80));
+        assert_eq!(u8::from(BarcodeHeight::Dots(80)), 80);
+
+        let option = |width, height| BarcodeOption::new(width, height, BarcodeFont::A, BarcodePosition::Below);
+        assert!(Barcode::new(
+            BarcodeSystem::EAN8,
+            "9857458",
+            option(BarcodeWidth::Dots(68), BarcodeHeight::Dots(1))
+        )
+        .is_ok());
+        assert!(Barcode::new(
+            BarcodeSystem::EAN8,
+            "9857458",
+            option(BarcodeWidth::Dots(0), BarcodeHeight::S)
+        )
+        .is_err());
+        assert!(Barcode::new(
+            BarcodeSystem::EAN8,
+            "9857458",
+            option(BarcodeWidth::Dots(69), BarcodeHeight::S)
+        )
+        .is_err());
+        assert!(Barcode::new(
+            BarcodeSystem::EAN8,
+            "9857458",
+            option(BarcodeWidth::M, BarcodeHeight::Dots(0))
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_barcode_estimated_width() {
+        assert_eq!(BarcodeSystem::UPCA.estimated_width("125879658746", 2), Some(190));
+        assert_eq!(BarcodeSystem::UPCE.estimated_width("04252614", 2), Some(102));
+        assert_eq!(BarcodeSystem::EAN8.estimated_width("98574582", 1), Some(67));
+
+        // Narrow 2 dots, wide 5 dots
+        assert_eq!(BarcodeSystem::CODE39.estimated_width("A1", 2), Some(4 * 29 - 2));
+        assert_eq!(BarcodeSystem::CODE39.estimated_width("*A1*", 2), Some(4 * 29 - 2));
+        assert_eq!(BarcodeSystem::ITF.estimated_width("1234", 2), Some(2 * 32 + 17));
+        assert_eq!(
+            BarcodeSystem::CODABAR.estimated_width("A12B", 2),
+            Some(2 * 23 + 2 * 20 + 3 * 2)
+        );
+        assert_eq!(
+            BarcodeSystem::CODABAR.estimated_width("12", 2),
+            BarcodeSystem::CODABAR.estimated_width("A12B", 2)
+        );
+
+        assert_eq!(BarcodeSystem::CODE93.estimated_width("AB", 1), Some(6 * 9 + 1));
+        assert_eq!(BarcodeSystem::CODE93.estimated_width("ab", 1), Some(8 * 9 + 1));
+
+        // Start C, 3 digit pairs, check and stop
+        assert_eq!(
+            BarcodeSystem::CODE128(Code128CodeSet::Auto).estimated_width("123456", 1),
+            Some(5 * 11 + 13)
+        );
+        assert_eq!(
+            BarcodeSystem::CODE128(Code128CodeSet::B).estimated_width("{", 1),
+            Some(3 * 11 + 13)
+        );
+        assert_eq!(
+            BarcodeSystem::CODE128(Code128CodeSet::C).estimated_width("123", 1),
+            None
+        );
+
+        let databar = |code_type| BarcodeSystem::GS1DataBar(code_type).estimated_width("(01)09501101530003", 1);
+        assert_eq!(databar(GS1DataBarType::Omnidirectional), Some(96));
+        assert_eq!(databar(GS1DataBarType::Limited), Some(79));
+        assert_eq!(databar(GS1DataBarType::Expanded), None);
+
+        let option = BarcodeOption::new(
+            BarcodeWidth::Dots(4),
+            BarcodeHeight::S,
+            BarcodeFont::A,
+            BarcodePosition::Below,
+        );
+        let barcode = Barcode::new(BarcodeSystem::EAN13, "012403258746", option).unwrap();
+        assert_eq!(barcode.estimated_width(), Some(380));
+    }
+
+    #[test]
+    fn test_upce_upca_conversion() {
+        // One example of each UPC-E last digit rule
+        for (upce, upca) in [
+            ("01234505", "012000003455"),
+            ("01234514", "012100003454"),
+            ("01234523", "012200003453"),
+            ("01234531", "012300000451"),
+            ("01234543", "012340000053"),
+            ("01234558", "012345000058"),
+            ("11234593", "112345000093"),
+        ] {
+            assert_eq!(upce_to_upca(upce).unwrap(), upca);
+            assert_eq!(upca_to_upce(upca).unwrap(), upce);
+        }
+
+        assert_eq!(upce_to_upca("123455").unwrap(), "012345000058");
+        assert!(upce_to_upca("21234558").is_err());
+        assert!(upce_to_upca("12345").is_err());
+        assert!(upca_to_upce("212345000058").is_err());
+        assert!(upca_to_upce("012345000059").is_err());
+        assert!(upca_to_upce("012345100058").is_err());
+    }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn ean13_check_digit_is_appended_and_verified(data in "[0-9]{12}", wrong in 1u8..10) {
+                let full = Barcode::new(BarcodeSystem::EAN13, &data, BarcodeOption::default()).unwrap().data;
+                prop_assert_eq!(&full[..12], data.as_str());
+                prop_assert!(Barcode::validate(BarcodeSystem::EAN13, &full).is_ok());
+
+                // Any other check digit is refused
+                let check = full.as_bytes()[12] - b'0';
+                let other = format!("{data}{}", (check + wrong) % 10);
+                prop_assert!(Barcode::validate(BarcodeSystem::EAN13, &other).is_err());
+            }
+
+            #[test]
+            fn upca_and_ean8_check_digits(upca in "[0-9]{11}", ean8 in "[0-9]{7}") {
+                let upca = Barcode::new(BarcodeSystem::UPCA, &upca, BarcodeOption::default()).unwrap().data;
+                prop_assert!(Barcode::validate(BarcodeSystem::UPCA, &upca).is_ok());
+                let ean8 = Barcode::new(BarcodeSystem::EAN8, &ean8, BarcodeOption::default()).unwrap().data;
+                prop_assert!(Barcode::validate(BarcodeSystem::EAN8, &ean8).is_ok());
+            }
+
+            #[test]
+            fn upce_round_trip(upce in "[01][0-9]{6}") {
+                let upca = upce_to_upca(&upce).unwrap();
+                prop_assert_eq!(upca.len(), 12);
+                prop_assert!(Barcode::validate(BarcodeSystem::UPCA, &upca).is_ok());
+
+                // The compressed form may differ (several UPC-E codes expand to the same UPC-A)
+                let compressed = upca_to_upce(&upca).unwrap();
+                prop_assert_eq!(upce_to_upca(&compressed).unwrap(), upca);
+            }
+
+            #[test]
+            fn itf_check_digit_makes_even_data(data in "[0-9]{1,30}") {
+                prop_assume!(data.len() % 2 == 1);
+                let itf = Barcode::new(BarcodeSystem::ITF, &data, BarcodeOption::default().check_digit()).unwrap().data;
+                prop_assert_eq!(itf.len() % 2, 0);
+                prop_assert!(Barcode::new(BarcodeSystem::ITF, &itf, BarcodeOption::default().check_digit()).is_ok());
+            }
+
+            #[test]
+            fn code39_check_character_is_valid(data in "[0-9A-Z .$/+%-]{1,20}") {
+                let code39 = Barcode::new(BarcodeSystem::CODE39, &data, BarcodeOption::default().check_digit()).unwrap().data;
+                prop_assert_eq!(code39.len(), data.len() + 1);
+                prop_assert!(Barcode::validate(BarcodeSystem::CODE39, &code39).is_ok());
+            }
+
+            #[test]
+            fn invalid_characters_are_located(prefix in "[0-9]{0,11}", c in "[a-z]") {
+                let data = format!("{prefix}{c}");
+                let error = Barcode::validate(BarcodeSystem::EAN13, &data).unwrap_err().to_string();
+                let expected = format!("not a digit at position {}", prefix.len());
+                prop_assert!(error.contains(&expected));
+            }
+        }
+    }
 }
@@ -0,0 +1,433 @@
+//! Composite symbology (GS1 Composite)
+
+use super::gs1::{gs1_check_digit, GS1Data, GS1_FNC1};
+use super::gs1_databar_2d::EXPANDED_STACKED_VALID_CHARS;
+use crate::errors::{PrinterError, Result};
+use std::fmt;
+
+/// Maximum number of characters of the 2D component (CC-A or CC-B)
+const COMPOSITE_MAX_2D_DATA_SIZE: usize = 338;
+/// Maximum number of characters of GS1-128 and GS1 DataBar Expanded linear components
+const COMPOSITE_MAX_LINEAR_DATA_SIZE: usize = 255;
+
+/// Linear component of a composite symbol
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum CompositeLinearType {
+    EAN8,
+    #[default]
+    EAN13,
+    UPCA,
+    GS1DataBarOmnidirectional,
+    GS1DataBarTruncated,
+    GS1DataBarStacked,
+    GS1DataBarStackedOmnidirectional,
+    GS1DataBarLimited,
+    GS1DataBarExpanded,
+    GS1DataBarExpandedStacked,
+    GS1128,
+}
+
+impl fmt::Display for CompositeLinearType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompositeLinearType::EAN8 => write!(f, "EAN8"),
+            CompositeLinearType::EAN13 => write!(f, "EAN13"),
+            CompositeLinearType::UPCA => write!(f, "UPC-A"),
+            CompositeLinearType::GS1DataBarOmnidirectional => write!(f, "GS1 DataBar Omnidirectional"),
+            CompositeLinearType::GS1DataBarTruncated => write!(f, "GS1 DataBar Truncated"),
+            CompositeLinearType::GS1DataBarStacked => write!(f, "GS1 DataBar Stacked"),
+            CompositeLinearType::GS1DataBarStackedOmnidirectional => {
+                write!(f, "GS1 DataBar Stacked Omnidirectional")
+            }
+            CompositeLinearType::GS1DataBarLimited => write!(f, "GS1 DataBar Limited"),
+            CompositeLinearType::GS1DataBarExpanded => write!(f, "GS1 DataBar Expanded"),
+            CompositeLinearType::GS1DataBarExpandedStacked => write!(f, "GS1 DataBar Expanded Stacked"),
+            CompositeLinearType::GS1128 => write!(f, "GS1-128"),
+        }
+    }
+}
+
+impl From<CompositeLinearType> for u8 {
+    fn from(value: CompositeLinearType) -> Self {
+        match value {
+            CompositeLinearType::EAN8 => 65,
+            CompositeLinearType::EAN13 => 66,
+            CompositeLinearType::UPCA => 67,
+            CompositeLinearType::GS1DataBarOmnidirectional => 70,
+            CompositeLinearType::GS1DataBarTruncated => 71,
+            CompositeLinearType::GS1DataBarStacked => 72,
+            CompositeLinearType::GS1DataBarStackedOmnidirectional => 73,
+            CompositeLinearType::GS1DataBarLimited => 74,
+            CompositeLinearType::GS1DataBarExpanded => 75,
+            CompositeLinearType::GS1DataBarExpandedStacked => 76,
+            CompositeLinearType::GS1128 => 77,
+        }
+    }
+}
+
+impl CompositeLinearType {
+    /// Get the number of digits without check digit of EAN and UPC types
+    fn ean_upc_length(&self) -> Option<usize> {
+        match self {
+            CompositeLinearType::EAN8 => Some(7),
+            CompositeLinearType::EAN13 => Some(12),
+            CompositeLinearType::UPCA => Some(11),
+            _ => None,
+        }
+    }
+}
+
+/// Composite symbol human readable interpretation (HRI) font
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum CompositeFont {
+    /// HRI characters not printed
+    None,
+    #[default]
+    A,
+    B,
+    C,
+    D,
+    E,
+    SpecialA,
+    SpecialB,
+}
+
+impl fmt::Display for CompositeFont {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompositeFont::None => write!(f, "No HRI characters"),
+            CompositeFont::A => write!(f, "HRI font A"),
+            CompositeFont::B => write!(f, "HRI font B"),
+            CompositeFont::C => write!(f, "HRI font C"),
+            CompositeFont::D => write!(f, "HRI font D"),
+            CompositeFont::E => write!(f, "HRI font E"),
+            CompositeFont::SpecialA => write!(f, "HRI special font A"),
+            CompositeFont::SpecialB => write!(f, "HRI special font B"),
+        }
+    }
+}
+
+impl From<CompositeFont> for u8 {
+    fn from(value: CompositeFont) -> Self {
+        match value {
+            CompositeFont::None => 0,
+            CompositeFont::A => 1,
+            CompositeFont::B => 2,
+            CompositeFont::C => 3,
+            CompositeFont::D => 4,
+            CompositeFont::E => 5,
+            CompositeFont::SpecialA => 97,
+            CompositeFont::SpecialB => 98,
+        }
+    }
+}
+
+/// Composite symbol option
+#[derive(Debug, Clone)]
+pub struct CompositeOption {
+    linear_type: CompositeLinearType,
+    module_width: u8,
+    expanded_max_width: u16,
+    font: CompositeFont,
+}
+
+impl Default for CompositeOption {
+    fn default() -> Self {
+        Self {
+            linear_type: CompositeLinearType::default(),
+            module_width: 2,
+            expanded_max_width: 0,
+            font: CompositeFont::default(),
+        }
+    }
+}
+
+impl CompositeOption {
+    /// Create a new `CompositeOption`
+    ///
+    /// The module width is in dots (2 - 8).
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = CompositeOption::new(CompositeLinearType::GS1128, 3, CompositeFont::B)?;
+    ///
+    /// assert_eq!(option.module_width(), 3);
+    /// assert!(CompositeOption::new(CompositeLinearType::GS1128, 9, CompositeFont::B).is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn new(linear_type: CompositeLinearType, module_width: u8, font: CompositeFont) -> Result<Self> {
+        if !(2..=8).contains(&module_width) {
+            return Err(PrinterError::Input(format!(
+                "composite symbol module width must be between 2 and 8 dots: {module_width}"
+            )));
+        }
+
+        Ok(Self {
+            linear_type,
+            module_width,
+            expanded_max_width: 0,
+            font,
+        })
+    }
+
+    /// Set the maximum width in dots of GS1 DataBar Expanded Stacked (106 - 3372, 0 for no limit)
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = CompositeOption::new(CompositeLinearType::GS1DataBarExpandedStacked, 2, CompositeFont::A)?
+    ///     .expanded_max_width(384)?;
+    ///
+    /// assert_eq!(option.get_expanded_max_width(), 384);
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn expanded_max_width(mut self, dots: u16) -> Result<Self> {
+        if dots != 0 && !(106..=3372).contains(&dots) {
+            return Err(PrinterError::Input(format!(
+                "GS1 DataBar Expanded Stacked maximum width must be 0 or between 106 and 3372 dots: {dots}"
+            )));
+        }
+        self.expanded_max_width = dots;
+        Ok(self)
+    }
+
+    /// Get linear component type
+    pub fn linear_type(&self) -> CompositeLinearType {
+        self.linear_type
+    }
+
+    /// Get module width
+    pub fn module_width(&self) -> u8 {
+        self.module_width
+    }
+
+    /// Get GS1 DataBar Expanded Stacked maximum width (0 for no limit)
+    pub fn get_expanded_max_width(&self) -> u16 {
+        self.expanded_max_width
+    }
+
+    /// Get HRI font
+    pub fn font(&self) -> CompositeFont {
+        self.font
+    }
+}
+
+/// Composite symbol: a linear component and a 2D component (CC-A or CC-B, selected by the printer)
+#[derive(Debug)]
+pub struct Composite {
+    /// Linear component data
+    pub linear_data: String,
+    /// 2D component data (GS1 Application Identifiers, `{1` separates variable length elements)
+    pub composite_data: String,
+    pub option: CompositeOption,
+}
+
+impl Composite {
+    /// Create a new `Composite`
+    ///
+    /// The linear component data is:
+    /// - EAN8, EAN13 and UPC-A: 7, 12 or 11 digits, optionally followed by the check digit
+    /// - GS1 DataBar Omnidirectional, Truncated, Stacked, Stacked Omnidirectional and Limited:
+    ///   the GTIN (13 digits) without its check digit
+    /// - GS1 DataBar Expanded, Expanded Stacked and GS1-128: GS1 Application Identifiers
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let composite = Composite::new("950110153000", "1712123110ABC", CompositeOption::default())?;
+    ///
+    /// assert_eq!(composite.linear_data, "950110153000");
+    /// assert!(Composite::new("9501101530001", "1712123110ABC", CompositeOption::default()).is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn new(linear_data: &str, composite_data: &str, option: CompositeOption) -> Result<Self> {
+        Self::check_linear_data(linear_data, option.linear_type)?;
+        Self::check_composite_data(composite_data)?;
+
+        Ok(Self {
+            linear_data: linear_data.to_owned(),
+            composite_data: composite_data.to_owned(),
+            option,
+        })
+    }
+
+    /// Create a new `Composite` from [`GS1Data`]
+    ///
+    /// EAN, UPC-A and GS1 DataBar types (except Expanded) only accept a GTIN (01) for the linear component.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let gtin = GS1Data::new().gtin("0950110153000")?;
+    /// let data = GS1Data::new().expiry_date(2025, 12, 31)?.batch("ABC")?;
+    /// let composite = Composite::from_gs1(&gtin, &data, CompositeOption::default())?;
+    ///
+    /// assert_eq!(composite.linear_data, "950110153000");
+    /// assert_eq!(composite.composite_data, "1725123110ABC");
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn from_gs1(linear_data: &GS1Data, composite_data: &GS1Data, option: CompositeOption) -> Result<Self> {
+        let linear_type = option.linear_type;
+        let linear = match linear_type {
+            CompositeLinearType::GS1DataBarExpanded
+            | CompositeLinearType::GS1DataBarExpandedStacked
+            | CompositeLinearType::GS1128 => linear_data.concatenated(GS1_FNC1),
+            _ => {
+                let gtin = linear_data.gtin_only().ok_or_else(|| {
+                    PrinterError::Input(format!(
+                        "{linear_type} data must only contain a GTIN (01): {linear_data}"
+                    ))
+                })?;
+                match linear_type.ean_upc_length() {
+                    // Leading zeros of the GTIN are not encoded in EAN and UPC
+                    Some(length) => gtin
+                        .strip_prefix(&"0".repeat(13 - length))
+                        .ok_or_else(|| PrinterError::Input(format!("GTIN {gtin} cannot be encoded in {linear_type}")))?
+                        .to_owned(),
+                    None => gtin.to_owned(),
+                }
+            }
+        };
+
+        Self::new(&linear, &composite_data.concatenated(GS1_FNC1), option)
+    }
+
+    /// Check linear component data
+    fn check_linear_data(data: &str, linear_type: CompositeLinearType) -> Result<()> {
+        let invalid = |reason: &str| PrinterError::Input(format!("invalid {linear_type} data {data}: {reason}"));
+        let all_digits = data.chars().all(|c| c.is_ascii_digit());
+
+        match linear_type {
+            CompositeLinearType::EAN8 | CompositeLinearType::EAN13 | CompositeLinearType::UPCA => {
+                let length = linear_type.ean_upc_length().unwrap_or_default();
+                if !all_digits || ![length, length + 1].contains(&data.len()) {
+                    return Err(invalid(&format!("{length} or {} digits expected", length + 1)));
+                }
+                if data.len() > length && gs1_check_digit(&data[..length])? != data.as_bytes()[length] - b'0' {
+                    return Err(invalid("wrong check digit"));
+                }
+                Ok(())
+            }
+            CompositeLinearType::GS1DataBarOmnidirectional
+            | CompositeLinearType::GS1DataBarTruncated
+            | CompositeLinearType::GS1DataBarStacked
+            | CompositeLinearType::GS1DataBarStackedOmnidirectional
+            | CompositeLinearType::GS1DataBarLimited => {
+                if !all_digits || data.len() != 13 {
+                    return Err(invalid("13 digits expected"));
+                }
+                if linear_type == CompositeLinearType::GS1DataBarLimited && !data.starts_with(['0', '1']) {
+                    return Err(invalid("first digit must be 0 or 1"));
+                }
+                Ok(())
+            }
+            CompositeLinearType::GS1DataBarExpanded | CompositeLinearType::GS1DataBarExpandedStacked => {
+                if data.is_empty() || data.len() > COMPOSITE_MAX_LINEAR_DATA_SIZE {
+                    return Err(invalid("1 to 255 characters expected"));
+                }
+                if !data.chars().all(|c| EXPANDED_STACKED_VALID_CHARS.contains(&c)) {
+                    return Err(invalid("invalid character"));
+                }
+                Ok(())
+            }
+            CompositeLinearType::GS1128 => {
+                if data.is_empty() || data.len() > COMPOSITE_MAX_LINEAR_DATA_SIZE {
+                    return Err(invalid("1 to 255 characters expected"));
+                }
+                if !data.chars().all(|c| c.is_ascii_graphic()) {
+                    return Err(invalid("invalid character"));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Check 2D component data
+    fn check_composite_data(data: &str) -> Result<()> {
+        if data.is_empty() || data.len() > COMPOSITE_MAX_2D_DATA_SIZE || !data.chars().all(|c| c.is_ascii_graphic()) {
+            return Err(PrinterError::Input(format!(
+                "invalid composite 2D component data, 1 to {COMPOSITE_MAX_2D_DATA_SIZE} ASCII characters expected: {data}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_composite_check_linear_data() {
+        use CompositeLinearType::*;
+
+        assert!(Composite::check_linear_data("9501101", EAN8).is_ok());
+        assert!(Composite::check_linear_data("95011011", EAN8).is_ok());
+        assert!(Composite::check_linear_data("95011015", EAN8).is_err());
+        assert!(Composite::check_linear_data("950110153000", EAN13).is_ok());
+        assert!(Composite::check_linear_data("9501101530003", EAN13).is_ok());
+        assert!(Composite::check_linear_data("9501101530004", EAN13).is_err());
+        assert!(Composite::check_linear_data("95011015300", EAN13).is_err());
+        assert!(Composite::check_linear_data("03600029145", UPCA).is_ok());
+        assert!(Composite::check_linear_data("036000291452", UPCA).is_ok());
+        assert!(Composite::check_linear_data("03600029145A", UPCA).is_err());
+
+        assert!(Composite::check_linear_data("0950110153000", GS1DataBarOmnidirectional).is_ok());
+        assert!(Composite::check_linear_data("09501101530003", GS1DataBarTruncated).is_err());
+        assert!(Composite::check_linear_data("1950110153000", GS1DataBarLimited).is_ok());
+        assert!(Composite::check_linear_data("2950110153000", GS1DataBarLimited).is_err());
+
+        assert!(Composite::check_linear_data("0109501101530003{110ABC", GS1DataBarExpanded).is_ok());
+        assert!(Composite::check_linear_data("0109501101530003{110abc", GS1DataBarExpandedStacked).is_err());
+        assert!(Composite::check_linear_data("", GS1DataBarExpanded).is_err());
+        assert!(Composite::check_linear_data("0109501101530003{110abc", GS1128).is_ok());
+        assert!(Composite::check_linear_data("01 0950", GS1128).is_err());
+    }
+
+    #[test]
+    fn test_composite_check_composite_data() {
+        assert!(Composite::check_composite_data("1712123110ABC").is_ok());
+        assert!(Composite::check_composite_data(&"1".repeat(338)).is_ok());
+        assert!(Composite::check_composite_data(&"1".repeat(339)).is_err());
+        assert!(Composite::check_composite_data("").is_err());
+        assert!(Composite::check_composite_data("10AB\n").is_err());
+    }
+
+    #[test]
+    fn test_composite_from_gs1() {
+        let gtin = GS1Data::new().gtin("0003600029145").unwrap();
+        let data = GS1Data::new().batch("ABC").unwrap().count(12).unwrap();
+        let option = |linear_type| CompositeOption::new(linear_type, 2, CompositeFont::None).unwrap();
+
+        let composite = Composite::from_gs1(&gtin, &data, option(CompositeLinearType::UPCA)).unwrap();
+        assert_eq!(composite.linear_data, "03600029145");
+        assert_eq!(composite.composite_data, "10ABC{13012");
+        assert!(Composite::from_gs1(&gtin, &data, option(CompositeLinearType::EAN8)).is_err());
+        assert_eq!(
+            Composite::from_gs1(&gtin, &data, option(CompositeLinearType::GS1DataBarStacked))
+                .unwrap()
+                .linear_data,
+            "0003600029145"
+        );
+
+        let linear = gtin.batch("LOT1").unwrap();
+        assert!(Composite::from_gs1(&linear, &data, option(CompositeLinearType::EAN13)).is_err());
+        assert_eq!(
+            Composite::from_gs1(&linear, &data, option(CompositeLinearType::GS1128))
+                .unwrap()
+                .linear_data,
+            "010003600029145210LOT1"
+        );
+    }
+
+    #[test]
+    fn test_composite_option() {
+        assert!(CompositeOption::new(CompositeLinearType::EAN13, 1, CompositeFont::A).is_err());
+        assert!(CompositeOption::new(CompositeLinearType::EAN13, 8, CompositeFont::A).is_ok());
+        let option = CompositeOption::default();
+        assert!(option.clone().expanded_max_width(105).is_err());
+        assert!(option.clone().expanded_max_width(3373).is_err());
+        assert_eq!(option.expanded_max_width(0).unwrap().get_expanded_max_width(), 0);
+    }
+}
@@ -1,5 +1,6 @@
 //! DataMatrix
 
+use super::eci::Eci;
 use crate::errors::{PrinterError, Result};
 use std::fmt;
 
@@ -105,18 +106,45 @@ impl DataMatrixOption {
 /// DataMatrix
 #[derive(Debug)]
 pub struct DataMatrix {
-    pub data: String,
+    pub data: Vec<u8>,
     pub option: DataMatrixOption,
+    /// Character set of text data
+    pub eci: Option<Eci>,
 }
 
 impl DataMatrix {
-    /// Create a new `DataMatrix`
+    /// Create a new `DataMatrix` (UTF-8 text)
     pub fn new(data: &str, option: DataMatrixOption) -> Self {
+        Self::from_bytes(data.as_bytes(), option)
+    }
+
+    /// Create a new `DataMatrix` from binary data
+    pub fn from_bytes(data: &[u8], option: DataMatrixOption) -> Self {
         Self {
-            data: data.to_string(),
+            data: data.to_vec(),
             option,
+            eci: None,
         }
     }
+
+    /// Create a new `DataMatrix` with text encoded in a character set
+    ///
+    /// The ECI designator is encoded in the symbol, DataMatrix is printed as image (`codes_2d_image` feature).
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let code = DataMatrix::with_eci("Café", Eci::Latin1, DataMatrixOption::default())?;
+    ///
+    /// assert_eq!(code.data, vec![b'C', b'a', b'f', 0xE9]);
+    /// assert!(DataMatrix::with_eci("€", Eci::Latin1, DataMatrixOption::default()).is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn with_eci(data: &str, eci: Eci, option: DataMatrixOption) -> Result<Self> {
+        let mut code = Self::from_bytes(&eci.encode(data)?, option);
+        code.eci = Some(eci);
+        Ok(code)
+    }
 }
 
 #[cfg(test)]
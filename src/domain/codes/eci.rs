@@ -0,0 +1,96 @@
+//! Extended Channel Interpretation (ECI)
+
+use crate::errors::{PrinterError, Result};
+use std::fmt;
+
+/// Character set of 2D code text data (Extended Channel Interpretation)
+///
+/// The text is encoded in the character set before being sent to the printer.
+/// ESC/POS 2D code functions have no ECI designator: 2D codes with an ECI are printed as images
+/// with the ECI designator (QR codes, DataMatrix and Aztec codes, `codes_2d_image` feature),
+/// other ones cannot be printed.
+/// To send text in a character set without ECI designator, create the code from [`Eci::encode()`] bytes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Eci {
+    /// ISO/IEC 8859-1 (Latin-1)
+    Latin1,
+    /// UTF-8
+    Utf8,
+    /// US-ASCII
+    Ascii,
+}
+
+impl Eci {
+    /// Get the ECI assignment number
+    ///
+    /// ```
+    /// use escpos::utils::Eci;
+    ///
+    /// assert_eq!(Eci::Latin1.value(), 3);
+    /// assert_eq!(Eci::Utf8.value(), 26);
+    /// ```
+    pub fn value(&self) -> u32 {
+        match self {
+            Eci::Latin1 => 3,
+            Eci::Utf8 => 26,
+            Eci::Ascii => 27,
+        }
+    }
+
+    /// Encode text in the character set
+    ///
+    /// ```
+    /// use escpos::utils::Eci;
+    ///
+    /// assert_eq!(Eci::Latin1.encode("Café")?, vec![b'C', b'a', b'f', 0xE9]);
+    /// assert_eq!(Eci::Utf8.encode("Café")?, "Café".as_bytes());
+    /// assert!(Eci::Ascii.encode("Café").is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>> {
+        let max = match self {
+            Eci::Utf8 => return Ok(text.as_bytes().to_vec()),
+            Eci::Latin1 => 0xFF,
+            Eci::Ascii => 0x7F,
+        };
+
+        text.chars()
+            .enumerate()
+            .map(|(position, c)| match u8::try_from(c) {
+                Ok(byte) if byte <= max => Ok(byte),
+                _ => Err(PrinterError::Input(format!(
+                    "character '{c}' at position {position} cannot be encoded in {self}"
+                ))),
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Eci {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Eci::Latin1 => write!(f, "ISO-8859-1"),
+            Eci::Utf8 => write!(f, "UTF-8"),
+            Eci::Ascii => write!(f, "US-ASCII"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eci_encode() {
+        assert_eq!(Eci::Latin1.encode("Aÿ").unwrap(), vec![b'A', 0xFF]);
+        assert!(Eci::Latin1
+            .encode("ÿ€")
+            .unwrap_err()
+            .to_string()
+            .contains("at position 1"));
+        assert_eq!(Eci::Latin1.encode("").unwrap(), Vec::<u8>::new());
+        assert_eq!(Eci::Ascii.encode("ABC").unwrap(), b"ABC");
+        assert!(Eci::Ascii.encode("\u{80}").is_err());
+        assert_eq!(Eci::Utf8.encode("€").unwrap(), vec![0xE2, 0x82, 0xAC]);
+    }
+}
@@ -0,0 +1,480 @@
+//! GS1 Application Identifiers
+//!
+//! [GS1 General Specifications](https://www.gs1.org/standards/barcodes-epcrfid-id-keys/gs1-general-specifications)
+
+use crate::errors::{PrinterError, Result};
+use std::fmt;
+
+/// FNC1 character in `GS k` and `GS ( k` data
+pub(crate) const GS1_FNC1: &str = "{1";
+
+/// Application Identifiers which are never followed by a separator (predefined length),
+/// identified by their first two digits
+const PREDEFINED_LENGTH_PREFIXES: [&str; 22] = [
+    "00", "01", "02", "03", "04", "11", "12", "13", "14", "15", "16", "17", "18", "19", "20", "31", "32", "33", "34",
+    "35", "36", "41",
+];
+
+/// GS1 AI encodable character set 82 (punctuation, digits and letters)
+const CSET_82_PUNCTUATION: [char; 20] = [
+    '!', '"', '%', '&', '\'', '(', ')', '*', '+', ',', '-', '.', '/', ':', ';', '<', '=', '>', '?', '_',
+];
+
+/// Format of an Application Identifier data field
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GS1Format {
+    /// Fixed number of digits ending with a check digit
+    NumericWithCheckDigit(usize),
+    /// Fixed number of digits
+    Numeric(usize),
+    /// Up to a number of digits
+    NumericUpTo(usize),
+    /// Up to a number of characters of the character set 82
+    AlphanumericUpTo(usize),
+    /// Date (YYMMDD), the day can be `00` for the last day of the month
+    Date,
+}
+
+/// Get the data format of an Application Identifier
+fn ai_format(ai: &str) -> Option<GS1Format> {
+    use GS1Format::*;
+
+    let format = match ai {
+        "00" => NumericWithCheckDigit(18),
+        "01" | "02" => NumericWithCheckDigit(14),
+        "10" | "21" | "22" | "420" => AlphanumericUpTo(20),
+        "11" | "12" | "13" | "15" | "16" | "17" => Date,
+        "20" => Numeric(2),
+        "30" | "37" => NumericUpTo(8),
+        "240" | "241" | "400" | "401" | "403" => AlphanumericUpTo(30),
+        "410" | "411" | "412" | "413" | "414" | "415" => NumericWithCheckDigit(13),
+        "421" => AlphanumericUpTo(12),
+        "422" | "424" | "426" => Numeric(3),
+        // Trade measures: the last digit is the number of decimals
+        _ if ai.len() == 4
+            && ai.chars().all(|c| c.is_ascii_digit())
+            && ("310"..="369").contains(&&ai[..3])
+            && ai.as_bytes()[3] <= b'5' =>
+        {
+            Numeric(6)
+        }
+        _ => return None,
+    };
+
+    Some(format)
+}
+
+/// Parse a supported Application Identifier in parentheses at the start of data, returns the AI and the rest
+fn parse_ai(data: &str) -> Option<(&str, &str)> {
+    let (ai, rest) = data.strip_prefix('(')?.split_once(')')?;
+    ai_format(ai).map(|_| (ai, rest))
+}
+
+/// Compute the GS1 check digit (modulo 10) of digits
+///
+/// ```
+/// use escpos::utils::gs1_check_digit;
+///
+/// assert_eq!(gs1_check_digit("950110153000").unwrap(), 3);
+/// assert!(gs1_check_digit("95011A").is_err());
+/// ```
+pub fn gs1_check_digit(digits: &str) -> Result<u8> {
+    if let Some(position) = digits.chars().position(|c| !c.is_ascii_digit()) {
+        return Err(PrinterError::Input(format!(
+            "invalid digit at position {position}: {digits}"
+        )));
+    }
+
+    // Weights 3 and 1 from the right
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(i, digit)| u32::from(digit - b'0') * if i % 2 == 0 { 3 } else { 1 })
+        .sum();
+
+    Ok(((10 - sum % 10) % 10) as u8)
+}
+
+/// Get the number of days of a month (two-digit year)
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Validate a field against its format, computing the check digit if missing
+fn validate_value(ai: &str, format: GS1Format, value: &str) -> Result<String> {
+    let invalid = |reason: String| PrinterError::Input(format!("invalid GS1 AI ({ai}) data {value}: {reason}"));
+    let check_digits = |value: &str| match value.chars().position(|c| !c.is_ascii_digit()) {
+        Some(position) => Err(invalid(format!("not a digit at position {position}"))),
+        None => Ok(()),
+    };
+
+    match format {
+        GS1Format::NumericWithCheckDigit(length) => {
+            check_digits(value)?;
+            if value.len() == length - 1 {
+                Ok(format!("{value}{}", gs1_check_digit(value)?))
+            } else if value.len() == length {
+                let expected = gs1_check_digit(&value[..length - 1])?;
+                if value.as_bytes()[length - 1] - b'0' != expected {
+                    return Err(invalid(format!("wrong check digit, expected {expected}")));
+                }
+                Ok(value.to_owned())
+            } else {
+                Err(invalid(format!("{} or {length} digits expected", length - 1)))
+            }
+        }
+        GS1Format::Numeric(length) => {
+            check_digits(value)?;
+            if value.len() != length {
+                return Err(invalid(format!("{length} digits expected")));
+            }
+            Ok(value.to_owned())
+        }
+        GS1Format::NumericUpTo(length) => {
+            check_digits(value)?;
+            if value.is_empty() || value.len() > length {
+                return Err(invalid(format!("1 to {length} digits expected")));
+            }
+            Ok(value.to_owned())
+        }
+        GS1Format::AlphanumericUpTo(length) => {
+            if let Some(position) = value
+                .chars()
+                .position(|c| !c.is_ascii_alphanumeric() && !CSET_82_PUNCTUATION.contains(&c))
+            {
+                return Err(invalid(format!("invalid character at position {position}")));
+            }
+            if value.is_empty() || value.len() > length {
+                return Err(invalid(format!("1 to {length} characters expected")));
+            }
+            Ok(value.to_owned())
+        }
+        GS1Format::Date => {
+            check_digits(value)?;
+            if value.len() != 6 {
+                return Err(invalid("YYMMDD date expected".to_owned()));
+            }
+            let field = |range: std::ops::Range<usize>| value[range].parse::<u32>().unwrap_or_default();
+            let (year, month, day) = (field(0..2), field(2..4), field(4..6));
+            if !(1..=12).contains(&month) {
+                return Err(invalid(format!("invalid month {month:02}")));
+            }
+            if day > days_in_month(year, month) {
+                return Err(invalid(format!("invalid day {day:02}")));
+            }
+            Ok(value.to_owned())
+        }
+    }
+}
+
+/// GS1 element string (Application Identifier and its data)
+#[derive(Debug, Clone, PartialEq)]
+pub struct GS1Element {
+    ai: String,
+    value: String,
+}
+
+impl GS1Element {
+    /// Get the Application Identifier
+    pub fn ai(&self) -> &str {
+        &self.ai
+    }
+
+    /// Get the data
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Is the element followed by a separator when it is not the last one?
+    fn is_variable_length(&self) -> bool {
+        !PREDEFINED_LENGTH_PREFIXES.contains(&&self.ai[..2])
+    }
+}
+
+/// GS1 data, a list of Application Identifiers and their data
+///
+/// Each element is validated against its AI format, missing check digits are computed.
+///
+/// ```
+/// use escpos::utils::*;
+///
+/// let data = GS1Data::new()
+///     .gtin("0950110153000")?
+///     .expiry_date(2025, 12, 31)?
+///     .net_weight_kg(1250, 3)?
+///     .batch("LOT-42")?;
+///
+/// assert_eq!(data.to_string(), "(01)09501101530003(17)251231(3103)001250(10)LOT-42");
+/// assert_eq!(GS1Data::parse("(01)09501101530003(10)LOT-42")?.elements().len(), 2);
+/// # Ok::<(), escpos::errors::PrinterError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GS1Data {
+    elements: Vec<GS1Element>,
+}
+
+impl GS1Data {
+    /// Create an empty `GS1Data`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse GS1 data with Application Identifiers in parentheses, e.g. `(01)09501101530003(17)251231`
+    ///
+    /// Values can contain parentheses, only a supported AI in parentheses (e.g. `(21)`) starts a new element.
+    pub fn parse(data: &str) -> Result<Self> {
+        let mut gs1 = Self::new();
+        let mut rest = data;
+
+        while !rest.is_empty() {
+            let (ai, tail) = parse_ai(rest)
+                .ok_or_else(|| PrinterError::Input(format!("invalid GS1 data, (AI) expected: {rest}")))?;
+            let end = tail
+                .match_indices('(')
+                .map(|(index, _)| index)
+                .find(|&index| parse_ai(&tail[index..]).is_some())
+                .unwrap_or(tail.len());
+            gs1 = gs1.element(ai, &tail[..end])?;
+            rest = &tail[end..];
+        }
+
+        if gs1.elements.is_empty() {
+            return Err(PrinterError::Input("GS1 data cannot be empty".to_owned()));
+        }
+
+        Ok(gs1)
+    }
+
+    /// Add an element
+    ///
+    /// Supported AIs: 00, 01, 02, 10, 11, 12, 13, 15, 16, 17, 20, 21, 22, 30, 37, 240, 241,
+    /// 400, 401, 403, 410 - 415, 420, 421, 422, 424, 426 and trade measures 310n - 369n.
+    pub fn element(mut self, ai: &str, value: &str) -> Result<Self> {
+        let format = ai_format(ai).ok_or_else(|| PrinterError::Input(format!("unsupported GS1 AI: ({ai})")))?;
+        let value = validate_value(ai, format, value)?;
+
+        self.elements.push(GS1Element {
+            ai: ai.to_owned(),
+            value,
+        });
+        Ok(self)
+    }
+
+    /// Add a GTIN (01), 13 digits without check digit or 14 digits
+    pub fn gtin(self, gtin: &str) -> Result<Self> {
+        self.element("01", gtin)
+    }
+
+    /// Add a SSCC (00), 17 digits without check digit or 18 digits
+    pub fn sscc(self, sscc: &str) -> Result<Self> {
+        self.element("00", sscc)
+    }
+
+    /// Add a batch or lot number (10)
+    pub fn batch(self, batch: &str) -> Result<Self> {
+        self.element("10", batch)
+    }
+
+    /// Add a serial number (21)
+    pub fn serial(self, serial: &str) -> Result<Self> {
+        self.element("21", serial)
+    }
+
+    /// Add a production date (11)
+    pub fn production_date(self, year: u16, month: u8, day: u8) -> Result<Self> {
+        self.date("11", year, month, day)
+    }
+
+    /// Add a best before date (15)
+    pub fn best_before_date(self, year: u16, month: u8, day: u8) -> Result<Self> {
+        self.date("15", year, month, day)
+    }
+
+    /// Add an expiration date (17), `day` can be `0` for the last day of the month
+    pub fn expiry_date(self, year: u16, month: u8, day: u8) -> Result<Self> {
+        self.date("17", year, month, day)
+    }
+
+    /// Add a count of items (30)
+    pub fn count(self, count: u32) -> Result<Self> {
+        self.element("30", &count.to_string())
+    }
+
+    /// Add a net weight in kilograms (310n) with `decimals` decimals (0 - 5)
+    ///
+    /// `net_weight_kg(1250, 3)` is 1.250 kg.
+    pub fn net_weight_kg(self, weight: u32, decimals: u8) -> Result<Self> {
+        if decimals > 5 {
+            return Err(PrinterError::Input(format!(
+                "invalid GS1 weight decimals (0-5): {decimals}"
+            )));
+        }
+        self.element(&format!("310{decimals}"), &format!("{weight:06}"))
+    }
+
+    /// Add a date (YYMMDD) for years 2000 - 2099
+    fn date(self, ai: &str, year: u16, month: u8, day: u8) -> Result<Self> {
+        if !(2000..=2099).contains(&year) {
+            return Err(PrinterError::Input(format!(
+                "invalid GS1 AI ({ai}) year (2000-2099): {year}"
+            )));
+        }
+        self.element(ai, &format!("{:02}{month:02}{day:02}", year % 100))
+    }
+
+    /// Get elements
+    pub fn elements(&self) -> &[GS1Element] {
+        &self.elements
+    }
+
+    /// Get the element of an Application Identifier
+    pub fn get(&self, ai: &str) -> Option<&str> {
+        self.elements
+            .iter()
+            .find(|element| element.ai == ai)
+            .map(|element| element.value.as_str())
+    }
+
+    /// Get the GTIN without its check digit if it is the only element
+    ///
+    /// GS1 DataBar printers add the (01) AI and the check digit.
+    pub(crate) fn gtin_only(&self) -> Option<&str> {
+        match self.elements.as_slice() {
+            [element] if element.ai == "01" => Some(&element.value[..13]),
+            _ => None,
+        }
+    }
+
+    /// Get the data without parentheses, variable length elements are followed by
+    /// `separator` unless they are the last one
+    pub(crate) fn concatenated(&self, separator: &str) -> String {
+        let mut data = String::new();
+        for (index, element) in self.elements.iter().enumerate() {
+            data.push_str(&element.ai);
+            data.push_str(&element.value);
+            if element.is_variable_length() && index + 1 < self.elements.len() {
+                data.push_str(separator);
+            }
+        }
+        data
+    }
+}
+
+impl fmt::Display for GS1Data {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for element in &self.elements {
+            write!(f, "({}){}", element.ai, element.value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gs1_check_digit() {
+        assert_eq!(gs1_check_digit("0950110153000").unwrap(), 3);
+        assert_eq!(gs1_check_digit("400638133393").unwrap(), 1);
+        assert_eq!(gs1_check_digit("00000000000000000").unwrap(), 0);
+        assert_eq!(gs1_check_digit("10614141123456789").unwrap(), 7);
+        assert!(gs1_check_digit("12a").is_err());
+    }
+
+    #[test]
+    fn test_gs1_data_check_digits() {
+        assert_eq!(
+            GS1Data::new().gtin("0950110153000").unwrap().get("01"),
+            Some("09501101530003")
+        );
+        assert!(GS1Data::new().gtin("950110153000").is_err());
+        assert!(GS1Data::new().gtin("09501101530003").is_ok());
+        assert!(GS1Data::new().gtin("09501101530004").is_err());
+        assert!(GS1Data::new().gtin("0950110153000A").is_err());
+        assert!(GS1Data::new().gtin("123456789012345").is_err());
+        assert_eq!(
+            GS1Data::new().sscc("10614141123456789").unwrap().get("00"),
+            Some("106141411234567897")
+        );
+    }
+
+    #[test]
+    fn test_gs1_data_dates() {
+        assert_eq!(
+            GS1Data::new().expiry_date(2025, 2, 28).unwrap().get("17"),
+            Some("250228")
+        );
+        assert_eq!(
+            GS1Data::new().expiry_date(2025, 2, 0).unwrap().get("17"),
+            Some("250200")
+        );
+        assert!(GS1Data::new().expiry_date(2024, 2, 29).is_ok());
+        assert!(GS1Data::new().expiry_date(2025, 2, 29).is_err());
+        assert!(GS1Data::new().production_date(2025, 13, 1).is_err());
+        assert!(GS1Data::new().best_before_date(2025, 4, 31).is_err());
+        assert!(GS1Data::new().best_before_date(1999, 4, 1).is_err());
+        assert!(GS1Data::new().element("17", "2501").is_err());
+    }
+
+    #[test]
+    fn test_gs1_data_weight() {
+        let data = GS1Data::new().net_weight_kg(1250, 3).unwrap();
+        assert_eq!(data.to_string(), "(3103)001250");
+        assert!(GS1Data::new().net_weight_kg(1_000_000, 3).is_err());
+        assert!(GS1Data::new().net_weight_kg(1, 6).is_err());
+        assert!(GS1Data::new().element("3106", "000001").is_err());
+        assert!(GS1Data::new().element("3202", "000450").is_ok());
+    }
+
+    #[test]
+    fn test_gs1_data_alphanumeric() {
+        assert!(GS1Data::new().batch("ABC-12/x").is_ok());
+        assert!(GS1Data::new().batch("").is_err());
+        assert!(GS1Data::new().batch(&"A".repeat(21)).is_err());
+        assert!(GS1Data::new().serial("A B").is_err());
+        assert!(GS1Data::new().serial("é").is_err());
+        assert!(GS1Data::new().element("99", "1").is_err());
+    }
+
+    #[test]
+    fn test_gs1_data_parse() {
+        let data = GS1Data::parse("(01)09501101530003(17)251231(10)AB(21)123").unwrap();
+        assert_eq!(data.elements().len(), 4);
+        assert_eq!(data.get("10"), Some("AB"));
+        assert_eq!(data.to_string(), "(01)09501101530003(17)251231(10)AB(21)123");
+
+        assert!(GS1Data::parse("").is_err());
+        assert!(GS1Data::parse("0109501101530003").is_err());
+        assert!(GS1Data::parse("(01)09501101530003(17").is_err());
+        assert!(GS1Data::parse("(01)09501101530004").is_err());
+        assert!(GS1Data::parse("(99)1").is_err());
+    }
+
+    #[test]
+    fn test_gs1_data_parse_parentheses() {
+        let data = GS1Data::parse("(10)LOT(A)(21)S(1)2(99)(17)251231").unwrap();
+        assert_eq!(data.elements().len(), 3);
+        assert_eq!(data.get("10"), Some("LOT(A)"));
+        assert_eq!(data.get("21"), Some("S(1)2(99)"));
+        assert_eq!(data.get("17"), Some("251231"));
+        assert_eq!(data.to_string(), "(10)LOT(A)(21)S(1)2(99)(17)251231");
+
+        assert_eq!(GS1Data::parse("(10)(").unwrap().get("10"), Some("("));
+    }
+
+    #[test]
+    fn test_gs1_data_concatenated() {
+        let data = GS1Data::parse("(10)AB(01)09501101530003(21)123(17)251231").unwrap();
+        assert_eq!(data.concatenated(GS1_FNC1), "10AB{1010950110153000321123{117251231");
+
+        let data = GS1Data::parse("(01)09501101530003(10)AB").unwrap();
+        assert_eq!(data.concatenated(GS1_FNC1), "010950110153000310AB");
+    }
+}
@@ -1,9 +1,10 @@
 //! 2D GS1 DataBar
 
+use super::gs1::{GS1Data, GS1_FNC1};
 use crate::errors::{PrinterError, Result};
 use std::fmt;
 
-const EXPANDED_STACKED_VALID_CHARS: [char; 36] = [
+pub(super) const EXPANDED_STACKED_VALID_CHARS: [char; 36] = [
     '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', ' ', '!', '"', '%', '$', '\'', '(', ')', '*',
     '+', ',', '-', '.', '/', ':', ';', '<', '=', '>', '?', '_', '{',
 ];
@@ -106,6 +107,36 @@ impl GS1DataBar2D {
         })
     }
 
+    /// Create a new `GS1DataBar2D` from [`GS1Data`]
+    ///
+    /// Stacked types only accept a GTIN (01), Expanded Stacked accepts any Application Identifiers.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let data = GS1Data::new().gtin("0950110153000")?.expiry_date(2025, 12, 31)?;
+    /// let option = GS1DataBar2DOption::new(GS1DataBar2DWidth::M, GS1DataBar2DType::ExpandedStacked);
+    ///
+    /// assert_eq!(GS1DataBar2D::from_gs1(&data, option)?.data, "010950110153000317251231");
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn from_gs1(data: &GS1Data, option: GS1DataBar2DOption) -> Result<Self> {
+        let data = match option.code_type {
+            GS1DataBar2DType::Stacked | GS1DataBar2DType::StackedOmnidirectional => data
+                .gtin_only()
+                .ok_or_else(|| {
+                    PrinterError::Input(format!(
+                        "{} data must only contain a GTIN (01): {data}",
+                        option.code_type
+                    ))
+                })?
+                .to_owned(),
+            GS1DataBar2DType::ExpandedStacked => data.concatenated(GS1_FNC1),
+        };
+
+        Ok(Self { data, option })
+    }
+
     /// Check data
     fn check_data(data: &str, code_type: &GS1DataBar2DType) -> Result<()> {
         let data_len = data.len();
@@ -158,4 +189,23 @@ mod tests {
         assert!(GS1DataBar2D::check_data("123456098765d", &GS1DataBar2DType::ExpandedStacked).is_err());
         assert!(GS1DataBar2D::check_data("", &GS1DataBar2DType::ExpandedStacked).is_ok());
     }
+
+    #[test]
+    fn test_gs1_databar_from_gs1() {
+        let gtin = GS1Data::new().gtin("0950110153000").unwrap();
+        let stacked = GS1DataBar2DOption::new(GS1DataBar2DWidth::M, GS1DataBar2DType::Stacked);
+        assert_eq!(
+            GS1DataBar2D::from_gs1(&gtin, stacked.clone()).unwrap().data,
+            "0950110153000"
+        );
+
+        let data = gtin.batch("LOT5").unwrap().count(12).unwrap();
+        assert!(GS1DataBar2D::from_gs1(&data, stacked).is_err());
+
+        let expanded = GS1DataBar2DOption::new(GS1DataBar2DWidth::M, GS1DataBar2DType::ExpandedStacked);
+        assert_eq!(
+            GS1DataBar2D::from_gs1(&data, expanded).unwrap().data,
+            "010950110153000310LOT5{13012"
+        );
+    }
 }
@@ -1,15 +1,35 @@
 //! MaxiCode
 
+use super::eci::Eci;
+use crate::errors::{PrinterError, Result};
 use std::fmt;
 
-/// PDF417 correction level
-#[derive(Debug, Default, Clone, Copy)]
+/// Group separator between the fields of a structured carrier message
+const GS: u8 = 0x1D;
+/// Record separator of the transportation message header
+const RS: u8 = 0x1E;
+/// Country code of the United States
+const MAXI_CODE_US_COUNTRY_CODE: u16 = 840;
+/// Number of data characters of the standard (modes 2, 3, 4 and 6) and enhanced (mode 5)
+/// error correction secondary messages
+const MAXI_CODE_SECONDARY_MESSAGE_SIZE: usize = 84;
+const MAXI_CODE_ENHANCED_SECONDARY_MESSAGE_SIZE: usize = 68;
+/// Number of data characters of the primary message outside of modes 2 and 3
+const MAXI_CODE_PRIMARY_MESSAGE_SIZE: usize = 9;
+
+/// MaxiCode mode
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub enum MaxiCodeMode {
-    #[default]
+    /// Structured carrier message with a numeric postal code
     Mode2,
+    /// Structured carrier message with an alphanumeric postal code
     Mode3,
+    /// Standard symbol
+    #[default]
     Mode4,
+    /// Full error correction
     Mode5,
+    /// Reader programming
     Mode6,
 }
 
@@ -37,18 +57,394 @@ impl From<MaxiCodeMode> for u8 {
     }
 }
 
+impl MaxiCodeMode {
+    /// Get the number of data characters
+    fn capacity(&self) -> usize {
+        match self {
+            MaxiCodeMode::Mode2 | MaxiCodeMode::Mode3 => MAXI_CODE_SECONDARY_MESSAGE_SIZE,
+            MaxiCodeMode::Mode4 | MaxiCodeMode::Mode6 => {
+                MAXI_CODE_PRIMARY_MESSAGE_SIZE + MAXI_CODE_SECONDARY_MESSAGE_SIZE
+            }
+            MaxiCodeMode::Mode5 => MAXI_CODE_PRIMARY_MESSAGE_SIZE + MAXI_CODE_ENHANCED_SECONDARY_MESSAGE_SIZE,
+        }
+    }
+}
+
+/// MaxiCode postal code of a structured carrier message
+#[derive(Debug, Clone, PartialEq)]
+pub enum MaxiCodePostalCode {
+    /// Numeric postal code of up to 9 digits (US ZIP or ZIP+4), encoded in mode 2
+    Numeric(String),
+    /// Alphanumeric international postal code of up to 6 characters (A-Z, 0-9 and space), encoded in mode 3
+    Alphanumeric(String),
+}
+
+impl MaxiCodePostalCode {
+    /// Get the MaxiCode mode of the postal code
+    pub fn mode(&self) -> MaxiCodeMode {
+        match self {
+            MaxiCodePostalCode::Numeric(_) => MaxiCodeMode::Mode2,
+            MaxiCodePostalCode::Alphanumeric(_) => MaxiCodeMode::Mode3,
+        }
+    }
+
+    /// Check the postal code characters and length
+    fn check(&self) -> Result<()> {
+        let valid = match self {
+            MaxiCodePostalCode::Numeric(code) => {
+                (1..=9).contains(&code.len()) && code.chars().all(|c| c.is_ascii_digit())
+            }
+            MaxiCodePostalCode::Alphanumeric(code) => {
+                (1..=6).contains(&code.len())
+                    && code
+                        .chars()
+                        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == ' ')
+            }
+        };
+        if !valid {
+            return Err(PrinterError::Input(format!("invalid MaxiCode postal code: {self}")));
+        }
+        Ok(())
+    }
+
+    fn value(&self) -> &str {
+        match self {
+            MaxiCodePostalCode::Numeric(code) | MaxiCodePostalCode::Alphanumeric(code) => code,
+        }
+    }
+}
+
+impl fmt::Display for MaxiCodePostalCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MaxiCodePostalCode::Numeric(code) => write!(f, "{code} (numeric)"),
+            MaxiCodePostalCode::Alphanumeric(code) => write!(f, "{code} (alphanumeric)"),
+        }
+    }
+}
+
+/// MaxiCode structured carrier message (modes 2 and 3)
+///
+/// The data is the postal code, the country code, the service class and the secondary message,
+/// separated by GS (`0x1D`).
+#[derive(Debug, Clone)]
+pub struct MaxiCodeCarrierMessage {
+    postal_code: MaxiCodePostalCode,
+    country_code: u16,
+    service_class: u16,
+    secondary_message: Vec<u8>,
+}
+
+impl MaxiCodeCarrierMessage {
+    /// Create a new `MaxiCodeCarrierMessage`
+    ///
+    /// Country code (ISO 3166 numeric) and service class are 0 - 999.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let postal_code = MaxiCodePostalCode::Alphanumeric("B1050".to_string());
+    /// let message = MaxiCodeCarrierMessage::new(postal_code, 56, 1)?.secondary_message("PARCEL 1/2")?;
+    ///
+    /// assert_eq!(message.mode(), MaxiCodeMode::Mode3);
+    /// assert_eq!(message.data(), b"B1050\x1D056\x1D001\x1DPARCEL 1/2");
+    /// assert!(MaxiCodeCarrierMessage::new(MaxiCodePostalCode::Alphanumeric("b1050".to_string()), 56, 1).is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn new(postal_code: MaxiCodePostalCode, country_code: u16, service_class: u16) -> Result<Self> {
+        postal_code.check()?;
+        if country_code > 999 || service_class > 999 {
+            return Err(PrinterError::Input(format!(
+                "invalid MaxiCode country code or service class (0-999): {country_code}, {service_class}"
+            )));
+        }
+
+        Ok(Self {
+            postal_code,
+            country_code,
+            service_class,
+            secondary_message: Vec::new(),
+        })
+    }
+
+    /// Create a new `MaxiCodeCarrierMessage` with a US ZIP code (5 digits or 9 digits for ZIP+4)
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let message = MaxiCodeCarrierMessage::us("152382802", 1)?;
+    ///
+    /// assert_eq!(message.mode(), MaxiCodeMode::Mode2);
+    /// assert_eq!(message.data(), b"152382802\x1D840\x1D001\x1D");
+    /// assert!(MaxiCodeCarrierMessage::us("1523", 1).is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn us(zip_code: &str, service_class: u16) -> Result<Self> {
+        if ![5, 9].contains(&zip_code.len()) {
+            return Err(PrinterError::Input(format!(
+                "US ZIP code must have 5 or 9 digits: {zip_code}"
+            )));
+        }
+        Self::new(
+            MaxiCodePostalCode::Numeric(zip_code.to_string()),
+            MAXI_CODE_US_COUNTRY_CODE,
+            service_class,
+        )
+    }
+
+    /// Set the secondary message
+    pub fn secondary_message(self, message: &str) -> Result<Self> {
+        self.secondary_message_bytes(message.as_bytes())
+    }
+
+    /// Set the secondary message from binary data
+    pub fn secondary_message_bytes(mut self, message: &[u8]) -> Result<Self> {
+        self.secondary_message = message.to_vec();
+        check_capacity(&self.secondary_message, self.mode())?;
+        Ok(self)
+    }
+
+    /// Get the postal code
+    pub fn postal_code(&self) -> &MaxiCodePostalCode {
+        &self.postal_code
+    }
+
+    /// Get the MaxiCode mode (2 for numeric postal codes, 3 for alphanumeric ones)
+    pub fn mode(&self) -> MaxiCodeMode {
+        self.postal_code.mode()
+    }
+
+    /// Get the symbol data
+    pub fn data(&self) -> Vec<u8> {
+        let mut data = self.postal_code.value().as_bytes().to_vec();
+        data.push(GS);
+        data.extend_from_slice(format!("{:03}", self.country_code).as_bytes());
+        data.push(GS);
+        data.extend_from_slice(format!("{:03}", self.service_class).as_bytes());
+        data.push(GS);
+        data.extend_from_slice(&self.secondary_message);
+        data
+    }
+}
+
+/// Get the number of MaxiCode characters encoding the data
+///
+/// Code set A characters use one character, runs of 9 digits or more use 6 characters per 9 digits,
+/// lowercase runs latch to code set B and other bytes are shifted (two characters each).
+fn encoded_length(data: &[u8]) -> usize {
+    let in_set_a =
+        |b: u8| b.is_ascii_uppercase() || b.is_ascii_digit() || b" \r\x1C\x1D\x1E\"#$%&'()*+,-./:".contains(&b);
+    let in_set_b = |b: u8| b.is_ascii_lowercase() || b"!;<=>?@[\\]^_`{|}~".contains(&b);
+
+    let mut length = 0;
+    let mut i = 0;
+    while i < data.len() {
+        let run = |predicate: &dyn Fn(u8) -> bool| data[i..].iter().take_while(|&&b| predicate(b)).count();
+        let digits = run(&|b: u8| b.is_ascii_digit());
+        if digits >= 9 {
+            length += digits / 9 * 6;
+            i += digits / 9 * 9;
+            continue;
+        }
+        let set_b = run(&in_set_b);
+        if set_b > 0 {
+            // Latch to code set B and back, or shift each character
+            length += (set_b + 2).min(set_b * 2);
+            i += set_b;
+        } else {
+            length += if in_set_a(data[i]) { 1 } else { 2 };
+            i += 1;
+        }
+    }
+    length
+}
+
+/// Check that the data fits in the symbol
+fn check_capacity(data: &[u8], mode: MaxiCodeMode) -> Result<()> {
+    let length = encoded_length(data);
+    if length > mode.capacity() {
+        return Err(PrinterError::Input(format!(
+            "MaxiCode data is too long for {mode}: {length} characters (maximum {})",
+            mode.capacity()
+        )));
+    }
+    Ok(())
+}
+
+/// Check the structured carrier message of modes 2 and 3
+///
+/// The data can start with the transportation message header `[)>` RS `01` GS and a two-digit year,
+/// which is encoded in the secondary message.
+fn check_carrier_message(data: &[u8], mode: MaxiCodeMode) -> Result<()> {
+    let invalid = |reason: &str| PrinterError::Input(format!("invalid {mode} structured carrier message: {reason}"));
+
+    let (header, message) = match data.strip_prefix(&[b'[', b')', b'>', RS, b'0', b'1', GS][..]) {
+        Some(rest) if rest.len() >= 2 && rest[..2].iter().all(u8::is_ascii_digit) => {
+            data.split_at(data.len() - rest.len() + 2)
+        }
+        Some(_) => return Err(invalid("two-digit year expected after the message header")),
+        None => (&data[..0], data),
+    };
+
+    let mut fields = message.splitn(4, |&b| b == GS);
+    let (Some(postal_code), Some(country_code), Some(service_class)) = (fields.next(), fields.next(), fields.next())
+    else {
+        return Err(invalid(
+            "postal code, country code and service class separated by GS expected",
+        ));
+    };
+    let postal_code = String::from_utf8_lossy(postal_code).to_string();
+    let postal_code = match mode {
+        MaxiCodeMode::Mode2 => MaxiCodePostalCode::Numeric(postal_code),
+        _ => MaxiCodePostalCode::Alphanumeric(postal_code),
+    };
+    postal_code.check()?;
+    for field in [country_code, service_class] {
+        if field.len() != 3 || !field.iter().all(u8::is_ascii_digit) {
+            return Err(invalid("country code and service class must have 3 digits"));
+        }
+    }
+
+    let mut secondary_message = header.to_vec();
+    secondary_message.extend_from_slice(fields.next().unwrap_or_default());
+    check_capacity(&secondary_message, mode)
+}
+
+/// MaxiCode
 #[derive(Debug)]
 pub struct MaxiCode {
-    pub data: String,
+    pub data: Vec<u8>,
     pub mode: MaxiCodeMode,
+    /// Character set of text data
+    pub eci: Option<Eci>,
 }
 
 impl MaxiCode {
-    /// Create a new `MaxiCode`
-    pub fn new(data: &str, mode: MaxiCodeMode) -> Self {
-        Self {
-            data: data.to_string(),
+    /// Create a new `MaxiCode` (UTF-8 text)
+    ///
+    /// Modes 2 and 3 data must be a structured carrier message (see [`MaxiCodeCarrierMessage`]).
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// assert!(MaxiCode::new("1245789658745", MaxiCodeMode::Mode4).is_ok());
+    /// assert!(MaxiCode::new("1245789658745", MaxiCodeMode::Mode2).is_err());
+    /// assert!(MaxiCode::new("152382802\x1D840\x1D001\x1DPARCEL", MaxiCodeMode::Mode2).is_ok());
+    /// assert!(MaxiCode::new(&"A".repeat(78), MaxiCodeMode::Mode5).is_err());
+    /// ```
+    pub fn new(data: &str, mode: MaxiCodeMode) -> Result<Self> {
+        Self::from_bytes(data.as_bytes(), mode)
+    }
+
+    /// Create a new `MaxiCode` from binary data
+    pub fn from_bytes(data: &[u8], mode: MaxiCodeMode) -> Result<Self> {
+        match mode {
+            MaxiCodeMode::Mode2 | MaxiCodeMode::Mode3 => check_carrier_message(data, mode)?,
+            _ => check_capacity(data, mode)?,
+        }
+
+        Ok(Self {
+            data: data.to_vec(),
             mode,
+            eci: None,
+        })
+    }
+
+    /// Create a new `MaxiCode` from a structured carrier message (mode 2 or 3)
+    pub fn from_carrier_message(message: &MaxiCodeCarrierMessage) -> Self {
+        Self {
+            data: message.data(),
+            mode: message.mode(),
+            eci: None,
         }
     }
+
+    /// Create a new `MaxiCode` with text encoded in a character set
+    ///
+    /// ESC/POS having no ECI function, MaxiCode with an ECI cannot be printed.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let code = MaxiCode::with_eci("Café", Eci::Latin1, MaxiCodeMode::default())?;
+    ///
+    /// assert_eq!(code.data, vec![b'C', b'a', b'f', 0xE9]);
+    /// assert!(MaxiCode::with_eci("€", Eci::Latin1, MaxiCodeMode::default()).is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn with_eci(data: &str, eci: Eci, mode: MaxiCodeMode) -> Result<Self> {
+        let mut code = Self::from_bytes(&eci.encode(data)?, mode)?;
+        code.eci = Some(eci);
+        Ok(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maxi_code_encoded_length() {
+        assert_eq!(encoded_length(b""), 0);
+        assert_eq!(encoded_length(b"ABC 123"), 7);
+        assert_eq!(encoded_length(b"123456789"), 6);
+        assert_eq!(encoded_length(b"1234567890"), 7);
+        assert_eq!(encoded_length(b"Ab"), 3);
+        assert_eq!(encoded_length(b"Abcdef"), 8);
+        assert_eq!(encoded_length(&[0xE9]), 2);
+    }
+
+    #[test]
+    fn test_maxi_code_capacity() {
+        assert!(MaxiCode::new(&"A".repeat(93), MaxiCodeMode::Mode4).is_ok());
+        assert!(MaxiCode::new(&"A".repeat(94), MaxiCodeMode::Mode4).is_err());
+        assert!(MaxiCode::new(&"A".repeat(93), MaxiCodeMode::Mode6).is_ok());
+        assert!(MaxiCode::new(&"A".repeat(77), MaxiCodeMode::Mode5).is_ok());
+        assert!(MaxiCode::new(&"1".repeat(138), MaxiCodeMode::Mode4).is_ok());
+        assert!(MaxiCode::new(&"a".repeat(92), MaxiCodeMode::Mode4).is_err());
+    }
+
+    #[test]
+    fn test_maxi_code_carrier_message() {
+        assert!(MaxiCode::new("152382802\x1D840\x1D001", MaxiCodeMode::Mode2).is_ok());
+        assert!(MaxiCode::new(
+            "[)>\x1E01\x1D96152382802\x1D840\x1D001\x1D1Z00004951",
+            MaxiCodeMode::Mode2
+        )
+        .is_ok());
+        assert!(MaxiCode::new("[)>\x1E01\x1DA52382802\x1D840\x1D001", MaxiCodeMode::Mode2).is_err());
+        assert!(MaxiCode::new("1523828021\x1D840\x1D001", MaxiCodeMode::Mode2).is_err());
+        assert!(MaxiCode::new("B1050\x1D056\x1D001", MaxiCodeMode::Mode2).is_err());
+        assert!(MaxiCode::new("B1050\x1D056\x1D001", MaxiCodeMode::Mode3).is_ok());
+        assert!(MaxiCode::new("B1050\x1D56\x1D001", MaxiCodeMode::Mode3).is_err());
+        assert!(MaxiCode::new("B1050\x1D056", MaxiCodeMode::Mode3).is_err());
+        assert!(MaxiCode::new(
+            &format!("B1050\x1D056\x1D001\x1D{}", "A".repeat(84)),
+            MaxiCodeMode::Mode3
+        )
+        .is_ok());
+        assert!(MaxiCode::new(
+            &format!("B1050\x1D056\x1D001\x1D{}", "A".repeat(85)),
+            MaxiCodeMode::Mode3
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_maxi_code_carrier_message_builder() {
+        let postal_code = MaxiCodePostalCode::Numeric("1234567890".to_string());
+        assert!(MaxiCodeCarrierMessage::new(postal_code, 840, 1).is_err());
+        let postal_code = MaxiCodePostalCode::Alphanumeric("ABCDEFG".to_string());
+        assert!(MaxiCodeCarrierMessage::new(postal_code, 250, 1).is_err());
+        let postal_code = MaxiCodePostalCode::Alphanumeric("75008".to_string());
+        assert!(MaxiCodeCarrierMessage::new(postal_code.clone(), 1000, 1).is_err());
+        assert!(MaxiCodeCarrierMessage::new(postal_code.clone(), 250, 1000).is_err());
+
+        let message = MaxiCodeCarrierMessage::new(postal_code, 250, 7).unwrap();
+        assert!(message.clone().secondary_message(&"A".repeat(85)).is_err());
+        let code = MaxiCode::from_carrier_message(&message.secondary_message("1Z").unwrap());
+        assert_eq!(code.mode, MaxiCodeMode::Mode3);
+        assert_eq!(code.data, b"75008\x1D250\x1D007\x1D1Z");
+        assert!(MaxiCode::from_bytes(&code.data, code.mode).is_ok());
+    }
 }
@@ -1,7 +1,10 @@
 //! Barcodes and 2D codes
 mod aztec;
 mod barcodes;
+mod composite;
 mod data_matrix;
+mod eci;
+mod gs1;
 mod gs1_databar_2d;
 mod maxi_code;
 mod pdf417;
@@ -10,12 +13,21 @@ mod qrcode;
 #[cfg(feature = "barcodes")]
 pub use barcodes::*;
 
+#[cfg(any(feature = "barcodes", feature = "codes_2d"))]
+pub use gs1::*;
+
 #[cfg(feature = "codes_2d")]
 pub use aztec::*;
 
+#[cfg(feature = "codes_2d")]
+pub use composite::*;
+
 #[cfg(feature = "codes_2d")]
 pub use data_matrix::*;
 
+#[cfg(feature = "codes_2d")]
+pub use eci::*;
+
 #[cfg(feature = "codes_2d")]
 pub use gs1_databar_2d::*;
 
@@ -1,5 +1,6 @@
 //! PDF417
 
+use super::eci::Eci;
 use crate::errors::{PrinterError, Result};
 use std::fmt;
 
@@ -166,18 +167,45 @@ impl Pdf417Option {
 /// PDF417
 #[derive(Debug)]
 pub struct Pdf417 {
-    pub data: String,
+    pub data: Vec<u8>,
     pub option: Pdf417Option,
+    /// Character set of text data
+    pub eci: Option<Eci>,
 }
 
 impl Pdf417 {
-    /// Create a new `Pdf417`
+    /// Create a new `Pdf417` (UTF-8 text)
     pub fn new(data: &str, option: Pdf417Option) -> Self {
+        Self::from_bytes(data.as_bytes(), option)
+    }
+
+    /// Create a new `Pdf417` from binary data
+    pub fn from_bytes(data: &[u8], option: Pdf417Option) -> Self {
         Self {
-            data: data.to_string(),
+            data: data.to_vec(),
             option,
+            eci: None,
         }
     }
+
+    /// Create a new `Pdf417` with text encoded in a character set
+    ///
+    /// ESC/POS having no ECI function, PDF417 with an ECI cannot be printed with commands.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let code = Pdf417::with_eci("Café", Eci::Latin1, Pdf417Option::default())?;
+    ///
+    /// assert_eq!(code.data, vec![b'C', b'a', b'f', 0xE9]);
+    /// assert!(Pdf417::with_eci("€", Eci::Latin1, Pdf417Option::default()).is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn with_eci(data: &str, eci: Eci, option: Pdf417Option) -> Result<Self> {
+        let mut code = Self::from_bytes(&eci.encode(data)?, option);
+        code.eci = Some(eci);
+        Ok(code)
+    }
 }
 
 #[cfg(test)]
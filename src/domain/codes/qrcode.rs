@@ -2,13 +2,71 @@
 
 #![cfg(feature = "codes_2d")]
 
-use crate::errors::Result;
+use super::eci::Eci;
+use crate::errors::{PrinterError, Result};
 use std::fmt;
 
-const QRCODE_MAX_DATA_SIZE: usize = 7089;
+/// Number of characters of the largest Model 1 symbol (version 14, error correction level L)
+/// in numeric, alphanumeric and byte modes
+const QRCODE_MODEL1_MAX_CHARACTERS: [usize; 3] = [1167, 707, 486];
+/// Maximum module size
+const QRCODE_MAX_SIZE: u8 = 15;
+/// Module size used by the printer when the size is 0
+const QRCODE_DEFAULT_SIZE: u8 = 4;
+/// Characters of the alphanumeric mode
+const QRCODE_ALPHANUMERIC_CHARS: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+/// Number of data bits of Micro QR versions (M1 - M4) for each error correction level (L, M, Q), 0 if not available
+const MICRO_QRCODE_DATA_BITS: [[u16; 3]; 4] = [[20, 0, 0], [40, 32, 0], [84, 68, 0], [128, 112, 80]];
+/// Maximum number of symbols linked with structured append
+const QRCODE_STRUCTURED_APPEND_MAX_SYMBOLS: usize = 16;
+/// Number of bits of the structured append header (mode indicator, position, total and parity)
+const QRCODE_STRUCTURED_APPEND_HEADER_BITS: usize = 20;
+/// Number of data codewords of Model 2 versions (1 - 40) for each error correction level (L, M, Q, H)
+const QRCODE_DATA_CODEWORDS: [[u16; 4]; 40] = [
+    [19, 16, 13, 9],
+    [34, 28, 22, 16],
+    [55, 44, 34, 26],
+    [80, 64, 48, 36],
+    [108, 86, 62, 46],
+    [136, 108, 76, 60],
+    [156, 124, 88, 66],
+    [194, 154, 110, 86],
+    [232, 182, 132, 100],
+    [274, 216, 154, 122],
+    [324, 254, 180, 140],
+    [370, 290, 206, 158],
+    [428, 334, 244, 180],
+    [461, 365, 261, 197],
+    [523, 415, 295, 223],
+    [589, 453, 325, 253],
+    [647, 507, 367, 283],
+    [721, 563, 397, 313],
+    [795, 627, 445, 341],
+    [861, 669, 485, 385],
+    [932, 714, 512, 406],
+    [1006, 782, 568, 442],
+    [1094, 860, 614, 464],
+    [1174, 914, 664, 514],
+    [1276, 1000, 718, 538],
+    [1370, 1062, 754, 596],
+    [1468, 1128, 808, 628],
+    [1531, 1193, 871, 661],
+    [1631, 1267, 911, 701],
+    [1735, 1373, 985, 745],
+    [1843, 1455, 1033, 793],
+    [1955, 1541, 1115, 845],
+    [2071, 1631, 1171, 901],
+    [2191, 1725, 1231, 961],
+    [2306, 1812, 1286, 986],
+    [2434, 1914, 1354, 1054],
+    [2566, 1992, 1426, 1096],
+    [2702, 2102, 1502, 1142],
+    [2812, 2216, 1582, 1222],
+    [2956, 2334, 1666, 1276],
+];
 
 /// QR Code model
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum QRCodeModel {
     Model1,
     Model2,
@@ -36,7 +94,7 @@ impl fmt::Display for QRCodeModel {
 }
 
 /// QR Code error correction level
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum QRCodeCorrectionLevel {
     L,
     M,
@@ -66,20 +124,207 @@ impl fmt::Display for QRCodeCorrectionLevel {
     }
 }
 
+impl QRCodeCorrectionLevel {
+    /// Get the index in `QRCODE_DATA_CODEWORDS`
+    fn index(&self) -> usize {
+        match self {
+            QRCodeCorrectionLevel::L => 0,
+            QRCodeCorrectionLevel::M => 1,
+            QRCodeCorrectionLevel::Q => 2,
+            QRCodeCorrectionLevel::H => 3,
+        }
+    }
+}
+
+/// QR code data encoding mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QRCodeMode {
+    /// Digits only
+    Numeric,
+    /// Digits, uppercase letters, space and `$%*+-./:`
+    Alphanumeric,
+    /// Any byte
+    Byte,
+}
+
+impl QRCodeMode {
+    /// Get the most compact mode encoding all the data
+    ///
+    /// ```
+    /// use escpos::utils::QRCodeMode;
+    ///
+    /// assert_eq!(QRCodeMode::detect(b"0123"), QRCodeMode::Numeric);
+    /// assert_eq!(QRCodeMode::detect(b"HTTPS://EXAMPLE.COM"), QRCodeMode::Alphanumeric);
+    /// assert_eq!(QRCodeMode::detect(b"https://example.com"), QRCodeMode::Byte);
+    /// ```
+    pub fn detect(data: &[u8]) -> Self {
+        if data.iter().all(u8::is_ascii_digit) {
+            QRCodeMode::Numeric
+        } else if data.iter().all(|c| QRCODE_ALPHANUMERIC_CHARS.contains(c)) {
+            QRCodeMode::Alphanumeric
+        } else {
+            QRCodeMode::Byte
+        }
+    }
+
+    /// Get the maximum number of characters of a Model 2 version (1 - 40)
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// assert_eq!(QRCodeMode::Numeric.capacity(40, QRCodeCorrectionLevel::L)?, 7089);
+    /// assert_eq!(QRCodeMode::Byte.capacity(1, QRCodeCorrectionLevel::H)?, 7);
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn capacity(&self, version: u8, correction_level: QRCodeCorrectionLevel) -> Result<usize> {
+        self.symbol_capacity(SymbolVersion::Normal(version), correction_level, 0)
+    }
+
+    /// Get the maximum number of characters of a Micro QR version (M1 - M4)
+    ///
+    /// M1 only supports the numeric mode and the error correction level L (error detection),
+    /// M2 and M3 the levels L and M, M4 the levels L, M and Q. The byte mode requires M3 or M4.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// assert_eq!(QRCodeMode::Numeric.micro_capacity(1, QRCodeCorrectionLevel::L)?, 5);
+    /// assert_eq!(QRCodeMode::Byte.micro_capacity(2, QRCodeCorrectionLevel::L)?, 0);
+    /// assert_eq!(QRCodeMode::Byte.micro_capacity(4, QRCodeCorrectionLevel::Q)?, 9);
+    /// assert!(QRCodeMode::Byte.micro_capacity(3, QRCodeCorrectionLevel::Q).is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn micro_capacity(&self, version: u8, correction_level: QRCodeCorrectionLevel) -> Result<usize> {
+        self.symbol_capacity(SymbolVersion::Micro(version), correction_level, 0)
+    }
+
+    /// Get the maximum number of characters of a version, after a header of `header_bits`
+    fn symbol_capacity(
+        &self,
+        version: SymbolVersion,
+        correction_level: QRCodeCorrectionLevel,
+        header_bits: usize,
+    ) -> Result<usize> {
+        let bits = version.data_bits(correction_level)?.saturating_sub(header_bits);
+        let Some(count_bits) = self.count_bits(version) else {
+            return Ok(0);
+        };
+
+        Ok(match bits.checked_sub(version.mode_bits() + count_bits) {
+            None => 0,
+            Some(bits) => match self {
+                QRCodeMode::Numeric => 3 * (bits / 10) + [0, 0, 0, 0, 1, 1, 1, 2, 2, 2][bits % 10],
+                QRCodeMode::Alphanumeric => 2 * (bits / 11) + usize::from(bits % 11 >= 6),
+                QRCodeMode::Byte => bits / 8,
+            },
+        })
+    }
+
+    /// Get the length of the character count indicator, `None` if the mode is not supported by the version
+    fn count_bits(&self, version: SymbolVersion) -> Option<usize> {
+        match version {
+            SymbolVersion::Normal(version) => {
+                let index = match version {
+                    0..=9 => 0,
+                    10..=26 => 1,
+                    _ => 2,
+                };
+                Some(match self {
+                    QRCodeMode::Numeric => [10, 12, 14][index],
+                    QRCodeMode::Alphanumeric => [9, 11, 13][index],
+                    QRCodeMode::Byte => [8, 16, 16][index],
+                })
+            }
+            SymbolVersion::Micro(version) => {
+                let bits = match self {
+                    QRCodeMode::Numeric => [Some(3), Some(4), Some(5), Some(6)],
+                    QRCodeMode::Alphanumeric => [None, Some(3), Some(4), Some(5)],
+                    QRCodeMode::Byte => [None, None, Some(4), Some(5)],
+                };
+                bits.get(usize::from(version).wrapping_sub(1)).copied().flatten()
+            }
+        }
+    }
+
+    /// Get the number of bits of encoded data (mode indicator, character count indicator and characters)
+    fn encoded_bits(&self, length: usize, version: SymbolVersion) -> Option<usize> {
+        let characters = match self {
+            QRCodeMode::Numeric => 10 * (length / 3) + [0, 4, 7][length % 3],
+            QRCodeMode::Alphanumeric => 11 * (length / 2) + 6 * (length % 2),
+            QRCodeMode::Byte => 8 * length,
+        };
+        Some(version.mode_bits() + self.count_bits(version)? + characters)
+    }
+}
+
+impl fmt::Display for QRCodeMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QRCodeMode::Numeric => write!(f, "numeric"),
+            QRCodeMode::Alphanumeric => write!(f, "alphanumeric"),
+            QRCodeMode::Byte => write!(f, "byte"),
+        }
+    }
+}
+
+/// Model 2 or Micro QR version
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SymbolVersion {
+    /// Model 2 version (1 - 40)
+    Normal(u8),
+    /// Micro QR version (M1 - M4)
+    Micro(u8),
+}
+
+impl SymbolVersion {
+    /// Get the number of data bits for an error correction level
+    fn data_bits(&self, correction_level: QRCodeCorrectionLevel) -> Result<usize> {
+        match *self {
+            SymbolVersion::Normal(version) => {
+                let codewords = QRCODE_DATA_CODEWORDS
+                    .get(usize::from(version).wrapping_sub(1))
+                    .ok_or_else(|| PrinterError::Input(format!("invalid QR code version (1-40): {version}")))?;
+                Ok(8 * usize::from(codewords[correction_level.index()]))
+            }
+            SymbolVersion::Micro(version) => {
+                let bits = MICRO_QRCODE_DATA_BITS
+                    .get(usize::from(version).wrapping_sub(1))
+                    .ok_or_else(|| PrinterError::Input(format!("invalid Micro QR code version (1-4): {version}")))?;
+                match bits.get(correction_level.index()) {
+                    Some(&bits) if bits > 0 => Ok(usize::from(bits)),
+                    _ => Err(PrinterError::Input(format!(
+                        "Micro QR code M{version} does not support error correction level {correction_level:?}"
+                    ))),
+                }
+            }
+        }
+    }
+
+    /// Get the length of the mode indicator
+    fn mode_bits(&self) -> usize {
+        match *self {
+            SymbolVersion::Normal(_) => 4,
+            SymbolVersion::Micro(version) => usize::from(version.saturating_sub(1)),
+        }
+    }
+}
+
 /// QR code option
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct QRCodeOption {
     model: QRCodeModel,
     size: u8,
     correction_level: QRCodeCorrectionLevel,
+    auto_size: bool,
 }
 
 impl Default for QRCodeOption {
     fn default() -> Self {
         Self {
-            model: QRCodeModel::Model1,
+            model: QRCodeModel::Model2,
             size: 4,
             correction_level: QRCodeCorrectionLevel::H,
+            auto_size: false,
         }
     }
 }
@@ -91,9 +336,29 @@ impl QRCodeOption {
             model,
             size,
             correction_level,
+            auto_size: false,
         }
     }
 
+    /// Use the largest module size fitting the paper width (Model 2 and Micro QR only)
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = QRCodeOption::new(QRCodeModel::Model2, 4, QRCodeCorrectionLevel::M).auto_size();
+    ///
+    /// assert!(option.is_auto_size());
+    /// ```
+    pub fn auto_size(mut self) -> Self {
+        self.auto_size = true;
+        self
+    }
+
+    /// Is the module size selected automatically?
+    pub fn is_auto_size(&self) -> bool {
+        self.auto_size
+    }
+
     /// Get model
     pub fn model(&self) -> QRCodeModel {
         self.model
@@ -110,31 +375,300 @@ impl QRCodeOption {
     }
 }
 
+/// Position of a QR code in a sequence of linked symbols (structured append)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QRCodeStructuredAppend {
+    /// Symbol position (0 - 15)
+    pub index: u8,
+    /// Number of symbols (1 - 16)
+    pub total: u8,
+    /// Parity of the whole data (XOR of all the bytes)
+    pub parity: u8,
+}
+
 /// QR code
 #[derive(Debug)]
 pub struct QRCode {
-    pub data: String,
+    pub data: Vec<u8>,
     pub option: QRCodeOption,
+    /// Character set of text data
+    pub eci: Option<Eci>,
+    /// Position in a sequence of linked symbols
+    pub structured_append: Option<QRCodeStructuredAppend>,
 }
 
 impl QRCode {
-    /// Create a new `QRCode`
+    /// Create a new `QRCode` (UTF-8 text)
     pub fn new(data: &str, option: Option<QRCodeOption>) -> Result<Self> {
-        Self::check_data(data)?;
+        Self::from_bytes(data.as_bytes(), option)
+    }
+
+    /// Create a new `QRCode` from binary data
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let token = [0x00, 0xA5, 0xFF, 0x10];
+    /// let qrcode = QRCode::from_bytes(&token, None)?;
+    ///
+    /// assert_eq!(qrcode.data, token);
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn from_bytes(data: &[u8], option: Option<QRCodeOption>) -> Result<Self> {
+        let option = option.unwrap_or_default();
+        Self::check_data(data, &option)?;
 
         Ok(Self {
-            data: data.to_string(),
-            option: option.unwrap_or_default(),
+            data: data.to_vec(),
+            option,
+            eci: None,
+            structured_append: None,
+        })
+    }
+
+    /// Create a new `QRCode` with text encoded in a character set
+    ///
+    /// The ECI designator is encoded in the symbol, the QR code is printed as image (`codes_2d_image` feature).
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let qrcode = QRCode::with_eci("Café", Eci::Latin1, None)?;
+    ///
+    /// assert_eq!(qrcode.data, vec![b'C', b'a', b'f', 0xE9]);
+    /// assert_eq!(qrcode.eci, Some(Eci::Latin1));
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn with_eci(data: &str, eci: Eci, option: Option<QRCodeOption>) -> Result<Self> {
+        let mut code = Self::from_bytes(&eci.encode(data)?, option)?;
+        code.eci = Some(eci);
+        Ok(code)
+    }
+
+    /// Split data in linked Model 2 QR codes of at most `max_version` (structured append)
+    ///
+    /// The data is split in the smallest number of symbols (up to 16) of similar length.
+    /// Scanners supporting structured append rebuild the data from the symbols, in any order.
+    /// ESC/POS printers do not support structured append: the symbols are printed as images
+    /// (`codes_2d_image` feature).
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = QRCodeOption::new(QRCodeModel::Model2, 2, QRCodeCorrectionLevel::M);
+    /// let url = format!("https://example.com/invoice?signature={}", "A1b2".repeat(200));
+    /// let qrcodes = QRCode::structured_append(url.as_bytes(), 10, option)?;
+    ///
+    /// assert_eq!(qrcodes.len(), 4);
+    /// assert_eq!(qrcodes[3].structured_append.unwrap().index, 3);
+    /// assert!(qrcodes.iter().all(|qrcode| qrcode.version().unwrap() <= 10));
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn structured_append(data: &[u8], max_version: u8, option: QRCodeOption) -> Result<Vec<Self>> {
+        if option.model != QRCodeModel::Model2 {
+            return Err(PrinterError::Input(format!(
+                "QR code structured append requires Model 2, not {}",
+                option.model
+            )));
+        }
+
+        let mode = QRCodeMode::detect(data);
+        let capacity = mode.symbol_capacity(
+            SymbolVersion::Normal(max_version),
+            option.correction_level,
+            QRCODE_STRUCTURED_APPEND_HEADER_BITS,
+        )?;
+        let max_length = QRCODE_STRUCTURED_APPEND_MAX_SYMBOLS * capacity;
+        if data.len() > max_length {
+            return Err(PrinterError::Input(format!(
+                "QR code data is too long for {QRCODE_STRUCTURED_APPEND_MAX_SYMBOLS} symbols of version {max_version}: \
+                {} characters in {mode} mode, max {max_length}",
+                data.len()
+            )));
+        }
+
+        let total = data.len().div_ceil(capacity).max(1);
+        let chunk_length = data.len().div_ceil(total).max(1);
+        let parity = data.iter().fold(0, |parity, byte| parity ^ byte);
+        let chunks: Vec<&[u8]> = match data.is_empty() {
+            true => vec![data],
+            false => data.chunks(chunk_length).collect(),
+        };
+
+        Ok(chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, chunk)| Self {
+                data: chunk.to_vec(),
+                option: option.clone(),
+                eci: None,
+                structured_append: Some(QRCodeStructuredAppend {
+                    index: index as u8,
+                    total: total as u8,
+                    parity,
+                }),
+            })
+            .collect())
+    }
+
+    /// Get the minimum version encoding the data: 1 - 40 for Model 2, 1 - 4 (M1 - M4) for Micro QR
+    ///
+    /// The data is encoded in a single [mode](QRCodeMode), the printer may use a smaller version.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = QRCodeOption::new(QRCodeModel::Model2, 4, QRCodeCorrectionLevel::M);
+    /// let qrcode = QRCode::new("https://github.com/fabienbellanger/escpos-rs", Some(option))?;
+    ///
+    /// assert_eq!(qrcode.version()?, 4);
+    /// assert_eq!(qrcode.modules()?, 33);
+    /// assert_eq!(qrcode.width()?, 132);
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn version(&self) -> Result<u8> {
+        if self.option.model == QRCodeModel::Model1 {
+            return Err(PrinterError::Input(format!(
+                "QR code capacity is only known for Model 2 and Micro QR, not {}",
+                self.option.model
+            )));
+        }
+        let header_bits = match self.structured_append {
+            Some(_) => QRCODE_STRUCTURED_APPEND_HEADER_BITS,
+            None => 0,
+        };
+        Self::min_version(&self.data, &self.option, header_bits)
+    }
+
+    /// Get the number of modules on each side (quiet zone excluded)
+    pub fn modules(&self) -> Result<u32> {
+        let version = u32::from(self.version()?);
+        Ok(match self.option.model {
+            QRCodeModel::Micro => 9 + 2 * version,
+            _ => 17 + 4 * version,
         })
     }
 
+    /// Get the printed width in dots with the option module size (quiet zone excluded)
+    pub fn width(&self) -> Result<u32> {
+        Ok(self.modules()? * u32::from(self.module_size()))
+    }
+
+    /// Get the module size in dots (the printer default size if 0)
+    pub(crate) fn module_size(&self) -> u8 {
+        match self.option.size {
+            0 => QRCODE_DEFAULT_SIZE,
+            size => size.min(QRCODE_MAX_SIZE),
+        }
+    }
+
+    /// Get the largest module size (1 - 15) fitting a width in dots
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = QRCodeOption::new(QRCodeModel::Model2, 4, QRCodeCorrectionLevel::M);
+    /// let qrcode = QRCode::new("https://github.com/fabienbellanger/escpos-rs", Some(option))?;
+    ///
+    /// // 33 modules
+    /// assert_eq!(qrcode.largest_size(384)?, 11);
+    /// assert!(qrcode.largest_size(32).is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn largest_size(&self, width: u32) -> Result<u8> {
+        self.largest_size_with_quiet_zone(width, 0)
+    }
+
+    /// Select the module size if automatic and check the width fits the paper width
+    ///
+    /// The width is only checked for Model 2 and Micro QR.
+    pub(crate) fn fit_paper_width(self, paper_width: u32) -> Result<Self> {
+        self.fit_paper_width_with_quiet_zone(paper_width, 0)
+    }
+
+    /// Same as [`QRCode::fit_paper_width()`], with a quiet zone of `quiet_zone` modules on each side
+    pub(crate) fn fit_paper_width_with_quiet_zone(mut self, paper_width: u32, quiet_zone: u32) -> Result<Self> {
+        if self.option.auto_size {
+            self.option.size = self.largest_size_with_quiet_zone(paper_width, quiet_zone)?;
+        } else if self.option.model != QRCodeModel::Model1 {
+            let width = (self.modules()? + 2 * quiet_zone) * u32::from(self.module_size());
+            if width > paper_width {
+                return Err(PrinterError::Input(format!(
+                    "QR code is too wide for the paper ({width} dots, max {paper_width})"
+                )));
+            }
+        }
+        Ok(self)
+    }
+
+    /// Get the largest module size fitting a width in dots, with a quiet zone of `quiet_zone` modules on each side
+    fn largest_size_with_quiet_zone(&self, width: u32, quiet_zone: u32) -> Result<u8> {
+        let modules = self.modules()?;
+        match u8::try_from(width / (modules + 2 * quiet_zone))
+            .unwrap_or(u8::MAX)
+            .min(QRCODE_MAX_SIZE)
+        {
+            0 => Err(PrinterError::Input(format!(
+                "QR code of {modules} modules does not fit {width} dots"
+            ))),
+            size => Ok(size),
+        }
+    }
+
+    /// Get the minimum Model 2 or Micro QR version encoding data after a header of `header_bits`
+    fn min_version(data: &[u8], option: &QRCodeOption, header_bits: usize) -> Result<u8> {
+        let (correction_level, micro) = (option.correction_level, option.model == QRCodeModel::Micro);
+        if micro && correction_level == QRCodeCorrectionLevel::H {
+            return Err(PrinterError::Input(
+                "Micro QR code error correction level must be L, M or Q".to_owned(),
+            ));
+        }
+
+        let (max_version, symbol): (u8, fn(u8) -> SymbolVersion) = match micro {
+            true => (4, SymbolVersion::Micro),
+            false => (40, SymbolVersion::Normal),
+        };
+        let mode = QRCodeMode::detect(data);
+        for version in 1..=max_version {
+            // Micro QR versions do not support all the levels and modes
+            let (Ok(bits), Some(encoded_bits)) = (
+                symbol(version).data_bits(correction_level),
+                mode.encoded_bits(data.len(), symbol(version)),
+            ) else {
+                continue;
+            };
+            if header_bits + encoded_bits <= bits {
+                return Ok(version);
+            }
+        }
+
+        let name = if micro { "Micro QR code" } else { "QR code" };
+        Err(PrinterError::Input(format!(
+            "{name} data is too long for error correction level {correction_level:?}: \
+            {} characters in {mode} mode, max {}",
+            data.len(),
+            mode.symbol_capacity(symbol(max_version), correction_level, header_bits)?
+        )))
+    }
+
     /// Check data
-    fn check_data(data: &str) -> Result<()> {
-        let bytes = data.as_bytes();
-        let data_len = bytes.len();
-        if data_len > QRCODE_MAX_DATA_SIZE {
-            return Err(crate::errors::PrinterError::Input(format!(
-                "QR code data is too long ({data_len}), its length should be smaller than 7090"
+    ///
+    /// Model 1 data is checked against the capacity of its largest symbol in the data mode.
+    fn check_data(data: &[u8], option: &QRCodeOption) -> Result<()> {
+        if option.model != QRCodeModel::Model1 {
+            return Self::min_version(data, option, 0).map(|_| ());
+        }
+
+        let mode = QRCodeMode::detect(data);
+        let max_length = QRCODE_MODEL1_MAX_CHARACTERS[match mode {
+            QRCodeMode::Numeric => 0,
+            QRCodeMode::Alphanumeric => 1,
+            QRCodeMode::Byte => 2,
+        }];
+        if data.len() > max_length {
+            return Err(PrinterError::Input(format!(
+                "QR code Model 1 data is too long: {} characters in {mode} mode, max {max_length}",
+                data.len()
             )));
         }
         Ok(())
@@ -158,10 +692,211 @@ mod tests {
 
     #[test]
     fn test_qrcode_check_data() {
+        let option = QRCodeOption::default();
         let data = "azerty123456789QTG,{";
-        assert!(QRCode::check_data(data).is_ok());
+        assert!(QRCode::check_data(data.as_bytes(), &option).is_ok());
 
         let data = "azerty123456789QTG,{".repeat(400);
-        assert!(QRCode::check_data(&data).is_err());
+        assert!(QRCode::check_data(data.as_bytes(), &option).is_err());
+
+        // Default option: Model 2, error correction level H
+        assert_eq!(option.model(), QRCodeModel::Model2);
+        assert!(QRCode::from_bytes(&[0xFF; 1273], None).is_ok());
+        assert!(QRCode::from_bytes(&[0xFF; 1274], None).is_err());
+
+        // Model 1 capacity is the one of version 14, level L
+        let model1 = Some(QRCodeOption::new(QRCodeModel::Model1, 4, QRCodeCorrectionLevel::L));
+        assert!(QRCode::new(&"1".repeat(1167), model1.clone()).is_ok());
+        assert!(QRCode::new(&"A".repeat(707), model1.clone()).is_ok());
+        assert!(QRCode::from_bytes(&[0xFF; 486], model1.clone()).is_ok());
+        assert!(QRCode::new(&"1".repeat(1168), model1.clone()).is_err());
+        assert!(QRCode::new(&"A".repeat(708), model1.clone()).is_err());
+        assert_eq!(
+            QRCode::from_bytes(&[0xFF; 487], model1).unwrap_err().to_string(),
+            "Input error: QR code Model 1 data is too long: 487 characters in byte mode, max 486"
+        );
+
+        // Model 2 capacity depends on the error correction level and the mode
+        let model2 = |level| Some(QRCodeOption::new(QRCodeModel::Model2, 4, level));
+        assert!(QRCode::from_bytes(&[0xFF; 2953], model2(QRCodeCorrectionLevel::L)).is_ok());
+        assert!(QRCode::from_bytes(&[0xFF; 2954], model2(QRCodeCorrectionLevel::L)).is_err());
+        assert!(QRCode::new(&"1".repeat(7089), model2(QRCodeCorrectionLevel::L)).is_ok());
+        assert!(QRCode::new(&"1".repeat(3058), model2(QRCodeCorrectionLevel::H)).is_err());
+        assert_eq!(
+            QRCode::from_bytes(&[0xFF; 1274], model2(QRCodeCorrectionLevel::H))
+                .unwrap_err()
+                .to_string(),
+            "Input error: QR code data is too long for error correction level H: 1274 characters in byte mode, max 1273"
+        );
+    }
+
+    #[test]
+    fn test_qrcode_capacity() {
+        use QRCodeCorrectionLevel::*;
+
+        assert_eq!(QRCodeMode::Numeric.capacity(1, L).unwrap(), 41);
+        assert_eq!(QRCodeMode::Alphanumeric.capacity(1, L).unwrap(), 25);
+        assert_eq!(QRCodeMode::Byte.capacity(1, L).unwrap(), 17);
+        assert_eq!(QRCodeMode::Numeric.capacity(1, H).unwrap(), 17);
+        assert_eq!(QRCodeMode::Alphanumeric.capacity(10, M).unwrap(), 311);
+        assert_eq!(QRCodeMode::Byte.capacity(27, Q).unwrap(), 805);
+        assert_eq!(QRCodeMode::Numeric.capacity(40, H).unwrap(), 3057);
+        assert_eq!(QRCodeMode::Alphanumeric.capacity(40, L).unwrap(), 4296);
+        assert!(QRCodeMode::Byte.capacity(0, L).is_err());
+        assert!(QRCodeMode::Byte.capacity(41, L).is_err());
+
+        assert_eq!(QRCodeMode::detect(b""), QRCodeMode::Numeric);
+        assert_eq!(QRCodeMode::detect(b"A B:"), QRCodeMode::Alphanumeric);
+        assert_eq!(QRCodeMode::detect(&[0xE9]), QRCodeMode::Byte);
+    }
+
+    #[test]
+    fn test_qrcode_version() {
+        let qrcode =
+            |data: &str, level| QRCode::new(data, Some(QRCodeOption::new(QRCodeModel::Model2, 3, level))).unwrap();
+
+        assert_eq!(qrcode(&"1".repeat(41), QRCodeCorrectionLevel::L).version().unwrap(), 1);
+        assert_eq!(qrcode(&"1".repeat(42), QRCodeCorrectionLevel::L).version().unwrap(), 2);
+        assert_eq!(qrcode(&"a".repeat(17), QRCodeCorrectionLevel::L).version().unwrap(), 1);
+        assert_eq!(qrcode(&"a".repeat(18), QRCodeCorrectionLevel::L).version().unwrap(), 2);
+        assert_eq!(
+            qrcode(&"a".repeat(2953), QRCodeCorrectionLevel::L).version().unwrap(),
+            40
+        );
+
+        let code = qrcode("HELLO WORLD", QRCodeCorrectionLevel::Q);
+        assert_eq!(code.version().unwrap(), 1);
+        assert_eq!(code.modules().unwrap(), 21);
+        assert_eq!(code.width().unwrap(), 63);
+
+        assert_eq!(QRCode::new("test", None).unwrap().version().unwrap(), 1);
+        let option = QRCodeOption::new(QRCodeModel::Model1, 3, QRCodeCorrectionLevel::L);
+        assert!(QRCode::new("test", Some(option)).unwrap().version().is_err());
+    }
+
+    #[test]
+    fn test_qrcode_fit_paper_width() {
+        let qrcode = |data: &str, model, size| {
+            let option = QRCodeOption::new(model, size, QRCodeCorrectionLevel::L);
+            QRCode::new(data, Some(option)).unwrap()
+        };
+        let data = "a".repeat(100);
+
+        // Version 5, 37 modules
+        assert_eq!(qrcode(&data, QRCodeModel::Model2, 8).width().unwrap(), 296);
+        assert!(qrcode(&data, QRCodeModel::Model2, 8).fit_paper_width(384).is_ok());
+        assert!(qrcode(&data, QRCodeModel::Model2, 8).fit_paper_width(256).is_err());
+
+        let mut code = qrcode(&data, QRCodeModel::Model2, 2);
+        code.option = code.option.auto_size();
+        assert_eq!(code.fit_paper_width(512).unwrap().option.size(), 13);
+
+        let mut code = qrcode("a", QRCodeModel::Model2, 2);
+        code.option = code.option.auto_size();
+        assert_eq!(code.fit_paper_width(576).unwrap().option.size(), 15);
+
+        // Model 1 width is not checked, automatic size is not available
+        assert!(qrcode("a", QRCodeModel::Model1, 15).fit_paper_width(100).is_ok());
+        let mut code = qrcode("a", QRCodeModel::Model1, 2);
+        code.option = code.option.auto_size();
+        assert!(code.fit_paper_width(512).is_err());
+    }
+
+    #[test]
+    fn test_micro_qrcode_capacity() {
+        use QRCodeCorrectionLevel::*;
+        use QRCodeMode::*;
+
+        // ISO/IEC 18004 table 7
+        let capacities = [
+            (1, L, [5, 0, 0]),
+            (2, L, [10, 6, 0]),
+            (2, M, [8, 5, 0]),
+            (3, L, [23, 14, 9]),
+            (3, M, [18, 11, 7]),
+            (4, L, [35, 21, 15]),
+            (4, M, [30, 18, 13]),
+            (4, Q, [21, 13, 9]),
+        ];
+        for (version, level, expected) in capacities {
+            for (mode, capacity) in [Numeric, Alphanumeric, Byte].into_iter().zip(expected) {
+                assert_eq!(
+                    mode.micro_capacity(version, level).unwrap(),
+                    capacity,
+                    "M{version} {level:?} {mode}"
+                );
+            }
+        }
+        assert!(Numeric.micro_capacity(1, M).is_err());
+        assert!(Numeric.micro_capacity(3, Q).is_err());
+        assert!(Numeric.micro_capacity(4, H).is_err());
+        assert!(Numeric.micro_capacity(5, L).is_err());
+    }
+
+    #[test]
+    fn test_micro_qrcode_version() {
+        let qrcode = |data: &str, level| QRCode::new(data, Some(QRCodeOption::new(QRCodeModel::Micro, 4, level)));
+
+        assert_eq!(qrcode("12345", QRCodeCorrectionLevel::L).unwrap().version().unwrap(), 1);
+        assert_eq!(qrcode("12345", QRCodeCorrectionLevel::M).unwrap().version().unwrap(), 2);
+        assert_eq!(
+            qrcode("123456", QRCodeCorrectionLevel::L).unwrap().version().unwrap(),
+            2
+        );
+        assert_eq!(qrcode("ABC", QRCodeCorrectionLevel::L).unwrap().version().unwrap(), 2);
+        assert_eq!(qrcode("abc", QRCodeCorrectionLevel::L).unwrap().version().unwrap(), 3);
+        assert_eq!(qrcode("abc", QRCodeCorrectionLevel::Q).unwrap().version().unwrap(), 4);
+
+        let code = qrcode(&"a".repeat(15), QRCodeCorrectionLevel::L).unwrap();
+        assert_eq!(code.version().unwrap(), 4);
+        assert_eq!(code.modules().unwrap(), 17);
+        assert_eq!(code.width().unwrap(), 68);
+
+        assert!(qrcode(&"a".repeat(16), QRCodeCorrectionLevel::L).is_err());
+        assert_eq!(
+            qrcode(&"a".repeat(10), QRCodeCorrectionLevel::Q).unwrap_err().to_string(),
+            "Input error: Micro QR code data is too long for error correction level Q: 10 characters in byte mode, max 9"
+        );
+        assert!(qrcode("1", QRCodeCorrectionLevel::H).is_err());
+    }
+
+    #[test]
+    fn test_qrcode_structured_append() {
+        let option = || QRCodeOption::new(QRCodeModel::Model2, 4, QRCodeCorrectionLevel::L);
+        let data = "a".repeat(100);
+
+        // Version 1, level L: 17 bytes, 15 with the header
+        let qrcodes = QRCode::structured_append(data.as_bytes(), 1, option()).unwrap();
+        assert_eq!(qrcodes.len(), 7);
+        assert_eq!(qrcodes[0].data.len(), 15);
+        assert_eq!(qrcodes[6].data.len(), 10);
+        assert_eq!(
+            qrcodes
+                .iter()
+                .flat_map(|qrcode| qrcode.data.clone())
+                .collect::<Vec<_>>(),
+            data.as_bytes()
+        );
+        for (index, qrcode) in qrcodes.iter().enumerate() {
+            assert_eq!(
+                qrcode.structured_append,
+                Some(QRCodeStructuredAppend {
+                    index: index as u8,
+                    total: 7,
+                    parity: 0,
+                })
+            );
+            assert_eq!(qrcode.version().unwrap(), 1);
+        }
+
+        let qrcodes = QRCode::structured_append(b"abc", 40, option()).unwrap();
+        assert_eq!(qrcodes.len(), 1);
+        assert_eq!(qrcodes[0].structured_append.unwrap().parity, b'a' ^ b'b' ^ b'c');
+
+        assert!(QRCode::structured_append(&[b'a'; 240], 1, option()).is_ok());
+        assert!(QRCode::structured_append(&[b'a'; 241], 1, option()).is_err());
+        assert!(QRCode::structured_append(b"abc", 41, option()).is_err());
+        let option = QRCodeOption::new(QRCodeModel::Micro, 4, QRCodeCorrectionLevel::L);
+        assert!(QRCode::structured_append(b"abc", 1, option).is_err());
     }
 }
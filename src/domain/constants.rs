@@ -3,6 +3,7 @@
 pub const _EOL: &str = "\n";
 pub const NUL: u8 = 0x00; // Null
 pub const EOT: u8 = 0x04; // End of transmission
+pub const ACK: u8 = 0x06; // Acknowledge
 pub const LF: u8 = 0x0A; // Line feed
 pub const _VT: u8 = 0x0B; // Vertical tab
 pub const _CR: u8 = 0x0D; // Carriage return
@@ -65,8 +66,21 @@ pub const GS_TEXT_SIZE_SELECT: &[u8] = &[GS, b'!'];
 pub const ESC_TEXT_UPSIDE_DOWN_OFF: &[u8] = &[ESC, b'{', 0];
 pub const ESC_TEXT_UPSIDE_DOWN_ON: &[u8] = &[ESC, b'{', 1];
 
+pub const ESC_TEXT_COLOR_BLACK: &[u8] = &[ESC, b'r', 0];
+pub const ESC_TEXT_COLOR_RED: &[u8] = &[ESC, b'r', 1];
+
+// User-defined characters
+pub const ESC_USER_DEFINED_CHARACTERS_DEFINE: &[u8] = &[ESC, b'&'];
+pub const ESC_USER_DEFINED_CHARACTERS_OFF: &[u8] = &[ESC, b'%', 0];
+pub const ESC_USER_DEFINED_CHARACTERS_ON: &[u8] = &[ESC, b'%', 1];
+pub const ESC_USER_DEFINED_CHARACTER_CANCEL: &[u8] = &[ESC, b'?'];
+
 // Printer Status
 pub const DLE_REAL_TIME_STATUS: &[u8] = &[DLE, EOT];
+pub const GS_AUTOMATIC_STATUS_BACK: &[u8] = &[GS, b'a'];
+pub const GS_PRINTER_ID: &[u8] = &[GS, b'I'];
+pub const GS_TRANSMIT_STATUS: &[u8] = &[GS, b'r'];
+pub const GS_PROCESS_ID_RESPONSE: &[u8] = &[GS, b'(', b'H', 6, 0, 48, 48];
 
 // Barcodes
 #[cfg(feature = "barcodes")]
@@ -100,6 +114,16 @@ pub const GS_2D_GS1_DATABAR_WIDTH_EXTENDED: &[u8] = &[GS, b'(', b'k', 3, 0, 51,
 #[cfg(feature = "codes_2d")]
 pub const GS_2D_GS1_DATABAR_PRINT: &[u8] = &[GS, b'(', b'k', 3, 0, 51, 81, 48];
 
+// Composite symbology
+#[cfg(feature = "codes_2d")]
+pub const GS_2D_COMPOSITE_MODULE_WIDTH: &[u8] = &[GS, b'(', b'k', 3, 0, 52, 60];
+#[cfg(feature = "codes_2d")]
+pub const GS_2D_COMPOSITE_EXPANDED_MAX_WIDTH: &[u8] = &[GS, b'(', b'k', 4, 0, 52, 61];
+#[cfg(feature = "codes_2d")]
+pub const GS_2D_COMPOSITE_HRI_FONT: &[u8] = &[GS, b'(', b'k', 3, 0, 52, 62];
+#[cfg(feature = "codes_2d")]
+pub const GS_2D_COMPOSITE_PRINT: &[u8] = &[GS, b'(', b'k', 3, 0, 52, 81, 48];
+
 // PDF417
 #[cfg(feature = "codes_2d")]
 pub const GS_2D_PDF417_COLUMNS: &[u8] = &[GS, b'(', b'k', 3, 0, 48, 65];
@@ -144,6 +168,8 @@ pub const GS_2D_AZTEC_CODE_PRINT: &[u8] = &[GS, b'(', b'k', 3, 0, 53, 81, 48];
 #[cfg(feature = "graphics")]
 pub const GS_IMAGE_BITMAP_PREFIX: &[u8] = &[GS, b'v', b'0'];
 #[cfg(feature = "graphics")]
+pub const ESC_IMAGE_COLUMN_PREFIX: &[u8] = &[ESC, b'*'];
+#[cfg(feature = "graphics")]
 pub const GS_IMAGE_LOW_PREFIX: &[u8] = &[GS, b'(', b'L'];
 #[cfg(feature = "graphics")]
 pub const GS_IMAGE_HIGH_PREFIX: &[u8] = &[GS, b'8', b'L'];
@@ -152,5 +178,29 @@ pub const GS_IMAGE_DENSITY: &[u8] = &[GS, b'(', b'L', 4, 0, 48, 49];
 #[cfg(feature = "graphics")]
 pub const GS_IMAGE_PRINT: &[u8] = &[GS, b'(', b'L', 2, 0, 48, 50];
 
+// NV and download graphics
+#[cfg(feature = "graphics")]
+pub const GS_GRAPHICS_NV_CAPACITY: &[u8] = &[GS, b'(', b'L', 2, 0, 48, 0];
+#[cfg(feature = "graphics")]
+pub const GS_GRAPHICS_NV_REMAINING_CAPACITY: &[u8] = &[GS, b'(', b'L', 2, 0, 48, 3];
+#[cfg(feature = "graphics")]
+pub const GS_GRAPHICS_DOWNLOAD_REMAINING_CAPACITY: &[u8] = &[GS, b'(', b'L', 2, 0, 48, 4];
+#[cfg(feature = "graphics")]
+pub const GS_GRAPHICS_NV_KEY_CODES: &[u8] = &[GS, b'(', b'L', 4, 0, 48, 64, b'K', b'C'];
+#[cfg(feature = "graphics")]
+pub const GS_GRAPHICS_DOWNLOAD_KEY_CODES: &[u8] = &[GS, b'(', b'L', 4, 0, 48, 80, b'K', b'C'];
+#[cfg(feature = "graphics")]
+pub const GS_GRAPHICS_NV_DELETE_ALL: &[u8] = &[GS, b'(', b'L', 5, 0, 48, 65, b'C', b'L', b'R'];
+#[cfg(feature = "graphics")]
+pub const GS_GRAPHICS_DOWNLOAD_DELETE_ALL: &[u8] = &[GS, b'(', b'L', 5, 0, 48, 81, b'C', b'L', b'R'];
+#[cfg(feature = "graphics")]
+pub const GS_GRAPHICS_NV_DELETE: &[u8] = &[GS, b'(', b'L', 4, 0, 48, 66];
+#[cfg(feature = "graphics")]
+pub const GS_GRAPHICS_DOWNLOAD_DELETE: &[u8] = &[GS, b'(', b'L', 4, 0, 48, 82];
+#[cfg(feature = "graphics")]
+pub const GS_GRAPHICS_NV_PRINT: &[u8] = &[GS, b'(', b'L', 6, 0, 48, 69];
+#[cfg(feature = "graphics")]
+pub const GS_GRAPHICS_DOWNLOAD_PRINT: &[u8] = &[GS, b'(', b'L', 6, 0, 48, 85];
+
 // Others
 pub const GS_SET_MOTION_UNITS: &[u8] = &[GS, b'P'];
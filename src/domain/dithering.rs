@@ -0,0 +1,326 @@
+//! Dithering
+//!
+//! Convert grayscale images to black and white before printing.
+
+#![cfg(feature = "graphics")]
+
+use crate::errors::{PrinterError, Result};
+use image::{GrayImage, Luma};
+use std::fmt;
+
+/// Bayer 4x4 threshold map
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Bayer 8x8 threshold map
+const BAYER_8X8: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+/// Floyd–Steinberg error diffusion (x offset, y offset, weight), divisor 16
+const FLOYD_STEINBERG: &[(i32, i32, i16)] = &[(1, 0, 7), (-1, 1, 3), (0, 1, 5), (1, 1, 1)];
+
+/// Atkinson error diffusion (x offset, y offset, weight), divisor 8 (only 3/4 of the error is spread)
+const ATKINSON: &[(i32, i32, i16)] = &[(1, 0, 1), (2, 0, 1), (-1, 1, 1), (0, 1, 1), (1, 1, 1), (0, 2, 1)];
+
+/// Dithering algorithm
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DitheringAlgorithm {
+    /// Pixels with a luminance lower than or equal to the level are black
+    Threshold(u8),
+    /// Floyd–Steinberg error diffusion
+    FloydSteinberg,
+    /// Atkinson error diffusion (lighter, keeps more contrast)
+    Atkinson,
+    /// Ordered dithering with a 4x4 Bayer matrix
+    Bayer4x4,
+    /// Ordered dithering with an 8x8 Bayer matrix
+    Bayer8x8,
+}
+
+impl Default for DitheringAlgorithm {
+    fn default() -> Self {
+        Self::Threshold(128)
+    }
+}
+
+impl fmt::Display for DitheringAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DitheringAlgorithm::Threshold(level) => write!(f, "Threshold ({level})"),
+            DitheringAlgorithm::FloydSteinberg => write!(f, "Floyd-Steinberg"),
+            DitheringAlgorithm::Atkinson => write!(f, "Atkinson"),
+            DitheringAlgorithm::Bayer4x4 => write!(f, "Bayer 4x4"),
+            DitheringAlgorithm::Bayer8x8 => write!(f, "Bayer 8x8"),
+        }
+    }
+}
+
+/// Dithering option
+///
+/// Gamma and contrast adjustments are applied before dithering.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dithering {
+    algorithm: DitheringAlgorithm,
+    gamma: f32,
+    contrast: f32,
+}
+
+impl Default for Dithering {
+    fn default() -> Self {
+        Self::new(DitheringAlgorithm::default())
+    }
+}
+
+impl Dithering {
+    /// Create a new `Dithering` without adjustment
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let dithering = Dithering::new(DitheringAlgorithm::FloydSteinberg)
+    ///     .gamma(1.8)?
+    ///     .contrast(0.2)?;
+    ///
+    /// assert_eq!(dithering.algorithm(), DitheringAlgorithm::FloydSteinberg);
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn new(algorithm: DitheringAlgorithm) -> Self {
+        Self {
+            algorithm,
+            gamma: 1.0,
+            contrast: 0.0,
+        }
+    }
+
+    /// Set gamma (greater than 0, values above 1.0 lighten the midtones)
+    pub fn gamma(mut self, gamma: f32) -> Result<Self> {
+        if !gamma.is_finite() || gamma <= 0.0 {
+            return Err(PrinterError::Input(format!("invalid dithering gamma (> 0): {gamma}")));
+        }
+        self.gamma = gamma;
+        Ok(self)
+    }
+
+    /// Set contrast (-1.0 to 1.0, 0.0 keeps the image unchanged)
+    pub fn contrast(mut self, contrast: f32) -> Result<Self> {
+        if !(-1.0..=1.0).contains(&contrast) {
+            return Err(PrinterError::Input(format!(
+                "invalid dithering contrast (-1.0 to 1.0): {contrast}"
+            )));
+        }
+        self.contrast = contrast;
+        Ok(self)
+    }
+
+    /// Get algorithm
+    pub fn algorithm(&self) -> DitheringAlgorithm {
+        self.algorithm
+    }
+
+    /// Get gamma
+    pub fn get_gamma(&self) -> f32 {
+        self.gamma
+    }
+
+    /// Get contrast
+    pub fn get_contrast(&self) -> f32 {
+        self.contrast
+    }
+
+    /// Adjust the luminance of a pixel with gamma and contrast
+    pub(crate) fn adjust(&self, luma: u8) -> u8 {
+        if self.gamma == 1.0 && self.contrast == 0.0 {
+            return luma;
+        }
+
+        let value = (f32::from(luma) / 255.0).powf(1.0 / self.gamma);
+        let value = (value - 0.5) * (1.0 + self.contrast) / (1.0 - self.contrast).max(f32::EPSILON) + 0.5;
+        (value.clamp(0.0, 1.0) * 255.0).round() as u8
+    }
+
+    /// Convert a grayscale image to black (0) and white (255)
+    pub fn apply(&self, image: &GrayImage) -> GrayImage {
+        let mut image = image.clone();
+        image
+            .pixels_mut()
+            .for_each(|pixel| pixel.0[0] = self.adjust(pixel.0[0]));
+
+        self.dither(&image)
+    }
+
+    /// Convert an already adjusted grayscale image to black (0) and white (255)
+    pub(crate) fn dither(&self, image: &GrayImage) -> GrayImage {
+        match self.algorithm {
+            DitheringAlgorithm::Threshold(level) => threshold(image, |_, _| level),
+            DitheringAlgorithm::Bayer4x4 => threshold(image, |x, y| bayer_level(&BAYER_4X4, x, y)),
+            DitheringAlgorithm::Bayer8x8 => threshold(image, |x, y| bayer_level(&BAYER_8X8, x, y)),
+            DitheringAlgorithm::FloydSteinberg => error_diffusion(image, FLOYD_STEINBERG, 16),
+            DitheringAlgorithm::Atkinson => error_diffusion(image, ATKINSON, 8),
+        }
+    }
+}
+
+/// Threshold level of an ordered dithering matrix cell
+fn bayer_level<const N: usize>(matrix: &[[u8; N]; N], x: u32, y: u32) -> u8 {
+    let cell = u32::from(matrix[y as usize % N][x as usize % N]);
+    let cells = (N * N) as u32;
+    // Center each cell in the 0 - 255 range
+    ((cell * 255 + 127) / cells) as u8
+}
+
+/// Pixels lower than or equal to the level are black
+fn threshold(image: &GrayImage, level: impl Fn(u32, u32) -> u8) -> GrayImage {
+    GrayImage::from_fn(image.width(), image.height(), |x, y| {
+        Luma([if image.get_pixel(x, y).0[0] <= level(x, y) {
+            0
+        } else {
+            255
+        }])
+    })
+}
+
+/// Spread the quantization error of each pixel to its neighbours
+fn error_diffusion(image: &GrayImage, weights: &[(i32, i32, i16)], divisor: i16) -> GrayImage {
+    let (width, height) = (image.width() as i32, image.height() as i32);
+    let mut values = image.pixels().map(|pixel| i16::from(pixel.0[0])).collect::<Vec<_>>();
+
+    for y in 0..height {
+        for x in 0..width {
+            let i = (y * width + x) as usize;
+            let old = values[i];
+            let new = if old < 128 { 0 } else { 255 };
+            let error = old - new;
+            values[i] = new;
+
+            for &(dx, dy, weight) in weights {
+                let (nx, ny) = (x + dx, y + dy);
+                if (0..width).contains(&nx) && ny < height {
+                    values[(ny * width + nx) as usize] += error * weight / divisor;
+                }
+            }
+        }
+    }
+
+    GrayImage::from_fn(image.width(), image.height(), |x, y| {
+        Luma([values[(y * image.width() + x) as usize].clamp(0, 255) as u8])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::imageops;
+
+    /// Golden images directory
+    const GOLDEN_DIRECTORY: &str = "./resources/images/dithering";
+
+    /// Compare the dithered image with the golden image
+    ///
+    /// Set `ESCPOS_UPDATE_GOLDEN` to write the golden images again.
+    fn assert_golden(source: &str, dithering: Dithering, name: &str) {
+        let image = image::open(format!("./resources/images/{source}"))
+            .unwrap()
+            .resize(128, 128, imageops::Triangle)
+            .to_luma8();
+        let dithered = dithering.apply(&image);
+        let path = format!("{GOLDEN_DIRECTORY}/{name}.png");
+
+        if std::env::var("ESCPOS_UPDATE_GOLDEN").is_ok() {
+            dithered.save(&path).unwrap();
+        }
+
+        let golden = image::open(&path).unwrap().to_luma8();
+        assert_eq!(dithered, golden, "{name} differs from the golden image");
+    }
+
+    #[test]
+    fn test_dithering_golden_images() {
+        let source = "rustacean-flat-happy.png";
+        assert_golden(source, Dithering::default(), "threshold");
+        assert_golden(
+            source,
+            Dithering::new(DitheringAlgorithm::Threshold(200)),
+            "threshold-200",
+        );
+        assert_golden(
+            source,
+            Dithering::new(DitheringAlgorithm::FloydSteinberg),
+            "floyd-steinberg",
+        );
+        assert_golden(source, Dithering::new(DitheringAlgorithm::Atkinson), "atkinson");
+        assert_golden(source, Dithering::new(DitheringAlgorithm::Bayer4x4), "bayer-4x4");
+        assert_golden(source, Dithering::new(DitheringAlgorithm::Bayer8x8), "bayer-8x8");
+        assert_golden(
+            source,
+            Dithering::new(DitheringAlgorithm::FloydSteinberg)
+                .gamma(1.8)
+                .unwrap()
+                .contrast(0.3)
+                .unwrap(),
+            "floyd-steinberg-adjusted",
+        );
+        assert_golden(
+            "rust-logo-small.png",
+            Dithering::new(DitheringAlgorithm::Atkinson),
+            "rust-logo-atkinson",
+        );
+    }
+
+    #[test]
+    fn test_dithering_black_and_white_only() {
+        let gradient = GrayImage::from_fn(64, 16, |x, _| Luma([(x * 4) as u8]));
+        for algorithm in [
+            DitheringAlgorithm::Threshold(100),
+            DitheringAlgorithm::FloydSteinberg,
+            DitheringAlgorithm::Atkinson,
+            DitheringAlgorithm::Bayer4x4,
+            DitheringAlgorithm::Bayer8x8,
+        ] {
+            let dithered = Dithering::new(algorithm).apply(&gradient);
+            assert!(dithered.pixels().all(|p| p.0[0] == 0 || p.0[0] == 255), "{algorithm}");
+            // Darker on the left side
+            let black = |range: std::ops::Range<u32>| {
+                range
+                    .flat_map(|x| (0..16).map(move |y| (x, y)))
+                    .filter(|&(x, y)| dithered.get_pixel(x, y).0[0] == 0)
+                    .count()
+            };
+            assert!(black(0..32) > black(32..64), "{algorithm}");
+        }
+    }
+
+    #[test]
+    fn test_dithering_threshold() {
+        let image = GrayImage::from_fn(3, 1, |x, _| Luma([[127, 128, 129][x as usize]]));
+        let dithered = Dithering::default().apply(&image);
+        assert_eq!(dithered.into_raw(), vec![0, 0, 255]);
+    }
+
+    #[test]
+    fn test_dithering_adjustments() {
+        assert!(Dithering::default().gamma(0.0).is_err());
+        assert!(Dithering::default().gamma(f32::NAN).is_err());
+        assert!(Dithering::default().contrast(1.5).is_err());
+        assert!(Dithering::default().contrast(-1.5).is_err());
+
+        let dithering = Dithering::default();
+        assert_eq!(dithering.adjust(100), 100);
+
+        let lighter = Dithering::default().gamma(2.0).unwrap();
+        assert!(lighter.adjust(100) > 100);
+        assert_eq!(lighter.adjust(0), 0);
+        assert_eq!(lighter.adjust(255), 255);
+
+        let contrasted = Dithering::default().contrast(0.5).unwrap();
+        assert!(contrasted.adjust(100) < 100);
+        assert!(contrasted.adjust(160) > 160);
+        assert_eq!(Dithering::default().contrast(1.0).unwrap().adjust(200), 255);
+    }
+}
@@ -2,10 +2,21 @@
 
 #![cfg(feature = "graphics")]
 
+use super::bit_image::{downscale, ResizeFilter};
+use super::dithering::{Dithering, DitheringAlgorithm};
 use crate::errors::{PrinterError, Result};
-use image::{DynamicImage, GenericImageView, Rgba};
+use image::{DynamicImage, GenericImageView, GrayImage, Luma, Pixel, Rgba};
 use std::fmt;
 
+/// Maximum graphic width in dots
+const GRAPHIC_MAX_WIDTH: u32 = 2400;
+/// Maximum monochrome graphic height in dots
+const GRAPHIC_MAX_HEIGHT_MONOCHROME: u32 = 2400;
+/// Maximum multiple tone graphic height in dots
+const GRAPHIC_MAX_HEIGHT_MULTIPLE_TONE: u32 = 600;
+/// Number of parameters before the data (`m`, `fn`, `a`, `bx`, `by`, `c`, `xL`, `xH`, `yL`, `yH`)
+pub(crate) const GRAPHIC_PARAMETERS_LENGTH: usize = 10;
+
 /// Graphic density
 #[derive(Debug, Clone, Copy)]
 pub enum GraphicDensity {
@@ -36,7 +47,9 @@ impl fmt::Display for GraphicDensity {
 /// Graphic tone
 #[derive(Debug, Clone, Copy)]
 pub enum GraphicTone {
+    /// Monochrome (digital)
     Monochrome,
+    /// Multiple tone (16 levels sent as 4 bit planes, most significant bit in color 1)
     Multiple,
 }
 
@@ -114,7 +127,7 @@ impl fmt::Display for GraphicSize {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 // TODO: Make fields private
 pub struct GraphicOption {
     /// Image max width
@@ -131,6 +144,19 @@ pub struct GraphicOption {
     pub width_size: GraphicSize,
     /// Height size
     pub height_size: GraphicSize,
+    /// Color used for the red pixels on two-color paper (monochrome tone only)
+    pub second_color: Option<GraphicColor>,
+    /// Dithering (monochrome tone, threshold 127 by default), multiple tone graphics only use its gamma and contrast
+    pub dithering: Dithering,
+    /// Filter used to downscale the image
+    pub resize_filter: ResizeFilter,
+    /// Limit the width to the printer paper width
+    pub fit_to_paper_width: bool,
+}
+
+/// Default graphic dithering: pixels with a darkness of at least 128 are black
+fn default_dithering() -> Dithering {
+    Dithering::new(DitheringAlgorithm::Threshold(127))
 }
 
 impl Default for GraphicOption {
@@ -143,6 +169,10 @@ impl Default for GraphicOption {
             color: GraphicColor::Color1,
             width_size: GraphicSize::Normal,
             height_size: GraphicSize::Normal,
+            second_color: None,
+            dithering: default_dithering(),
+            resize_filter: ResizeFilter::default(),
+            fit_to_paper_width: false,
         }
     }
 }
@@ -166,7 +196,54 @@ impl GraphicOption {
             color,
             width_size,
             height_size,
+            second_color: None,
+            dithering: default_dithering(),
+            resize_filter: ResizeFilter::default(),
+            fit_to_paper_width: false,
+        }
+    }
+
+    /// Print the red pixels of the image with a second color (two-color paper)
+    ///
+    /// The other dark pixels are printed with `color`.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = GraphicOption::default().second_color(GraphicColor::Color2);
+    ///
+    /// assert!(option.second_color.is_some());
+    /// ```
+    pub fn second_color(mut self, color: GraphicColor) -> Self {
+        self.second_color = Some(color);
+        self
+    }
+
+    /// Set the [dithering](Dithering)
+    pub fn dithering(mut self, dithering: Dithering) -> Self {
+        self.dithering = dithering;
+        self
+    }
+
+    /// Set the [resize filter](ResizeFilter)
+    pub fn resize_filter(mut self, filter: ResizeFilter) -> Self {
+        self.resize_filter = filter;
+        self
+    }
+
+    /// Downscale the image to the paper width of the printer
+    pub fn fit_to_paper_width(mut self) -> Self {
+        self.fit_to_paper_width = true;
+        self
+    }
+
+    /// Limit the max width to the paper width if needed
+    pub(crate) fn paper_width(mut self, dots: u16) -> Self {
+        if self.fit_to_paper_width {
+            let dots = u32::from(dots);
+            self.max_width = Some(self.max_width.map_or(dots, |max_width| max_width.min(dots)));
         }
+        self
     }
 }
 
@@ -177,6 +254,10 @@ pub struct Graphic {
     /// Image option
     option: GraphicOption,
     image: DynamicImage,
+    /// Adjusted luminance (transparent pixels are white)
+    luma: GrayImage,
+    /// Dithered luminance (black or white)
+    dithered: GrayImage,
 }
 
 impl Graphic {
@@ -185,27 +266,35 @@ impl Graphic {
         let img = image::open(path)?;
         let option = option.unwrap_or_default();
 
-        // Resize image with max width and max height constraints and convert to grayscale
-        let img = match (option.max_width, option.max_height) {
-            (Some(max_width), None) => {
-                let resized = img.resize(max_width, max_width, image::imageops::Nearest);
-                resized.grayscale()
-            }
-            (None, Some(max_height)) => {
-                let resized = img.resize(max_height, max_height, image::imageops::Nearest);
-                resized.grayscale()
-            }
-            (Some(max_width), Some(max_height)) => {
-                let resized = img.resize(max_width, max_height, image::imageops::Nearest);
-                resized.grayscale()
-            }
-            _ => img.grayscale(),
+        // Downscale image with max width and max height constraints
+        // (colors are kept for two-color printing)
+        let img = downscale(img, option.max_width, option.max_height, option.resize_filter);
+
+        let (width, height) = img.dimensions();
+        let max_height = match option.tone {
+            GraphicTone::Monochrome => GRAPHIC_MAX_HEIGHT_MONOCHROME,
+            GraphicTone::Multiple => GRAPHIC_MAX_HEIGHT_MULTIPLE_TONE,
         };
+        if !(1..=GRAPHIC_MAX_WIDTH).contains(&width) || !(1..=max_height).contains(&height) {
+            return Err(PrinterError::Input(format!(
+                "invalid graphic size (1-{GRAPHIC_MAX_WIDTH} x 1-{max_height} dots): {width} x {height}"
+            )));
+        }
+
+        // Gamma and contrast are applied once, multiple tone graphics use the adjusted luminance
+        let luma = GrayImage::from_fn(width, height, |x, y| {
+            let pixel = img.get_pixel(x, y);
+            let darkness = (255 - u32::from(pixel.to_luma().0[0])) * u32::from(pixel[3]) / 255;
+            Luma([option.dithering.adjust(255 - darkness as u8)])
+        });
+        let dithered = option.dithering.dither(&luma);
 
         Ok(Self {
             path: path.to_string(),
             option,
             image: img,
+            luma,
+            dithered,
         })
     }
 
@@ -226,7 +315,7 @@ impl Graphic {
 
     /// Get image width in bytes
     pub fn width_bytes(&self) -> u32 {
-        (self.width() + 7) / 8
+        self.width().div_ceil(8)
     }
 
     /// Get path
@@ -276,9 +365,25 @@ impl Graphic {
         self.option.height_size.into()
     }
 
-    /// Get (p1, p2, p3, p4)
+    /// Get pixel darkness (0 for white or transparent, 255 for black)
+    fn darkness(&self, x: u32, y: u32) -> u8 {
+        255 - self.luma.get_pixel(x, y).0[0]
+    }
+
+    /// Is the pixel black after dithering?
+    fn is_dithered_black(&self, x: u32, y: u32) -> bool {
+        self.dithered.get_pixel(x, y).0[0] == 0
+    }
+
+    /// Is the pixel red? (printed with the second color on two-color paper)
+    fn is_red_pixel(&self, x: u32, y: u32) -> bool {
+        let [r, g, b, a] = self.pixel(x, y).0;
+        a > 127 && r > 127 && r / 2 > g && r / 2 > b
+    }
+
+    /// Get (p1, p2, p3, p4) of one color plane (`m`, `fn`, `a`, `bx`, `by`, `c`, `xL`, `xH`, `yL`, `yH` and data)
     pub fn data_size(&self) -> Result<(u8, u8, u8, u8)> {
-        let length = self.image.as_bytes().len() - 11;
+        let length = GRAPHIC_PARAMETERS_LENGTH + usize::try_from(self.width_bytes() * self.height())?;
         let p4 = length / 16_777_216;
         let p3 = length
             .checked_add_signed(-16_777_216 * isize::try_from(p4)?)
@@ -317,8 +422,13 @@ impl Graphic {
         Ok((u8::try_from(pl)?, u8::try_from(ph)?))
     }
 
-    /// Data in raster mode
+    /// Data in raster mode (black pixels after dithering)
     pub fn data(&self) -> Result<Vec<u8>> {
+        Ok(self.raster(|x, y| self.is_dithered_black(x, y)))
+    }
+
+    /// Build raster data from a pixel predicate
+    fn raster(&self, is_printed: impl Fn(u32, u32) -> bool) -> Vec<u8> {
         let width = self.width_bytes();
         let height = self.height();
 
@@ -327,14 +437,45 @@ impl Graphic {
             for x in 0..width {
                 for b in 0..8 {
                     let i = x * 8 + b;
-                    if i < self.width() && !self.is_blank_pixel(i, y) {
-                        data[(y * width + x) as usize] += 0x80 >> (b & 0x7);
+                    if i < self.width() && is_printed(i, y) {
+                        data[(y * width + x) as usize] |= 0x80 >> b;
                     }
                 }
             }
         }
 
-        Ok(data)
+        data
+    }
+
+    /// Color planes in raster mode
+    ///
+    /// - Monochrome: one plane with the dithered pixels, or two planes when a second color is set
+    ///   (red pixels in the second color, other dithered pixels in the first color)
+    /// - Multiple tone: four bit planes of a 16-level darkness, most significant bit in color 1
+    pub fn planes(&self) -> Result<Vec<(GraphicColor, Vec<u8>)>> {
+        Ok(match (self.option.tone, self.option.second_color) {
+            (GraphicTone::Monochrome, None) => vec![(self.option.color, self.data()?)],
+            (GraphicTone::Monochrome, Some(second_color)) => vec![
+                (
+                    self.option.color,
+                    self.raster(|x, y| !self.is_red_pixel(x, y) && self.is_dithered_black(x, y)),
+                ),
+                (second_color, self.raster(|x, y| self.is_red_pixel(x, y))),
+            ],
+            (GraphicTone::Multiple, _) => [
+                GraphicColor::Color1,
+                GraphicColor::Color2,
+                GraphicColor::Color3,
+                GraphicColor::Color4,
+            ]
+            .into_iter()
+            .enumerate()
+            .map(|(plane, color)| {
+                let bit = 0x08 >> plane;
+                (color, self.raster(|x, y| (self.darkness(x, y) >> 4) & bit != 0))
+            })
+            .collect(),
+        })
     }
 }
 
@@ -353,4 +494,106 @@ mod tests {
         let graphic = Graphic::new("./resources/images/rust-logo.png", None).unwrap();
         assert_eq!(graphic.height(), 1_000);
     }
+
+    #[test]
+    fn test_graphic_downscale() {
+        let option = GraphicOption {
+            max_width: Some(100),
+            resize_filter: ResizeFilter::Lanczos3,
+            ..Default::default()
+        };
+        let graphic = Graphic::new("./resources/images/rust-logo-small.png", Some(option)).unwrap();
+        assert_eq!(graphic.dimensions(), (100, 100));
+
+        // Never upscaled
+        let option = GraphicOption {
+            max_width: Some(400),
+            max_height: Some(400),
+            ..Default::default()
+        };
+        let graphic = Graphic::new("./resources/images/rust-logo-small.png", Some(option)).unwrap();
+        assert_eq!(graphic.dimensions(), (200, 200));
+
+        let option = GraphicOption::default().fit_to_paper_width().paper_width(150);
+        assert_eq!(option.max_width, Some(150));
+    }
+
+    #[test]
+    fn test_graphic_too_high_for_multiple_tone() {
+        let option = GraphicOption {
+            tone: GraphicTone::Multiple,
+            ..Default::default()
+        };
+        assert!(Graphic::new("./resources/images/rust-logo.png", Some(option)).is_err());
+    }
+
+    #[test]
+    fn test_graphic_data_size() {
+        // 200 x 200 dots: 25 x 200 bytes + 10 parameters = 5_010 bytes
+        let graphic = Graphic::new("./resources/images/rust-logo-small.png", None).unwrap();
+        assert_eq!(graphic.data_size().unwrap(), (0x92, 0x13, 0, 0));
+
+        // 1_000 x 1_000 dots: 125 x 1_000 bytes + 10 parameters = 125_010 bytes
+        let graphic = Graphic::new("./resources/images/rust-logo.png", None).unwrap();
+        assert_eq!(graphic.data_size().unwrap(), (0x52, 0xE8, 0x01, 0));
+    }
+
+    #[test]
+    fn test_graphic_planes() {
+        let graphic = Graphic::new("./resources/images/rust-logo-small.png", None).unwrap();
+        let planes = graphic.planes().unwrap();
+        assert_eq!(planes.len(), 1);
+        assert_eq!(u8::from(planes[0].0), 49);
+        assert_eq!(planes[0].1, graphic.data().unwrap());
+
+        let option = GraphicOption::default().second_color(GraphicColor::Color2);
+        let graphic = Graphic::new("./resources/images/rust-logo-small.png", Some(option)).unwrap();
+        let planes = graphic.planes().unwrap();
+        assert_eq!(planes.len(), 2);
+        assert_eq!(u8::from(planes[1].0), 50);
+        // A pixel is never printed with both colors
+        assert!(planes[0].1.iter().zip(&planes[1].1).all(|(c1, c2)| c1 & c2 == 0));
+
+        let option = GraphicOption {
+            tone: GraphicTone::Multiple,
+            ..Default::default()
+        };
+        let graphic = Graphic::new("./resources/images/rust-logo-small.png", Some(option)).unwrap();
+        let planes = graphic.planes().unwrap();
+        assert_eq!(planes.len(), 4);
+        assert!(planes.iter().all(|(_, data)| data.len() == 25 * 200));
+        // The most significant bit plane matches the monochrome data
+        assert_eq!(planes[0].1, graphic.data().unwrap());
+        assert_eq!(
+            planes.iter().map(|(color, _)| u8::from(*color)).collect::<Vec<_>>(),
+            vec![49, 50, 51, 52]
+        );
+    }
+
+    #[test]
+    fn test_graphic_gamma_applied_once() {
+        // 50 is adjusted to 113 with a gamma of 2.0 (170 if adjusted twice)
+        let path = std::env::temp_dir().join("escpos-graphic-gamma.png");
+        GrayImage::from_pixel(8, 1, Luma([50])).save(&path).unwrap();
+        let dithering = Dithering::new(DitheringAlgorithm::Threshold(127)).gamma(2.0).unwrap();
+        let option = GraphicOption::default().dithering(dithering);
+        let graphic = Graphic::new(path.to_str().unwrap(), Some(option)).unwrap();
+
+        assert_eq!(dithering.adjust(50), 113);
+        assert!(graphic.luma.pixels().all(|pixel| pixel.0[0] == 113));
+        assert!(graphic.dithered.pixels().all(|pixel| pixel.0[0] == 0));
+        assert_eq!(graphic.data().unwrap(), vec![0xFF]);
+
+        // Multiple tone: darkness 142 (0b1000_1110)
+        let option = GraphicOption {
+            tone: GraphicTone::Multiple,
+            ..GraphicOption::default().dithering(dithering)
+        };
+        let graphic = Graphic::new(path.to_str().unwrap(), Some(option)).unwrap();
+        let planes = graphic.planes().unwrap();
+        assert_eq!(
+            planes.iter().map(|(_, data)| data[0]).collect::<Vec<_>>(),
+            vec![0xFF, 0, 0, 0]
+        );
+    }
 }
@@ -1,21 +1,39 @@
+mod asb;
 mod bit_image;
 mod character;
+mod code_image;
 mod codes;
 pub(crate) mod common;
 mod constants;
+mod dithering;
 mod graphics;
 mod page_codes;
+mod printer_id;
 mod protocol;
 mod status;
+mod stored_graphics;
+mod text_image;
 mod types;
+mod user_defined;
 
+pub use asb::*;
 #[cfg(feature = "graphics")]
 pub use bit_image::*;
 pub use character::*;
+#[cfg(feature = "codes_2d_image")]
+pub use code_image::*;
 pub use codes::*;
 pub use constants::*;
 #[cfg(feature = "graphics")]
+pub use dithering::*;
+#[cfg(feature = "graphics")]
 pub use graphics::*;
+pub use printer_id::*;
 pub use protocol::*;
 pub use status::*;
+#[cfg(feature = "graphics")]
+pub use stored_graphics::*;
+#[cfg(feature = "ttf")]
+pub use text_image::*;
 pub use types::*;
+pub use user_defined::*;
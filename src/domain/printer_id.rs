@@ -0,0 +1,359 @@
+//! Printer identification (`GS I`), transmit status (`GS r`) and process ID response (`GS ( H`)
+//!
+//! [Epson Documentation](https://download4.epson.biz/sec_pubs/pos/reference_en/escpos/gs_ci.html)
+
+use super::asb::PrinterResponse;
+use super::status::PaperSensorStatus;
+use crate::errors::PrinterError;
+use std::fmt;
+
+/// Header of the `GS I` text responses
+const PRINTER_ID_HEADER: u8 = 0x5F;
+/// Header of the `GS ( H` process ID response
+const PROCESS_ID_HEADER: [u8; 2] = [0x37, 0x22];
+
+/// Printer ID request (`GS I`)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PrinterIdRequest {
+    ModelId,
+    TypeId,
+    VersionId,
+    Firmware,
+    Manufacturer,
+    ModelName,
+    SerialNumber,
+}
+
+impl From<PrinterIdRequest> for u8 {
+    fn from(value: PrinterIdRequest) -> Self {
+        match value {
+            PrinterIdRequest::ModelId => 1,
+            PrinterIdRequest::TypeId => 2,
+            PrinterIdRequest::VersionId => 3,
+            PrinterIdRequest::Firmware => 65,
+            PrinterIdRequest::Manufacturer => 66,
+            PrinterIdRequest::ModelName => 67,
+            PrinterIdRequest::SerialNumber => 68,
+        }
+    }
+}
+
+/// Printer type ID (`GS I 2`)
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PrinterTypeId {
+    pub multi_byte_characters: bool,
+    pub autocutter: bool,
+}
+
+impl PrinterTypeId {
+    /// Parse the response (0xx0xxxxb)
+    pub fn parse(response: u8) -> Result<Self, PrinterError> {
+        check_one_byte_response(response, "printer type ID")?;
+        Ok(Self {
+            multi_byte_characters: response & 0b0000_0001 != 0,
+            autocutter: response & 0b0000_0010 != 0,
+        })
+    }
+}
+
+/// Printer ID (`GS I`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrinterId {
+    ModelId(u8),
+    TypeId(PrinterTypeId),
+    VersionId(u8),
+    Firmware(String),
+    Manufacturer(String),
+    ModelName(String),
+    SerialNumber(String),
+}
+
+impl PrinterId {
+    /// Parse the response of a request
+    ///
+    /// Model, type and version IDs are one byte, other information is text between the `_` header and NUL.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let response = PrinterResponse::Block(b"_TM-T88V\0".to_vec());
+    /// let id = PrinterId::parse(PrinterIdRequest::ModelName, &response)?;
+    ///
+    /// assert_eq!(id, PrinterId::ModelName("TM-T88V".to_string()));
+    /// assert!(PrinterId::parse(PrinterIdRequest::ModelId, &response).is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn parse(request: PrinterIdRequest, response: &PrinterResponse) -> Result<Self, PrinterError> {
+        match (request, response) {
+            (PrinterIdRequest::ModelId, PrinterResponse::Byte(id)) => {
+                Ok(Self::ModelId(check_one_byte_response(*id, "printer model ID")?))
+            }
+            (PrinterIdRequest::TypeId, PrinterResponse::Byte(id)) => Ok(Self::TypeId(PrinterTypeId::parse(*id)?)),
+            (PrinterIdRequest::VersionId, PrinterResponse::Byte(id)) => {
+                Ok(Self::VersionId(check_one_byte_response(*id, "printer version ID")?))
+            }
+            (PrinterIdRequest::Firmware, PrinterResponse::Block(data)) => Ok(Self::Firmware(parse_text(data)?)),
+            (PrinterIdRequest::Manufacturer, PrinterResponse::Block(data)) => Ok(Self::Manufacturer(parse_text(data)?)),
+            (PrinterIdRequest::ModelName, PrinterResponse::Block(data)) => Ok(Self::ModelName(parse_text(data)?)),
+            (PrinterIdRequest::SerialNumber, PrinterResponse::Block(data)) => Ok(Self::SerialNumber(parse_text(data)?)),
+            (request, response) => Err(PrinterError::InvalidResponse(format!(
+                "unexpected response to printer ID request {request:?}: {response:?}"
+            ))),
+        }
+    }
+
+    /// Get the text of firmware, manufacturer, model name and serial number
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            Self::Firmware(text) | Self::Manufacturer(text) | Self::ModelName(text) | Self::SerialNumber(text) => {
+                Some(text)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Printer identity, used to select the printer options matching a printer model
+///
+/// Information the printer did not send is `None`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PrinterIdentity {
+    pub manufacturer: Option<String>,
+    pub model_name: Option<String>,
+    pub serial_number: Option<String>,
+    pub firmware: Option<String>,
+    pub type_id: Option<PrinterTypeId>,
+}
+
+impl fmt::Display for PrinterIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = [&self.manufacturer, &self.model_name]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+        write!(f, "{}", if name.is_empty() { "unknown printer" } else { &name })?;
+
+        let details = [("serial number", &self.serial_number), ("firmware", &self.firmware)]
+            .into_iter()
+            .filter_map(|(label, value)| value.as_ref().map(|value| format!("{label}: {value}")))
+            .collect::<Vec<_>>();
+        if !details.is_empty() {
+            write!(f, " ({})", details.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Transmit status request (`GS r`)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TransmitStatusRequest {
+    PaperSensor,
+    Drawer,
+    Ink,
+}
+
+impl From<TransmitStatusRequest> for u8 {
+    fn from(value: TransmitStatusRequest) -> Self {
+        match value {
+            TransmitStatusRequest::PaperSensor => 1,
+            TransmitStatusRequest::Drawer => 2,
+            TransmitStatusRequest::Ink => 4,
+        }
+    }
+}
+
+/// Transmit status (`GS r`)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TransmitStatus {
+    PaperSensor(PaperSensorStatus),
+    Drawer { kick_out_connector_pin3_low: bool },
+    Ink { ink_a_near_end: bool, ink_b_near_end: bool },
+}
+
+impl TransmitStatus {
+    /// Parse the response of a request
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let status = TransmitStatus::parse(TransmitStatusRequest::PaperSensor, 0b0000_0011)?;
+    ///
+    /// assert_eq!(
+    ///     status,
+    ///     TransmitStatus::PaperSensor(PaperSensorStatus { paper_adequate: false, paper_present: true })
+    /// );
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn parse(request: TransmitStatusRequest, response: u8) -> Result<Self, PrinterError> {
+        check_one_byte_response(response, "transmit status")?;
+        let bit = |bit: u8| response & (1 << bit) != 0;
+
+        Ok(match request {
+            TransmitStatusRequest::PaperSensor => Self::PaperSensor(PaperSensorStatus {
+                paper_adequate: !bit(0) && !bit(1),
+                paper_present: !bit(2) && !bit(3),
+            }),
+            TransmitStatusRequest::Drawer => Self::Drawer {
+                kick_out_connector_pin3_low: !bit(0),
+            },
+            TransmitStatusRequest::Ink => Self::Ink {
+                ink_a_near_end: bit(0),
+                ink_b_near_end: bit(1),
+            },
+        })
+    }
+}
+
+/// Process ID (`GS ( H`), 4 digits sent back by the printer once the previous data is processed
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ProcessId([u8; 4]);
+
+impl ProcessId {
+    /// Create a new `ProcessId` (0 - 9999)
+    pub fn new(id: u16) -> Result<Self, PrinterError> {
+        if id > 9999 {
+            return Err(PrinterError::Input(format!("invalid process ID (0-9999): {id}")));
+        }
+        let digits = format!("{id:04}").into_bytes();
+        Ok(Self([digits[0], digits[1], digits[2], digits[3]]))
+    }
+
+    /// Get the digits
+    pub fn digits(&self) -> [u8; 4] {
+        self.0
+    }
+
+    /// Parse the process ID response (`37h 22h d1 d2 d3 d4 00h`)
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let response = PrinterResponse::Block(vec![0x37, 0x22, b'0', b'0', b'4', b'2', 0]);
+    ///
+    /// assert_eq!(ProcessId::parse(&response)?, ProcessId::new(42)?);
+    /// assert!(ProcessId::parse(&PrinterResponse::Block(b"_TM\0".to_vec())).is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn parse(response: &PrinterResponse) -> Result<Self, PrinterError> {
+        match response {
+            PrinterResponse::Block(data)
+                if data.len() == 7
+                    && data.starts_with(&PROCESS_ID_HEADER)
+                    && data[2..6].iter().all(u8::is_ascii_digit)
+                    && data[6] == 0 =>
+            {
+                Ok(Self([data[2], data[3], data[4], data[5]]))
+            }
+            response => Err(PrinterError::InvalidResponse(format!(
+                "invalid process ID response: {response:?}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ProcessId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(&self.0))
+    }
+}
+
+/// Check the fixed bits of a one-byte response (0xx0xxxxb)
+fn check_one_byte_response(response: u8, name: &str) -> Result<u8, PrinterError> {
+    if response & 0b1001_0000 != 0 {
+        return Err(PrinterError::InvalidResponse(format!(
+            "invalid {name} response pattern: {response:08b} (0xx0xxxx expected)"
+        )));
+    }
+    Ok(response)
+}
+
+/// Get the text of a `GS I` response between the `_` header and NUL
+fn parse_text(data: &[u8]) -> Result<String, PrinterError> {
+    match data {
+        [PRINTER_ID_HEADER, text @ .., 0] if text.iter().all(|&c| c != 0) => {
+            Ok(String::from_utf8_lossy(text).trim().to_string())
+        }
+        _ => Err(PrinterError::InvalidResponse(format!(
+            "invalid printer ID response: {data:02X?}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_printer_id_parse() {
+        let byte = |id| PrinterResponse::Byte(id);
+        assert_eq!(
+            PrinterId::parse(PrinterIdRequest::ModelId, &byte(0x20)).unwrap(),
+            PrinterId::ModelId(0x20)
+        );
+        assert_eq!(
+            PrinterId::parse(PrinterIdRequest::TypeId, &byte(0b0000_0010)).unwrap(),
+            PrinterId::TypeId(PrinterTypeId {
+                multi_byte_characters: false,
+                autocutter: true,
+            })
+        );
+        assert!(PrinterId::parse(PrinterIdRequest::VersionId, &byte(0b0001_0000)).is_err());
+
+        let block = |data: &[u8]| PrinterResponse::Block(data.to_vec());
+        let firmware = PrinterId::parse(PrinterIdRequest::Firmware, &block(b"_30.01 ESC/POS\0")).unwrap();
+        assert_eq!(firmware, PrinterId::Firmware("30.01 ESC/POS".to_string()));
+        assert_eq!(firmware.text(), Some("30.01 ESC/POS"));
+        assert_eq!(
+            PrinterId::parse(PrinterIdRequest::Manufacturer, &block(b"_EPSON\0")).unwrap(),
+            PrinterId::Manufacturer("EPSON".to_string())
+        );
+        assert_eq!(
+            PrinterId::parse(PrinterIdRequest::SerialNumber, &block(b"_\0")).unwrap(),
+            PrinterId::SerialNumber(String::new())
+        );
+        assert!(PrinterId::parse(PrinterIdRequest::ModelName, &block(b"EPSON\0")).is_err());
+        assert!(PrinterId::parse(PrinterIdRequest::ModelName, &block(&[0x37, 0x22, 0])).is_err());
+        assert!(PrinterId::parse(PrinterIdRequest::ModelName, &byte(0)).is_err());
+        assert_eq!(PrinterId::ModelId(1).text(), None);
+    }
+
+    #[test]
+    fn test_transmit_status_parse() {
+        assert_eq!(
+            TransmitStatus::parse(TransmitStatusRequest::PaperSensor, 0b0000_1100).unwrap(),
+            TransmitStatus::PaperSensor(PaperSensorStatus {
+                paper_adequate: true,
+                paper_present: false,
+            })
+        );
+        assert_eq!(
+            TransmitStatus::parse(TransmitStatusRequest::Drawer, 0b0000_0001).unwrap(),
+            TransmitStatus::Drawer {
+                kick_out_connector_pin3_low: false
+            }
+        );
+        assert_eq!(
+            TransmitStatus::parse(TransmitStatusRequest::Ink, 0b0000_0010).unwrap(),
+            TransmitStatus::Ink {
+                ink_a_near_end: false,
+                ink_b_near_end: true,
+            }
+        );
+        assert!(TransmitStatus::parse(TransmitStatusRequest::Ink, 0b1000_0000).is_err());
+    }
+
+    #[test]
+    fn test_process_id() {
+        assert_eq!(ProcessId::new(7).unwrap().digits(), *b"0007");
+        assert_eq!(ProcessId::new(9999).unwrap().to_string(), "9999");
+        assert!(ProcessId::new(10000).is_err());
+
+        let response = PrinterResponse::Block(vec![0x37, 0x22, b'9', b'9', b'9', b'9', 0]);
+        assert_eq!(ProcessId::parse(&response).unwrap(), ProcessId::new(9999).unwrap());
+        let response = PrinterResponse::Block(vec![0x37, 0x22, b'9', b'A', b'9', b'9', 0]);
+        assert!(ProcessId::parse(&response).is_err());
+        assert!(ProcessId::parse(&PrinterResponse::Byte(0x37)).is_err());
+    }
+}
@@ -1,8 +1,11 @@
 //! Protocol used to communicate with the printer
 
 #[cfg(feature = "graphics")]
-use super::bit_image::*;
-use super::{character::*, codes::*, common::get_parameters_number_2, constants::*, types::*, RealTimeStatusRequest};
+use super::{bit_image::*, graphics::*, stored_graphics::*};
+use super::{
+    character::*, codes::*, common::get_parameters_number_2, constants::*, types::*, user_defined::*, AsbEvents,
+    PrinterIdRequest, ProcessId, RealTimeStatusRequest, TransmitStatusRequest,
+};
 use crate::{
     domain::page_codes::PageCodeTable,
     errors::{PrinterError, Result},
@@ -167,6 +170,14 @@ impl Protocol {
         }
     }
 
+    /// Print color
+    pub(crate) fn color(&self, color: Color) -> Command {
+        match color {
+            Color::Black => ESC_TEXT_COLOR_BLACK.to_vec(),
+            Color::Red => ESC_TEXT_COLOR_RED.to_vec(),
+        }
+    }
+
     /// Cash drawer
     pub(crate) fn cash_drawer(&self, pin: CashDrawer) -> Command {
         match pin {
@@ -208,6 +219,101 @@ impl Protocol {
         }
     }
 
+    /// Print text replacing some characters by user-defined characters
+    ///
+    /// Runs of user-defined characters are printed with the font of their glyph and the user-defined
+    /// character set selected (`ESC % 1`), other text with the current font and the set cancelled (`ESC % 0`).
+    /// The font and the user-defined character set selection of `state` are restored at the end.
+    pub(crate) fn text_with_user_defined(
+        &self,
+        text: &str,
+        page_code: Option<PageCode>,
+        state: &UserDefinedState,
+    ) -> Result<Command> {
+        let mut cmd = Vec::new();
+        let mut current = (state.font, state.selected);
+        let mut chars = text.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match state.characters.get(&c) {
+                Some(&(code, font)) => {
+                    let mut codes = vec![code];
+                    while let Some(&(code, _)) = chars
+                        .peek()
+                        .and_then(|next| state.characters.get(next))
+                        .filter(|(_, next_font)| *next_font == font)
+                    {
+                        codes.push(code);
+                        chars.next();
+                    }
+                    self.switch_user_defined_state(&mut cmd, &mut current, (font, true));
+                    cmd.extend_from_slice(&codes);
+                }
+                None => {
+                    let mut run = String::from(c);
+                    while let Some(next) = chars.next_if(|next| !state.characters.contains_key(next)) {
+                        run.push(next);
+                    }
+                    self.switch_user_defined_state(&mut cmd, &mut current, (state.font, false));
+                    cmd.append(&mut self.text(&run, page_code)?);
+                }
+            }
+        }
+        self.switch_user_defined_state(&mut cmd, &mut current, (state.font, state.selected));
+
+        Ok(cmd)
+    }
+
+    /// Select the font and the user-defined character set, only sending the commands needed
+    fn switch_user_defined_state(&self, cmd: &mut Command, current: &mut (Font, bool), target: (Font, bool)) {
+        if current.1 && !target.1 {
+            cmd.append(&mut self.user_defined_characters(false));
+        }
+        if current.0 != target.0 {
+            cmd.append(&mut self.font(target.0));
+        }
+        if !current.1 && target.1 {
+            cmd.append(&mut self.user_defined_characters(true));
+        }
+        *current = target;
+    }
+
+    /// Define a user-defined character
+    ///
+    /// The glyph is defined for the selected font, so the font of the character is selected
+    /// before `ESC &` when it is not `current_font`, then `current_font` is restored.
+    pub(crate) fn define_user_character(&self, character: &UserDefinedCharacter, current_font: Font) -> Command {
+        let mut cmd = Vec::new();
+        if character.font() != current_font {
+            cmd.append(&mut self.font(character.font()));
+        }
+        cmd.extend_from_slice(ESC_USER_DEFINED_CHARACTERS_DEFINE);
+        cmd.push(USER_DEFINED_VERTICAL_BYTES);
+        cmd.push(character.code());
+        cmd.push(character.code());
+        cmd.push(character.width());
+        cmd.extend_from_slice(character.data());
+        if character.font() != current_font {
+            cmd.append(&mut self.font(current_font));
+        }
+        cmd
+    }
+
+    /// Select or cancel user-defined character set
+    pub(crate) fn user_defined_characters(&self, enabled: bool) -> Command {
+        match enabled {
+            true => ESC_USER_DEFINED_CHARACTERS_ON.to_vec(),
+            false => ESC_USER_DEFINED_CHARACTERS_OFF.to_vec(),
+        }
+    }
+
+    /// Cancel a user-defined character
+    pub(crate) fn cancel_user_defined_character(&self, code: u8) -> Command {
+        let mut cmd = ESC_USER_DEFINED_CHARACTER_CANCEL.to_vec();
+        cmd.push(code);
+        cmd
+    }
+
     /// Set horizontal and vertical motion units
     pub(crate) fn motion_units(&self, x: u8, y: u8) -> Command {
         let mut cmd = GS_SET_MOTION_UNITS.to_vec();
@@ -225,6 +331,34 @@ impl Protocol {
         cmd
     }
 
+    /// Enable or disable automatic status back
+    pub(crate) fn automatic_status_back(&self, events: AsbEvents) -> Command {
+        let mut cmd = GS_AUTOMATIC_STATUS_BACK.to_vec();
+        cmd.push(events.into());
+        cmd
+    }
+
+    /// Transmit printer ID
+    pub(crate) fn transmit_printer_id(&self, request: PrinterIdRequest) -> Command {
+        let mut cmd = GS_PRINTER_ID.to_vec();
+        cmd.push(request.into());
+        cmd
+    }
+
+    /// Transmit status
+    pub(crate) fn transmit_status(&self, request: TransmitStatusRequest) -> Command {
+        let mut cmd = GS_TRANSMIT_STATUS.to_vec();
+        cmd.push(request.into());
+        cmd
+    }
+
+    /// Request transmission of the process ID response
+    pub(crate) fn process_id_response(&self, id: ProcessId) -> Command {
+        let mut cmd = GS_PROCESS_ID_RESPONSE.to_vec();
+        cmd.extend(id.digits());
+        cmd
+    }
+
     #[cfg(feature = "barcodes")]
     /// Set barcode font
     fn barcode_font(&self, font: BarcodeFont) -> Command {
@@ -245,12 +379,16 @@ impl Protocol {
     }
 
     #[cfg(feature = "barcodes")]
-    /// Set barcode width (1 - 5)
+    /// Set barcode module width (1 - 68)
     fn barcode_width(&self, width: u8) -> Result<Command> {
         if width == 0 {
             return Err(PrinterError::Input("barcode width cannot be equal to 0".to_owned()));
         }
-        let width = if width > 5 { 5 } else { width };
+        if width > BARCODE_MAX_MODULE_WIDTH {
+            return Err(PrinterError::Input(format!(
+                "barcode width cannot be greater than {BARCODE_MAX_MODULE_WIDTH}: {width}"
+            )));
+        }
         let mut cmd = GS_BARCODE_WIDTH.to_vec();
         cmd.push(width);
         Ok(cmd)
@@ -266,12 +404,27 @@ impl Protocol {
 
     #[cfg(feature = "barcodes")]
     /// Print barcode
-    fn barcode_print(&self, system: BarcodeSystem, data: &str) -> Command {
+    ///
+    /// Function A data is NUL-terminated, function B data is prefixed by its length.
+    fn barcode_print(&self, system: BarcodeSystem, data: &str, function: BarcodeFunction) -> Result<Command> {
+        let m = system
+            .m(function)
+            .ok_or_else(|| PrinterError::Input(format!("{system} barcode is not available with {function}")))?;
+        let mut data = system.encode(data)?;
+
         let mut cmd = GS_BARCODE_PRINT.to_vec();
-        cmd.push(system.into());
-        cmd.append(&mut data.as_bytes().to_vec());
-        cmd.push(NUL);
-        cmd
+        cmd.push(m);
+        match function {
+            BarcodeFunction::A => {
+                cmd.append(&mut data);
+                cmd.push(NUL);
+            }
+            BarcodeFunction::B => {
+                cmd.push(u8::try_from(data.len())?);
+                cmd.append(&mut data);
+            }
+        }
+        Ok(cmd)
     }
 
     #[cfg(feature = "barcodes")]
@@ -282,7 +435,7 @@ impl Protocol {
             self.barcode_height(option.height().into())?,
             self.barcode_font(option.font()),
             self.barcode_position(option.position()),
-            self.barcode_print(system, data),
+            self.barcode_print(system, data, option.function().unwrap_or(system.default_function()))?,
         ])
     }
 
@@ -314,11 +467,11 @@ impl Protocol {
 
     #[cfg(feature = "codes_2d")]
     /// QR code data
-    fn qrcode_data(&self, data: &str) -> Result<Command> {
+    fn qrcode_data(&self, data: &[u8]) -> Result<Command> {
         let mut cmd = GS_2D.to_vec();
         let (pl, ph) = get_parameters_number_2(data, 3)?;
         cmd.append(&mut vec![pl, ph, 49, 80, 48]);
-        cmd.append(&mut data.as_bytes().to_vec());
+        cmd.extend_from_slice(data);
         Ok(cmd)
     }
 
@@ -330,7 +483,7 @@ impl Protocol {
 
     #[cfg(feature = "codes_2d")]
     /// QR code print
-    pub(crate) fn qrcode(&self, data: &str, option: QRCodeOption) -> Result<Vec<Command>> {
+    pub(crate) fn qrcode(&self, data: &[u8], option: QRCodeOption) -> Result<Vec<Command>> {
         Ok(vec![
             self.qrcode_model(option.model()),
             self.qrcode_size(option.size()),
@@ -361,7 +514,7 @@ impl Protocol {
     /// 2D GS1 DataBar data
     fn gs1_databar_2d_data(&self, data: &str, code_type: GS1DataBar2DType) -> Result<Command> {
         let mut cmd = GS_2D.to_vec();
-        let (pl, ph) = get_parameters_number_2(data, 4)?;
+        let (pl, ph) = get_parameters_number_2(data.as_bytes(), 4)?;
         cmd.push(pl);
         cmd.push(ph);
         cmd.append(&mut vec![51, 80, 48]);
@@ -388,6 +541,77 @@ impl Protocol {
         ])
     }
 
+    #[cfg(feature = "codes_2d")]
+    /// Composite symbology module width
+    fn composite_module_width(&self, width: u8) -> Command {
+        let mut cmd = GS_2D_COMPOSITE_MODULE_WIDTH.to_vec();
+        cmd.push(width);
+        cmd
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Composite symbology GS1 DataBar Expanded Stacked max width
+    fn composite_expanded_max_width(&self, dots: u16) -> Command {
+        let mut cmd = GS_2D_COMPOSITE_EXPANDED_MAX_WIDTH.to_vec();
+        cmd.extend_from_slice(&dots.to_le_bytes());
+        cmd
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Composite symbology HRI font
+    fn composite_hri_font(&self, font: CompositeFont) -> Command {
+        let mut cmd = GS_2D_COMPOSITE_HRI_FONT.to_vec();
+        cmd.push(font.into());
+        cmd
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Composite symbology linear component data
+    fn composite_linear_data(&self, data: &str, linear_type: CompositeLinearType) -> Result<Command> {
+        let mut cmd = GS_2D.to_vec();
+        let (pl, ph) = get_parameters_number_2(data.as_bytes(), 5)?;
+        cmd.push(pl);
+        cmd.push(ph);
+        cmd.append(&mut vec![52, 80, 48, 48]);
+        cmd.push(linear_type.into());
+        cmd.append(&mut data.as_bytes().to_vec());
+
+        Ok(cmd)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Composite symbology 2D component data (CC-A or CC-B automatically selected)
+    fn composite_2d_data(&self, data: &str) -> Result<Command> {
+        let mut cmd = GS_2D.to_vec();
+        let (pl, ph) = get_parameters_number_2(data.as_bytes(), 5)?;
+        cmd.push(pl);
+        cmd.push(ph);
+        cmd.append(&mut vec![52, 80, 48, 49, 65]);
+        cmd.append(&mut data.as_bytes().to_vec());
+
+        Ok(cmd)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Composite symbology print
+    fn composite_print(&self) -> Command {
+        GS_2D_COMPOSITE_PRINT.to_vec()
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Composite symbology
+    pub(crate) fn composite(&self, composite: &Composite) -> Result<Vec<Command>> {
+        let option = &composite.option;
+        Ok(vec![
+            self.composite_module_width(option.module_width()),
+            self.composite_expanded_max_width(option.get_expanded_max_width()),
+            self.composite_hri_font(option.font()),
+            self.composite_linear_data(&composite.linear_data, option.linear_type())?,
+            self.composite_2d_data(&composite.composite_data)?,
+            self.composite_print(),
+        ])
+    }
+
     #[cfg(feature = "codes_2d")]
     /// PDF417 number of columns
     fn pdf417_columns(&self, option: &Pdf417Option) -> Command {
@@ -440,13 +664,13 @@ impl Protocol {
 
     #[cfg(feature = "codes_2d")]
     /// PDF417 data
-    fn pdf417_data(&self, data: &str) -> Result<Command> {
+    fn pdf417_data(&self, data: &[u8]) -> Result<Command> {
         let mut cmd = GS_2D.to_vec();
         let (pl, ph) = get_parameters_number_2(data, 3)?;
         cmd.push(pl);
         cmd.push(ph);
         cmd.append(&mut vec![48, 80, 48]);
-        cmd.append(&mut data.as_bytes().to_vec());
+        cmd.extend_from_slice(data);
         Ok(cmd)
     }
 
@@ -458,7 +682,7 @@ impl Protocol {
 
     #[cfg(feature = "codes_2d")]
     /// PDF417
-    pub(crate) fn pdf417(&self, data: &str, option: Pdf417Option) -> Result<Vec<Command>> {
+    pub(crate) fn pdf417(&self, data: &[u8], option: Pdf417Option) -> Result<Vec<Command>> {
         Ok(vec![
             self.pdf417_columns(&option),
             self.pdf417_rows(&option),
@@ -481,13 +705,13 @@ impl Protocol {
 
     #[cfg(feature = "codes_2d")]
     /// MaxiCode data
-    fn maxi_code_data(&self, data: &str) -> Result<Command> {
+    fn maxi_code_data(&self, data: &[u8]) -> Result<Command> {
         let mut cmd = GS_2D.to_vec();
         let (pl, ph) = get_parameters_number_2(data, 3)?;
         cmd.push(pl);
         cmd.push(ph);
         cmd.append(&mut vec![50, 80, 48]);
-        cmd.append(&mut data.as_bytes().to_vec());
+        cmd.extend_from_slice(data);
         Ok(cmd)
     }
 
@@ -499,12 +723,10 @@ impl Protocol {
 
     #[cfg(feature = "codes_2d")]
     /// MaxiCode
-    pub(crate) fn maxi_code(&self, data: &str, mode: MaxiCodeMode) -> Result<Vec<Command>> {
-        let code = MaxiCode::new(data, mode);
-
+    pub(crate) fn maxi_code(&self, data: &[u8], mode: MaxiCodeMode) -> Result<Vec<Command>> {
         Ok(vec![
-            self.maxi_code_mode(code.mode),
-            self.maxi_code_data(&code.data)?,
+            self.maxi_code_mode(mode),
+            self.maxi_code_data(data)?,
             self.maxi_code_print(),
         ])
     }
@@ -530,13 +752,13 @@ impl Protocol {
 
     #[cfg(feature = "codes_2d")]
     /// DataMatrix data
-    fn data_matrix_data(&self, data: &str) -> Result<Command> {
+    fn data_matrix_data(&self, data: &[u8]) -> Result<Command> {
         let mut cmd = GS_2D.to_vec();
         let (pl, ph) = get_parameters_number_2(data, 3)?;
         cmd.push(pl);
         cmd.push(ph);
         cmd.append(&mut vec![54, 80, 48]);
-        cmd.append(&mut data.as_bytes().to_vec());
+        cmd.extend_from_slice(data);
         Ok(cmd)
     }
 
@@ -548,7 +770,7 @@ impl Protocol {
 
     #[cfg(feature = "codes_2d")]
     /// DataMatrix
-    pub(crate) fn data_matrix(&self, data: &str, option: DataMatrixOption) -> Result<Vec<Command>> {
+    pub(crate) fn data_matrix(&self, data: &[u8], option: DataMatrixOption) -> Result<Vec<Command>> {
         Ok(vec![
             self.data_matrix_type(option.code_type())?,
             self.data_matrix_size(option.size()),
@@ -585,13 +807,13 @@ impl Protocol {
 
     #[cfg(feature = "codes_2d")]
     /// Aztec code data
-    fn aztec_data(&self, data: &str) -> Result<Command> {
+    fn aztec_data(&self, data: &[u8]) -> Result<Command> {
         let mut cmd = GS_2D.to_vec();
         let (pl, ph) = get_parameters_number_2(data, 3)?;
         cmd.push(pl);
         cmd.push(ph);
         cmd.append(&mut vec![53, 80, 48]);
-        cmd.append(&mut data.as_bytes().to_vec());
+        cmd.extend_from_slice(data);
         Ok(cmd)
     }
 
@@ -603,12 +825,11 @@ impl Protocol {
 
     #[cfg(feature = "codes_2d")]
     /// Aztec code
-    pub(crate) fn aztec(&self, data: &str, option: AztecOption) -> Result<Vec<Command>> {
-        let code = Aztec::new(data, option);
+    pub(crate) fn aztec(&self, data: &[u8], option: AztecOption) -> Result<Vec<Command>> {
         Ok(vec![
-            self.aztec_mode(code.option.mode())?,
-            self.aztec_size(code.option.size()),
-            self.aztec_correction_level(code.option.correction_level()),
+            self.aztec_mode(option.mode())?,
+            self.aztec_size(option.size()),
+            self.aztec_correction_level(option.correction_level()),
             self.aztec_data(data)?,
             self.aztec_print(),
         ])
@@ -616,97 +837,238 @@ impl Protocol {
 
     #[cfg(feature = "graphics")]
     /// Print bit image
-    pub(crate) fn bit_image(&self, path: &str, option: BitImageOption) -> Result<Command> {
+    pub(crate) fn bit_image(&self, path: &str, option: BitImageOption) -> Result<Vec<Command>> {
         let bit_image = BitImage::new(path, option)?;
-        self.build_bit_image(bit_image)
+        self.build_bit_image(&bit_image, bit_image.mode().unwrap_or_default())
     }
 
     #[cfg(feature = "graphics")]
     /// Print bit image from bytes
-    pub(crate) fn bit_image_from_bytes(&self, bytes: &[u8], option: BitImageOption) -> Result<Command> {
+    pub(crate) fn bit_image_from_bytes(&self, bytes: &[u8], option: BitImageOption) -> Result<Vec<Command>> {
         let bit_image = BitImage::from_bytes(bytes, option)?;
-        self.build_bit_image(bit_image)
+        self.build_bit_image(&bit_image, bit_image.mode().unwrap_or_default())
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print a bit image with a mode
+    pub(crate) fn build_bit_image(&self, bit_image: &BitImage, mode: BitImageMode) -> Result<Vec<Command>> {
+        match mode.column_format() {
+            Some((m, band_height)) => self.build_column_bit_image(bit_image, m, band_height),
+            None => self.build_raster_bit_image(bit_image),
+        }
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Column format bit image (`ESC *`)
+    ///
+    /// The image is printed band by band, with the line spacing set to the band height.
+    fn build_column_bit_image(&self, bit_image: &BitImage, m: u8, band_height: u8) -> Result<Vec<Command>> {
+        let [nl, nh] = bit_image.width()?.to_le_bytes();
+
+        let mut cmds = vec![self.line_spacing(band_height)];
+        for mut band in bit_image.column_data(band_height)? {
+            let mut cmd = ESC_IMAGE_COLUMN_PREFIX.to_vec();
+            cmd.append(&mut vec![m, nl, nh]);
+            cmd.append(&mut band);
+            cmd.push(LF);
+            cmds.push(cmd);
+        }
+        cmds.push(self.reset_line_spacing());
+
+        Ok(cmds)
     }
 
     #[cfg(feature = "graphics")]
-    fn build_bit_image(&self, bit_image: BitImage) -> Result<Command> {
-        let mut cmd = GS_IMAGE_BITMAP_PREFIX.to_vec();
+    /// Raster format bit image (`GS v 0`), one command per band
+    fn build_raster_bit_image(&self, bit_image: &BitImage) -> Result<Vec<Command>> {
+        let size = bit_image.size().into();
+        let width = bit_image.with_bytes_u8()?;
+
+        bit_image
+            .raster_bands()?
+            .into_iter()
+            .map(|(mut height, mut data)| {
+                let mut cmd = GS_IMAGE_BITMAP_PREFIX.to_vec();
 
-        // Size
-        cmd.push(bit_image.size().into());
+                // Size
+                cmd.push(size);
 
-        // Width and height
-        cmd.append(&mut bit_image.with_bytes_u8()?);
-        cmd.append(&mut bit_image.height_u8()?);
+                // Width and height
+                cmd.extend_from_slice(&width);
+                cmd.append(&mut height);
 
-        // Data
-        cmd.append(&mut bit_image.raster_data()?);
+                // Data
+                cmd.append(&mut data);
 
+                Ok(cmd)
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Graphics command (`GS ( L` or `GS 8 L` depending on the data size)
+    fn graphics_command(&self, params: &[u8]) -> Result<Command> {
+        let mut cmd = match u16::try_from(params.len()) {
+            Ok(len) => {
+                let mut cmd = GS_IMAGE_LOW_PREFIX.to_vec();
+                cmd.extend_from_slice(&len.to_le_bytes());
+                cmd
+            }
+            Err(_) => {
+                let mut cmd = GS_IMAGE_HIGH_PREFIX.to_vec();
+                cmd.extend_from_slice(&u32::try_from(params.len())?.to_le_bytes());
+                cmd
+            }
+        };
+        cmd.extend_from_slice(params);
         Ok(cmd)
     }
 
-    // #[cfg(feature = "graphics")]
-    // /// Graphic density
-    // pub(crate) fn graphic_density(&self, density: GraphicDensity) -> Command {
-    //     let mut cmd = GS_IMAGE_DENSITY.to_vec();
-    //     cmd.push(density.into());
-    //     cmd.push(density.into());
-    //     cmd
-    // }
-
-    // #[cfg(feature = "graphics")]
-    // /// Print graphic
-    // pub(crate) fn graphic_print(&self) -> Command {
-    //     GS_IMAGE_PRINT.to_vec()
-    // }
-
-    // #[cfg(feature = "graphics")]
-    // /// Print graphic
-    // pub(crate) fn graphic_data(&self, path: &str) -> Result<Command> {
-    //     let mut cmd = GS_IMAGE_HIGH_PREFIX.to_vec();
-    //
-    //     // pL, pH => Parameters
-    //     let graphic = Graphic::new(path, None)?;
-    //     let (p1, p2, p3, p4) = graphic.data_size()?;
-    //     cmd.push(p1);
-    //     cmd.push(p2);
-    //     cmd.push(p3);
-    //     cmd.push(p4);
-    //     cmd.push(48);
-    //     cmd.push(112);
-    //
-    //     // Tone
-    //     cmd.push(graphic.tone());
-    //
-    //     // bx, by
-    //     cmd.push(1);
-    //     cmd.push(1);
-    //
-    //     // Color
-    //     cmd.push(graphic.color());
-    //
-    //     // Number of dots
-    //     let (xl, xh) = graphic.dots_per_direction(graphic.width() as usize)?;
-    //     let (yl, yh) = graphic.dots_per_direction(graphic.height() as usize)?;
-    //     cmd.push(xl);
-    //     cmd.push(xh);
-    //     cmd.push(yl);
-    //     cmd.push(yh);
-    //
-    //     dbg!(p1, p2, p3, p4, xl, xh, yl, yh);
-    //
-    //     // Image data
-    //     for i in graphic.data()? {
-    //         cmd.push(i);
-    //     }
-    //
-    //     Ok(cmd)
-    // }
+    #[cfg(feature = "graphics")]
+    /// Define NV or download graphic in raster format
+    pub(crate) fn stored_graphic_define(
+        &self,
+        memory: GraphicMemory,
+        key: GraphicKey,
+        bit_image: &BitImage,
+    ) -> Result<Command> {
+        let (width, height) = (bit_image.width()?, bit_image.height()?);
+        if !(1..=8192).contains(&width) || !(1..=2304).contains(&height) {
+            return Err(PrinterError::Input(format!(
+                "invalid {memory} size (1-8192 x 1-2304 dots): {width} x {height}"
+            )));
+        }
+
+        let (kc1, kc2) = key.codes();
+        let function = match memory {
+            GraphicMemory::NV => 67,
+            GraphicMemory::Download => 83,
+        };
+        let mut params = vec![48, function, 48, kc1, kc2, 1];
+        params.extend_from_slice(&width.to_le_bytes());
+        params.extend_from_slice(&height.to_le_bytes());
+        params.push(GraphicColor::Color1.into());
+        params.append(&mut bit_image.raster_data()?);
+
+        self.graphics_command(&params)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print NV or download graphic
+    pub(crate) fn stored_graphic_print(
+        &self,
+        memory: GraphicMemory,
+        key: GraphicKey,
+        width_size: GraphicSize,
+        height_size: GraphicSize,
+    ) -> Command {
+        let mut cmd = match memory {
+            GraphicMemory::NV => GS_GRAPHICS_NV_PRINT.to_vec(),
+            GraphicMemory::Download => GS_GRAPHICS_DOWNLOAD_PRINT.to_vec(),
+        };
+        let (kc1, kc2) = key.codes();
+        cmd.append(&mut vec![kc1, kc2, width_size.into(), height_size.into()]);
+        cmd
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Delete NV or download graphic
+    pub(crate) fn stored_graphic_delete(&self, memory: GraphicMemory, key: GraphicKey) -> Command {
+        let mut cmd = match memory {
+            GraphicMemory::NV => GS_GRAPHICS_NV_DELETE.to_vec(),
+            GraphicMemory::Download => GS_GRAPHICS_DOWNLOAD_DELETE.to_vec(),
+        };
+        let (kc1, kc2) = key.codes();
+        cmd.push(kc1);
+        cmd.push(kc2);
+        cmd
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Delete all NV or download graphics
+    pub(crate) fn stored_graphics_delete_all(&self, memory: GraphicMemory) -> Command {
+        match memory {
+            GraphicMemory::NV => GS_GRAPHICS_NV_DELETE_ALL.to_vec(),
+            GraphicMemory::Download => GS_GRAPHICS_DOWNLOAD_DELETE_ALL.to_vec(),
+        }
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Transmit the key code list of NV or download graphics
+    pub(crate) fn stored_graphics_key_codes(&self, memory: GraphicMemory) -> Command {
+        match memory {
+            GraphicMemory::NV => GS_GRAPHICS_NV_KEY_CODES.to_vec(),
+            GraphicMemory::Download => GS_GRAPHICS_DOWNLOAD_KEY_CODES.to_vec(),
+        }
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Transmit the remaining capacity of NV or download graphics memory
+    pub(crate) fn stored_graphics_remaining_capacity(&self, memory: GraphicMemory) -> Command {
+        match memory {
+            GraphicMemory::NV => GS_GRAPHICS_NV_REMAINING_CAPACITY.to_vec(),
+            GraphicMemory::Download => GS_GRAPHICS_DOWNLOAD_REMAINING_CAPACITY.to_vec(),
+        }
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Transmit the NV graphics memory capacity
+    pub(crate) fn nv_graphics_capacity(&self) -> Command {
+        GS_GRAPHICS_NV_CAPACITY.to_vec()
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Set graphic reference dot density
+    pub(crate) fn graphic_density(&self, density: GraphicDensity) -> Command {
+        let mut cmd = GS_IMAGE_DENSITY.to_vec();
+        cmd.push(density.into());
+        cmd.push(density.into());
+        cmd
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print graphic stored in the print buffer
+    pub(crate) fn graphic_print(&self) -> Command {
+        GS_IMAGE_PRINT.to_vec()
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Store graphic data in the print buffer (raster format)
+    ///
+    /// One command is generated for each color plane.
+    pub(crate) fn graphic_data(&self, path: &str, option: GraphicOption) -> Result<Vec<Command>> {
+        let graphic = Graphic::new(path, Some(option))?;
+        let (xl, xh) = graphic.dots_per_direction(usize::try_from(graphic.width())?)?;
+        let (yl, yh) = graphic.dots_per_direction(usize::try_from(graphic.height())?)?;
+
+        graphic
+            .planes()?
+            .into_iter()
+            .map(|(color, mut data)| {
+                let mut params = Vec::with_capacity(GRAPHIC_PARAMETERS_LENGTH + data.len());
+                params.extend_from_slice(&[
+                    48,
+                    112,
+                    graphic.tone(),
+                    graphic.width_size(),
+                    graphic.height_size(),
+                    color.into(),
+                    xl,
+                    xh,
+                    yl,
+                    yh,
+                ]);
+                params.append(&mut data);
+                self.graphics_command(&params)
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
 
     #[test]
     fn test_init() {
@@ -847,6 +1209,13 @@ mod tests {
         assert_eq!(protocol.upside_down(true), vec![27, 123, 1]);
     }
 
+    #[test]
+    fn test_color() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(protocol.color(Color::Black), vec![27, 114, 0]);
+        assert_eq!(protocol.color(Color::Red), vec![27, 114, 1]);
+    }
+
     #[test]
     fn test_cash_drawer() {
         let protocol = Protocol::new(Encoder::default());
@@ -988,6 +1357,93 @@ mod tests {
         assert!(protocol.text("My text", Some(PageCode::Hiragana)).is_err());
     }
 
+    #[test]
+    fn test_text_with_user_defined() {
+        let protocol = Protocol::new(Encoder::default());
+        let state = UserDefinedState {
+            characters: HashMap::from([('€', (35, Font::A)), ('■', (36, Font::A))]),
+            ..Default::default()
+        };
+        assert_eq!(
+            protocol.text_with_user_defined("a€■b", None, &state).unwrap(),
+            vec![97, 27, 37, 1, 35, 36, 27, 37, 0, 98]
+        );
+        assert_eq!(
+            protocol.text_with_user_defined("€", None, &state).unwrap(),
+            vec![27, 37, 1, 35, 27, 37, 0]
+        );
+        assert_eq!(
+            protocol
+                .text_with_user_defined("é€", Some(PageCode::PC858), &state)
+                .unwrap(),
+            vec![130, 27, 37, 1, 35, 27, 37, 0]
+        );
+        assert_eq!(
+            protocol
+                .text_with_user_defined("abc", None, &UserDefinedState::default())
+                .unwrap(),
+            vec![97, 98, 99]
+        );
+    }
+
+    #[test]
+    fn test_text_with_user_defined_state() {
+        let protocol = Protocol::new(Encoder::default());
+        let mut state = UserDefinedState {
+            characters: HashMap::from([('€', (35, Font::A)), ('■', (36, Font::B))]),
+            ..Default::default()
+        };
+        // Font B glyph selects font B, then font A is restored
+        assert_eq!(
+            protocol.text_with_user_defined("a€■b", None, &state).unwrap(),
+            vec![97, 27, 37, 1, 35, 27, 77, 1, 36, 27, 37, 0, 27, 77, 0, 98]
+        );
+        assert_eq!(
+            protocol.text_with_user_defined("ab", None, &state).unwrap(),
+            vec![97, 98]
+        );
+
+        // User-defined character set already selected: only cancelled for other text
+        state.selected = true;
+        assert_eq!(protocol.text_with_user_defined("€", None, &state).unwrap(), vec![35]);
+        assert_eq!(
+            protocol.text_with_user_defined("€a", None, &state).unwrap(),
+            vec![35, 27, 37, 0, 97, 27, 37, 1]
+        );
+
+        state.font = Font::B;
+        state.selected = false;
+        assert_eq!(
+            protocol.text_with_user_defined("■€", None, &state).unwrap(),
+            vec![27, 37, 1, 36, 27, 77, 0, 35, 27, 37, 0, 27, 77, 1]
+        );
+    }
+
+    #[test]
+    fn test_define_user_character() {
+        let protocol = Protocol::new(Encoder::default());
+        let mut rows = vec![vec![false; 2]; 24];
+        rows[0][0] = true;
+        rows[23][1] = true;
+        let character = UserDefinedCharacter::new('€', 65, Font::A, &rows).unwrap();
+        assert_eq!(
+            protocol.define_user_character(&character, Font::A),
+            vec![27, 38, 3, 65, 65, 2, 128, 0, 0, 0, 0, 1]
+        );
+        assert_eq!(
+            protocol.define_user_character(&character, Font::B),
+            vec![27, 77, 0, 27, 38, 3, 65, 65, 2, 128, 0, 0, 0, 0, 1, 27, 77, 1]
+        );
+    }
+
+    #[test]
+    fn test_user_defined_characters() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(protocol.user_defined_characters(false), vec![27, 37, 0]);
+        assert_eq!(protocol.user_defined_characters(true), vec![27, 37, 1]);
+        assert_eq!(protocol.cancel_user_defined_character(65), vec![27, 63, 65]);
+    }
+
     #[test]
     fn test_motion_units() {
         let protocol = Protocol::new(Encoder::default());
@@ -1036,6 +1492,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_automatic_status_back() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(protocol.automatic_status_back(AsbEvents::default()), vec![29, 97, 0]);
+        assert_eq!(protocol.automatic_status_back(AsbEvents::all()), vec![29, 97, 79]);
+    }
+
+    #[test]
+    fn test_transmit_printer_id() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(protocol.transmit_printer_id(PrinterIdRequest::ModelId), vec![29, 73, 1]);
+        assert_eq!(
+            protocol.transmit_printer_id(PrinterIdRequest::VersionId),
+            vec![29, 73, 3]
+        );
+        assert_eq!(
+            protocol.transmit_printer_id(PrinterIdRequest::Firmware),
+            vec![29, 73, 65]
+        );
+        assert_eq!(
+            protocol.transmit_printer_id(PrinterIdRequest::SerialNumber),
+            vec![29, 73, 68]
+        );
+    }
+
+    #[test]
+    fn test_transmit_status() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(
+            protocol.transmit_status(TransmitStatusRequest::PaperSensor),
+            vec![29, 114, 1]
+        );
+        assert_eq!(
+            protocol.transmit_status(TransmitStatusRequest::Drawer),
+            vec![29, 114, 2]
+        );
+        assert_eq!(protocol.transmit_status(TransmitStatusRequest::Ink), vec![29, 114, 4]);
+    }
+
+    #[test]
+    fn test_process_id_response() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(
+            protocol.process_id_response(ProcessId::new(42).unwrap()),
+            vec![29, 40, 72, 6, 0, 48, 48, 48, 48, 52, 50]
+        );
+    }
+
     #[cfg(feature = "barcodes")]
     #[test]
     fn test_barcode_font() {
@@ -1062,7 +1566,9 @@ mod tests {
         assert!(protocol.barcode_width(0).is_err());
         assert_eq!(protocol.barcode_width(5).unwrap(), vec![29, 119, 5]);
         assert_eq!(protocol.barcode_width(1).unwrap(), vec![29, 119, 1]);
-        assert_eq!(protocol.barcode_width(18).unwrap(), vec![29, 119, 5]);
+        assert_eq!(protocol.barcode_width(18).unwrap(), vec![29, 119, 18]);
+        assert_eq!(protocol.barcode_width(68).unwrap(), vec![29, 119, 68]);
+        assert!(protocol.barcode_width(69).is_err());
     }
 
     #[cfg(feature = "barcodes")]
@@ -1080,35 +1586,102 @@ mod tests {
     fn test_barcode_print() {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
-            protocol.barcode_print(BarcodeSystem::UPCA, "12587458745"),
+            protocol
+                .barcode_print(BarcodeSystem::UPCA, "12587458745", BarcodeFunction::A)
+                .unwrap(),
             vec![29, 107, 0, b'1', b'2', b'5', b'8', b'7', b'4', b'5', b'8', b'7', b'4', b'5', 0]
         );
         assert_eq!(
-            protocol.barcode_print(BarcodeSystem::UPCE, "02587458745"),
+            protocol
+                .barcode_print(BarcodeSystem::UPCE, "02587458745", BarcodeFunction::A)
+                .unwrap(),
             vec![29, 107, 1, b'0', b'2', b'5', b'8', b'7', b'4', b'5', b'8', b'7', b'4', b'5', 0]
         );
         assert_eq!(
-            protocol.barcode_print(BarcodeSystem::EAN13, "025874587456"),
+            protocol
+                .barcode_print(BarcodeSystem::EAN13, "025874587456", BarcodeFunction::A)
+                .unwrap(),
             vec![29, 107, 2, b'0', b'2', b'5', b'8', b'7', b'4', b'5', b'8', b'7', b'4', b'5', b'6', 0]
         );
         assert_eq!(
-            protocol.barcode_print(BarcodeSystem::EAN8, "0587456"),
+            protocol
+                .barcode_print(BarcodeSystem::EAN8, "0587456", BarcodeFunction::A)
+                .unwrap(),
             vec![29, 107, 3, b'0', b'5', b'8', b'7', b'4', b'5', b'6', 0]
         );
         assert_eq!(
-            protocol.barcode_print(BarcodeSystem::CODE39, "05A$"),
+            protocol
+                .barcode_print(BarcodeSystem::CODE39, "05A$", BarcodeFunction::A)
+                .unwrap(),
             vec![29, 107, 4, b'0', b'5', b'A', b'$', 0]
         );
         assert_eq!(
-            protocol.barcode_print(BarcodeSystem::ITF, "0585"),
+            protocol
+                .barcode_print(BarcodeSystem::ITF, "0585", BarcodeFunction::A)
+                .unwrap(),
             vec![29, 107, 5, b'0', b'5', b'8', b'5', 0]
         );
         assert_eq!(
-            protocol.barcode_print(BarcodeSystem::CODABAR, "A05A$C"),
+            protocol
+                .barcode_print(BarcodeSystem::CODABAR, "A05A$C", BarcodeFunction::A)
+                .unwrap(),
             vec![29, 107, 6, b'A', b'0', b'5', b'A', b'$', b'C', 0]
         );
     }
 
+    #[cfg(feature = "barcodes")]
+    #[test]
+    fn test_barcode_print_function_b() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(
+            protocol
+                .barcode_print(BarcodeSystem::EAN8, "0587456", BarcodeFunction::B)
+                .unwrap(),
+            vec![29, 107, 68, 7, b'0', b'5', b'8', b'7', b'4', b'5', b'6']
+        );
+        assert_eq!(
+            protocol
+                .barcode_print(BarcodeSystem::CODABAR, "A05A$C", BarcodeFunction::B)
+                .unwrap(),
+            vec![29, 107, 71, 6, b'A', b'0', b'5', b'A', b'$', b'C']
+        );
+        assert_eq!(
+            protocol
+                .barcode_print(BarcodeSystem::CODE93, "ab-1", BarcodeFunction::B)
+                .unwrap(),
+            vec![29, 107, 72, 4, b'a', b'b', b'-', b'1']
+        );
+        assert_eq!(
+            protocol
+                .barcode_print(
+                    BarcodeSystem::CODE128(Code128CodeSet::Auto),
+                    "No.123456",
+                    BarcodeFunction::B
+                )
+                .unwrap(),
+            vec![29, 107, 73, 10, b'{', b'B', b'N', b'o', b'.', b'{', b'C', 12, 34, 56]
+        );
+        assert!(protocol
+            .barcode_print(BarcodeSystem::CODE93, "ab-1", BarcodeFunction::A)
+            .is_err());
+        assert_eq!(
+            protocol
+                .barcode_print(
+                    BarcodeSystem::GS1DataBar(GS1DataBarType::Omnidirectional),
+                    "(01)09501101530003",
+                    BarcodeFunction::B
+                )
+                .unwrap(),
+            [&[29, 107, 75, 13][..], b"0950110153000"].concat()
+        );
+        assert_eq!(
+            protocol
+                .barcode_print(BarcodeSystem::GS1CODE128, "(3103)001250", BarcodeFunction::B)
+                .unwrap(),
+            vec![29, 107, 73, 9, b'{', b'C', b'{', b'1', 31, 3, 0, 12, 50]
+        );
+    }
+
     #[cfg(feature = "barcodes")]
     #[test]
     fn test_barcode() {
@@ -1190,13 +1763,19 @@ mod tests {
     fn test_qrcode_data() {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
-            protocol.qrcode_data("test data qrcode").unwrap(),
+            protocol.qrcode_data(b"test data qrcode").unwrap(),
             vec![
                 29, 40, 107, 19, 0, 49, 80, 48, 116, 101, 115, 116, 32, 100, 97, 116, 97, 32, 113, 114, 99, 111, 100,
                 101
             ]
         );
-        assert_eq!(protocol.qrcode_data("").unwrap(), vec![29, 40, 107, 3, 0, 49, 80, 48]);
+        assert_eq!(protocol.qrcode_data(b"").unwrap(), vec![29, 40, 107, 3, 0, 49, 80, 48]);
+
+        // Binary data, pL and pH count bytes
+        let data = [0xFF, 0x00, 0xE9].repeat(100);
+        let cmd = protocol.qrcode_data(&data).unwrap();
+        assert_eq!(cmd[..8], [29, 40, 107, 47, 1, 49, 80, 48]);
+        assert_eq!(cmd[8..], data);
     }
 
     #[cfg(feature = "codes_2d")]
@@ -1220,7 +1799,7 @@ mod tests {
         assert_eq!(
             protocol
                 .qrcode(
-                    "test",
+                    b"test",
                     QRCodeOption::new(QRCodeModel::Model1, 4, QRCodeCorrectionLevel::L)
                 )
                 .unwrap(),
@@ -1292,6 +1871,70 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "codes_2d")]
+    #[test]
+    fn test_composite_options() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(protocol.composite_module_width(3), vec![29, 40, 107, 3, 0, 52, 60, 3]);
+        assert_eq!(
+            protocol.composite_expanded_max_width(0),
+            vec![29, 40, 107, 4, 0, 52, 61, 0, 0]
+        );
+        assert_eq!(
+            protocol.composite_expanded_max_width(384),
+            vec![29, 40, 107, 4, 0, 52, 61, 128, 1]
+        );
+        assert_eq!(
+            protocol.composite_hri_font(CompositeFont::None),
+            vec![29, 40, 107, 3, 0, 52, 62, 0]
+        );
+        assert_eq!(
+            protocol.composite_hri_font(CompositeFont::SpecialB),
+            vec![29, 40, 107, 3, 0, 52, 62, 98]
+        );
+    }
+
+    #[cfg(feature = "codes_2d")]
+    #[test]
+    fn test_composite_data() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(
+            protocol
+                .composite_linear_data("9501101", CompositeLinearType::EAN8)
+                .unwrap(),
+            vec![29, 40, 107, 12, 0, 52, 80, 48, 48, 65, 57, 53, 48, 49, 49, 48, 49]
+        );
+        assert_eq!(
+            protocol
+                .composite_linear_data("10ABC", CompositeLinearType::GS1128)
+                .unwrap(),
+            vec![29, 40, 107, 10, 0, 52, 80, 48, 48, 77, 49, 48, 65, 66, 67]
+        );
+        assert_eq!(
+            protocol.composite_2d_data("10ABC").unwrap(),
+            vec![29, 40, 107, 10, 0, 52, 80, 48, 49, 65, 49, 48, 65, 66, 67]
+        );
+    }
+
+    #[cfg(feature = "codes_2d")]
+    #[test]
+    fn test_composite() {
+        let protocol = Protocol::new(Encoder::default());
+        let option = CompositeOption::new(CompositeLinearType::UPCA, 2, CompositeFont::A).unwrap();
+        let composite = Composite::new("03600029145", "10ABC", option).unwrap();
+        assert_eq!(
+            protocol.composite(&composite).unwrap(),
+            vec![
+                vec![29, 40, 107, 3, 0, 52, 60, 2],
+                vec![29, 40, 107, 4, 0, 52, 61, 0, 0],
+                vec![29, 40, 107, 3, 0, 52, 62, 1],
+                vec![29, 40, 107, 16, 0, 52, 80, 48, 48, 67, 48, 51, 54, 48, 48, 48, 50, 57, 49, 52, 53],
+                vec![29, 40, 107, 10, 0, 52, 80, 48, 49, 65, 49, 48, 65, 66, 67],
+                vec![29, 40, 107, 3, 0, 52, 81, 48]
+            ]
+        );
+    }
+
     #[cfg(feature = "codes_2d")]
     #[test]
     fn test_pdf417_columns() {
@@ -1361,7 +2004,7 @@ mod tests {
     fn test_pdf417_data() {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
-            protocol.pdf417_data("test").unwrap(),
+            protocol.pdf417_data(b"test").unwrap(),
             vec![29, 40, 107, 7, 0, 48, 80, 48, 116, 101, 115, 116]
         );
     }
@@ -1379,7 +2022,7 @@ mod tests {
         let protocol = Protocol::new(Encoder::default());
         let option = Pdf417Option::default();
         assert_eq!(
-            protocol.pdf417("test", option).unwrap(),
+            protocol.pdf417(b"test", option).unwrap(),
             vec![
                 vec![29, 40, 107, 3, 0, 48, 65, 0],
                 vec![29, 40, 107, 3, 0, 48, 66, 0],
@@ -1399,7 +2042,7 @@ mod tests {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
             protocol.maxi_code_mode(MaxiCodeMode::default()),
-            vec![29, 40, 107, 3, 0, 50, 65, 50]
+            vec![29, 40, 107, 3, 0, 50, 65, 52]
         );
         assert_eq!(
             protocol.maxi_code_mode(MaxiCodeMode::Mode2),
@@ -1428,7 +2071,7 @@ mod tests {
     fn test_maxi_code_data() {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
-            protocol.maxi_code_data("1245").unwrap(),
+            protocol.maxi_code_data(b"1245").unwrap(),
             vec![29, 40, 107, 7, 0, 50, 80, 48, 49, 50, 52, 53]
         );
     }
@@ -1445,7 +2088,7 @@ mod tests {
     fn test_maxi_code() {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
-            protocol.maxi_code("1245", MaxiCodeMode::default()).unwrap(),
+            protocol.maxi_code(b"1245", MaxiCodeMode::Mode2).unwrap(),
             vec![
                 vec![29, 40, 107, 3, 0, 50, 65, 50],
                 vec![29, 40, 107, 7, 0, 50, 80, 48, 49, 50, 52, 53],
@@ -1453,7 +2096,7 @@ mod tests {
             ]
         );
         assert_eq!(
-            protocol.maxi_code("test1245", MaxiCodeMode::default()).unwrap(),
+            protocol.maxi_code(b"test1245", MaxiCodeMode::Mode2).unwrap(),
             vec![
                 vec![29, 40, 107, 3, 0, 50, 65, 50],
                 vec![29, 40, 107, 11, 0, 50, 80, 48, 116, 101, 115, 116, 49, 50, 52, 53],
@@ -1497,7 +2140,7 @@ mod tests {
     fn test_data_matrix_data() {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
-            protocol.data_matrix_data("test123").unwrap(),
+            protocol.data_matrix_data(b"test123").unwrap(),
             vec![29, 40, 107, 10, 0, 54, 80, 48, 116, 101, 115, 116, 49, 50, 51]
         );
     }
@@ -1514,7 +2157,7 @@ mod tests {
     fn test_data_matrix() {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
-            protocol.data_matrix("test123", DataMatrixOption::default()).unwrap(),
+            protocol.data_matrix(b"test123", DataMatrixOption::default()).unwrap(),
             vec![
                 vec![29, 40, 107, 5, 0, 54, 66, 0, 0, 0],
                 vec![29, 40, 107, 3, 0, 54, 67, 3],
@@ -1524,7 +2167,7 @@ mod tests {
         );
         let option = DataMatrixOption::new(DataMatrixType::Rectangle(8, 0), 16).unwrap();
         assert_eq!(
-            protocol.data_matrix("test123", option).unwrap(),
+            protocol.data_matrix(b"test123", option).unwrap(),
             vec![
                 vec![29, 40, 107, 5, 0, 54, 66, 1, 8, 0],
                 vec![29, 40, 107, 3, 0, 54, 67, 16],
@@ -1573,7 +2216,7 @@ mod tests {
     fn test_aztec_data() {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
-            protocol.aztec_data("test123").unwrap(),
+            protocol.aztec_data(b"test123").unwrap(),
             vec![29, 40, 107, 10, 0, 53, 80, 48, 116, 101, 115, 116, 49, 50, 51]
         );
     }
@@ -1590,7 +2233,7 @@ mod tests {
     fn test_aztec() {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
-            protocol.aztec("test123", AztecOption::default()).unwrap(),
+            protocol.aztec(b"test123", AztecOption::default()).unwrap(),
             vec![
                 vec![29, 40, 107, 4, 0, 53, 66, 0, 0],
                 vec![29, 40, 107, 3, 0, 53, 67, 3],
@@ -1612,10 +2255,10 @@ mod tests {
                     BitImageOption::new(None, None, BitImageSize::default()).unwrap(),
                 )
                 .unwrap(),
-            vec![
+            vec![vec![
                 29, 118, 48, 0, 2, 0, 16, 0, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 255, 255, 255,
                 255, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128
-            ]
+            ]]
         );
     }
 
@@ -1632,41 +2275,268 @@ mod tests {
                     BitImageOption::new(None, None, BitImageSize::default()).unwrap(),
                 )
                 .unwrap(),
-            vec![
+            vec![vec![
                 29, 118, 48, 0, 2, 0, 16, 0, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 255, 255, 255,
                 255, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128
+            ]]
+        );
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_bit_image_column_8_dots() {
+        let protocol = Protocol::new(Encoder::default());
+        let option = BitImageOption::new(None, None, BitImageSize::default())
+            .unwrap()
+            .mode(BitImageMode::Column8DotDoubleDensity);
+
+        assert_eq!(
+            protocol.bit_image("./resources/images/small.jpg", option).unwrap(),
+            vec![
+                vec![27, 51, 8],
+                [vec![27, 42, 1, 16, 0], vec![1; 7], vec![255; 2], vec![1; 7], vec![10]].concat(),
+                [
+                    vec![27, 42, 1, 16, 0],
+                    vec![128; 7],
+                    vec![255; 2],
+                    vec![128; 7],
+                    vec![10]
+                ]
+                .concat(),
+                vec![27, 50],
             ]
         );
     }
 
-    // #[cfg(feature = "graphics")]
-    // #[test]
-    // fn test_graphic_density() {
-    //     let protocol = Protocol::new(Encoder::default());
-    //     assert_eq!(
-    //         protocol.graphic_density(GraphicDensity::Low),
-    //         vec![29, 40, 76, 4, 0, 48, 49, 50, 50]
-    //     );
-    //     assert_eq!(
-    //         protocol.graphic_density(GraphicDensity::High),
-    //         vec![29, 40, 76, 4, 0, 48, 49, 51, 51]
-    //     );
-    // }
-
-    // #[cfg(feature = "graphics")]
-    // #[test]
-    // fn test_graphic_print() {
-    //     let protocol = Protocol::new(Encoder::default());
-    //     assert_eq!(protocol.graphic_print(), vec![29, 40, 76, 2, 0, 48, 50]);
-    // }
-
-    // #[cfg(feature = "graphics")]
-    // #[test]
-    // fn test_graphic_data() {
-    //     let protocol = Protocol::new(Encoder::default());
-    //     assert_eq!(
-    //         protocol.graphic_data("./resources/images/rust-logo-small.png").unwrap(),
-    //         vec![29, 40, 76, 48, 1, 1, 49, 200, 0, 200, 0]
-    //     );
-    // }
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_bit_image_column_24_dots() {
+        let protocol = Protocol::new(Encoder::default());
+        let option = BitImageOption::new(None, None, BitImageSize::default())
+            .unwrap()
+            .mode(BitImageMode::Column24DotSingleDensity);
+
+        assert_eq!(
+            protocol.bit_image("./resources/images/small.jpg", option).unwrap(),
+            vec![
+                vec![27, 51, 24],
+                [
+                    vec![27, 42, 32, 16, 0],
+                    [1, 128, 0].repeat(7),
+                    [255, 255, 0].repeat(2),
+                    [1, 128, 0].repeat(7),
+                    vec![10],
+                ]
+                .concat(),
+                vec![27, 50],
+            ]
+        );
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_bit_image_bands() {
+        let protocol = Protocol::new(Encoder::default());
+        let option = BitImageOption::new(None, None, BitImageSize::DoubleWidth)
+            .unwrap()
+            .band_height(Some(6))
+            .unwrap();
+
+        let cmds = protocol.bit_image("./resources/images/small.jpg", option).unwrap();
+        assert_eq!(
+            cmds,
+            vec![
+                [vec![29, 118, 48, 1, 2, 0, 6, 0], [1, 128].repeat(6)].concat(),
+                [
+                    vec![29, 118, 48, 1, 2, 0, 6, 0],
+                    vec![1, 128],
+                    vec![255; 4],
+                    [1, 128].repeat(3)
+                ]
+                .concat(),
+                [vec![29, 118, 48, 1, 2, 0, 4, 0], [1, 128].repeat(4)].concat(),
+            ]
+        );
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_graphics_command() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(
+            protocol.graphics_command(&[48, 50]).unwrap(),
+            vec![29, 40, 76, 2, 0, 48, 50]
+        );
+
+        let params = vec![0; 70_000];
+        let cmd = protocol.graphics_command(&params).unwrap();
+        assert_eq!(cmd[..7], [29, 56, 76, 0x70, 0x11, 0x01, 0x00]);
+        assert_eq!(cmd.len(), 70_007);
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_stored_graphic_define() {
+        let protocol = Protocol::new(Encoder::default());
+        let bit_image = BitImage::new(
+            "./resources/images/small.jpg",
+            BitImageOption::new(None, None, BitImageSize::default()).unwrap(),
+        )
+        .unwrap();
+        let key = GraphicKey::try_from("LG").unwrap();
+
+        let cmd = protocol
+            .stored_graphic_define(GraphicMemory::NV, key, &bit_image)
+            .unwrap();
+        assert_eq!(cmd[..16], [29, 40, 76, 43, 0, 48, 67, 48, 76, 71, 1, 16, 0, 16, 0, 49]);
+        assert_eq!(cmd[16..], bit_image.raster_data().unwrap());
+
+        let cmd = protocol
+            .stored_graphic_define(GraphicMemory::Download, key, &bit_image)
+            .unwrap();
+        assert_eq!(cmd[..16], [29, 40, 76, 43, 0, 48, 83, 48, 76, 71, 1, 16, 0, 16, 0, 49]);
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_stored_graphic_print() {
+        let protocol = Protocol::new(Encoder::default());
+        let key = GraphicKey::try_from("LG").unwrap();
+        assert_eq!(
+            protocol.stored_graphic_print(GraphicMemory::NV, key, GraphicSize::Normal, GraphicSize::Double),
+            vec![29, 40, 76, 6, 0, 48, 69, 76, 71, 1, 2]
+        );
+        assert_eq!(
+            protocol.stored_graphic_print(GraphicMemory::Download, key, GraphicSize::Normal, GraphicSize::Normal),
+            vec![29, 40, 76, 6, 0, 48, 85, 76, 71, 1, 1]
+        );
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_stored_graphics_management() {
+        let protocol = Protocol::new(Encoder::default());
+        let key = GraphicKey::try_from("LG").unwrap();
+        assert_eq!(
+            protocol.stored_graphic_delete(GraphicMemory::NV, key),
+            vec![29, 40, 76, 4, 0, 48, 66, 76, 71]
+        );
+        assert_eq!(
+            protocol.stored_graphic_delete(GraphicMemory::Download, key),
+            vec![29, 40, 76, 4, 0, 48, 82, 76, 71]
+        );
+        assert_eq!(
+            protocol.stored_graphics_delete_all(GraphicMemory::NV),
+            vec![29, 40, 76, 5, 0, 48, 65, 67, 76, 82]
+        );
+        assert_eq!(
+            protocol.stored_graphics_delete_all(GraphicMemory::Download),
+            vec![29, 40, 76, 5, 0, 48, 81, 67, 76, 82]
+        );
+        assert_eq!(
+            protocol.stored_graphics_key_codes(GraphicMemory::NV),
+            vec![29, 40, 76, 4, 0, 48, 64, 75, 67]
+        );
+        assert_eq!(
+            protocol.stored_graphics_key_codes(GraphicMemory::Download),
+            vec![29, 40, 76, 4, 0, 48, 80, 75, 67]
+        );
+        assert_eq!(
+            protocol.stored_graphics_remaining_capacity(GraphicMemory::NV),
+            vec![29, 40, 76, 2, 0, 48, 3]
+        );
+        assert_eq!(
+            protocol.stored_graphics_remaining_capacity(GraphicMemory::Download),
+            vec![29, 40, 76, 2, 0, 48, 4]
+        );
+        assert_eq!(protocol.nv_graphics_capacity(), vec![29, 40, 76, 2, 0, 48, 0]);
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_graphic_density() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(
+            protocol.graphic_density(GraphicDensity::Low),
+            vec![29, 40, 76, 4, 0, 48, 49, 50, 50]
+        );
+        assert_eq!(
+            protocol.graphic_density(GraphicDensity::High),
+            vec![29, 40, 76, 4, 0, 48, 49, 51, 51]
+        );
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_graphic_print() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(protocol.graphic_print(), vec![29, 40, 76, 2, 0, 48, 50]);
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_graphic_data() {
+        let protocol = Protocol::new(Encoder::default());
+        let path = "./resources/images/rust-logo-small.png";
+        let graphic = Graphic::new(path, None).unwrap();
+
+        // 200 x 200 dots: 10 parameters + 5_000 bytes
+        let cmds = protocol.graphic_data(path, GraphicOption::default()).unwrap();
+        assert_eq!(cmds.len(), 1);
+        assert_eq!(
+            cmds[0][..15],
+            [29, 40, 76, 0x92, 0x13, 48, 112, 48, 1, 1, 49, 200, 0, 200, 0]
+        );
+        assert_eq!(cmds[0][15..], graphic.data().unwrap());
+
+        let option = GraphicOption::new(
+            GraphicDensity::High,
+            GraphicTone::Monochrome,
+            GraphicColor::Color1,
+            GraphicSize::Double,
+            GraphicSize::Normal,
+            None,
+            None,
+        )
+        .second_color(GraphicColor::Color2);
+        let cmds = protocol.graphic_data(path, option).unwrap();
+        assert_eq!(cmds.len(), 2);
+        assert_eq!(
+            cmds[0][..15],
+            [29, 40, 76, 0x92, 0x13, 48, 112, 48, 2, 1, 49, 200, 0, 200, 0]
+        );
+        assert_eq!(
+            cmds[1][..15],
+            [29, 40, 76, 0x92, 0x13, 48, 112, 48, 2, 1, 50, 200, 0, 200, 0]
+        );
+
+        let option = GraphicOption {
+            tone: GraphicTone::Multiple,
+            max_width: Some(16),
+            ..Default::default()
+        };
+        let cmds = protocol.graphic_data(path, option).unwrap();
+        assert_eq!(
+            cmds.iter().map(|cmd| cmd[..15].to_vec()).collect::<Vec<_>>(),
+            (49..=52)
+                .map(|c| vec![29, 40, 76, 42, 0, 48, 112, 52, 1, 1, c, 16, 0, 16, 0])
+                .collect::<Vec<_>>()
+        );
+        assert!(cmds.iter().all(|cmd| cmd.len() == 15 + 32));
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_graphic_data_large() {
+        // 1_000 x 1_000 dots do not fit in GS ( L: 10 parameters + 125_000 bytes
+        let protocol = Protocol::new(Encoder::default());
+        let cmds = protocol
+            .graphic_data("./resources/images/rust-logo.png", GraphicOption::default())
+            .unwrap();
+        assert_eq!(
+            cmds[0][..17],
+            [29, 56, 76, 0x52, 0xE8, 0x01, 0, 48, 112, 48, 1, 1, 49, 0xE8, 0x03, 0xE8, 0x03]
+        );
+        assert_eq!(cmds[0].len(), 7 + 125_010);
+    }
 }
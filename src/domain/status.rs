@@ -4,6 +4,7 @@
 
 use crate::errors::PrinterError;
 use std::collections::HashMap;
+use std::fmt;
 
 /// Printer real-time status
 #[derive(Debug, Copy, Clone)]
@@ -92,74 +93,503 @@ impl RealTimeStatusResponse {
     }
 
     /// Parse the response
+    ///
+    /// The typed status structs ([`PrinterStatus`], [`OfflineCause`], etc.) and [`PrinterState`]
+    /// avoid looking up the keys of each request.
     pub fn parse(request: RealTimeStatusRequest, response: u8) -> Result<HashMap<Self, bool>, PrinterError> {
-        let binary = format!("{:08b}", response)
-            .chars()
-            .map(|c| c.to_digit(2).unwrap_or(0) as u8)
-            .rev()
-            .collect::<Vec<_>>();
-
-        if !Self::is_pattern_valid(&binary) {
-            return Err(PrinterError::InvalidResponse(format!(
-                "invalid response pattern: {:08b} (0xx1xx10 expected)",
-                response
-            )));
-        }
-
-        let mut result = HashMap::new();
-        match request {
+        let result = match request {
             RealTimeStatusRequest::Printer => {
-                result.insert(Self::DrawerKickOutConnectorPin3Low, binary[2] == 0);
-                result.insert(Self::Online, binary[3] == 0);
-                result.insert(Self::WaitingForOnlineRecovery, binary[5] == 1);
-                result.insert(Self::PaperFeedButtonPressed, binary[6] == 1);
+                let status = PrinterStatus::parse(response)?;
+                HashMap::from([
+                    (
+                        Self::DrawerKickOutConnectorPin3Low,
+                        status.drawer_kick_out_connector_pin3_low,
+                    ),
+                    (Self::Online, status.online),
+                    (Self::WaitingForOnlineRecovery, status.waiting_for_online_recovery),
+                    (Self::PaperFeedButtonPressed, status.paper_feed_button_pressed),
+                ])
             }
             RealTimeStatusRequest::OfflineCause => {
-                result.insert(Self::CoverClosed, binary[2] == 0);
-                result.insert(Self::PaperFedByPaperFeedButton, binary[3] == 1);
-                result.insert(Self::PrintingStopsDueToPaperEnd, binary[5] == 1);
-                result.insert(Self::ErrorOccurred, binary[6] == 1);
+                let status = OfflineCause::parse(response)?;
+                HashMap::from([
+                    (Self::CoverClosed, status.cover_closed),
+                    (Self::PaperFedByPaperFeedButton, status.paper_fed_by_paper_feed_button),
+                    (Self::PrintingStopsDueToPaperEnd, status.printing_stops_due_to_paper_end),
+                    (Self::ErrorOccurred, status.error_occurred),
+                ])
             }
             RealTimeStatusRequest::ErrorCause => {
-                result.insert(Self::RecoverableErrorOccurred, binary[2] == 1);
-                result.insert(Self::AutocutterErrorOccurred, binary[3] == 1);
-                result.insert(Self::UnrecoverableErrorOccurred, binary[5] == 1);
-                result.insert(Self::AutoRecoverableErrorOccurred, binary[6] == 1);
+                let status = ErrorCause::parse(response)?;
+                HashMap::from([
+                    (Self::RecoverableErrorOccurred, status.recoverable_error),
+                    (Self::AutocutterErrorOccurred, status.autocutter_error),
+                    (Self::UnrecoverableErrorOccurred, status.unrecoverable_error),
+                    (Self::AutoRecoverableErrorOccurred, status.auto_recoverable_error),
+                ])
             }
             RealTimeStatusRequest::RollPaperSensor => {
-                result.insert(
-                    Self::RollPaperNearEndSensorPaperAdequate,
-                    binary[2] == 0 && binary[3] == 0,
-                );
-                result.insert(Self::RollPaperEndSensorPaperPresent, binary[5] == 0 && binary[6] == 0);
+                let status = PaperSensorStatus::parse(response)?;
+                HashMap::from([
+                    (Self::RollPaperNearEndSensorPaperAdequate, status.paper_adequate),
+                    (Self::RollPaperEndSensorPaperPresent, status.paper_present),
+                ])
             }
-            RealTimeStatusRequest::InkA => {
-                result.insert(Self::InkNearEndDetected, binary[2] == 1);
-                result.insert(Self::InkEndDetected, binary[3] == 1);
-                result.insert(Self::InkCartridgeDetected, binary[5] == 0);
-                result.insert(Self::CleaningPerformed, binary[6] == 1);
-            }
-            RealTimeStatusRequest::InkB => {
-                result.insert(Self::InkNearEndDetected, binary[2] == 1);
-                result.insert(Self::InkEndDetected, binary[3] == 1);
-                result.insert(Self::InkCartridgeDetected, binary[5] == 0);
+            RealTimeStatusRequest::InkA | RealTimeStatusRequest::InkB => {
+                let status = InkStatus::parse(response)?;
+                let mut result = HashMap::from([
+                    (Self::InkNearEndDetected, status.near_end),
+                    (Self::InkEndDetected, status.end),
+                    (Self::InkCartridgeDetected, status.cartridge_detected),
+                ]);
+                if let RealTimeStatusRequest::InkA = request {
+                    result.insert(Self::CleaningPerformed, status.cleaning_performed);
+                }
+                result
             }
             RealTimeStatusRequest::Peeler => {
-                result.insert(Self::WaitingForLabelToBeRemoved, binary[2] == 1);
-                result.insert(Self::PaperPresentInLabelPeelingDetector, binary[5] == 0);
-            }
-            RealTimeStatusRequest::Interface => {
-                result.insert(Self::PrintingMultipleInterfacesEnabled, binary[2] == 1);
+                let status = PeelerStatus::parse(response)?;
+                HashMap::from([
+                    (Self::WaitingForLabelToBeRemoved, status.waiting_for_label_removal),
+                    (
+                        Self::PaperPresentInLabelPeelingDetector,
+                        status.paper_in_peeling_detector,
+                    ),
+                ])
             }
-            RealTimeStatusRequest::DMD => {
-                result.insert(Self::DMDTransmissionStatusReady, binary[2] == 0);
-            }
-        }
+            RealTimeStatusRequest::Interface => HashMap::from([(
+                Self::PrintingMultipleInterfacesEnabled,
+                InterfaceStatus::parse(response)?.multiple_interfaces_enabled,
+            )]),
+            RealTimeStatusRequest::DMD => HashMap::from([(
+                Self::DMDTransmissionStatusReady,
+                DMDStatus::parse(response)?.transmission_ready,
+            )]),
+        };
 
         Ok(result)
     }
 }
 
+/// Get the bits of a real-time status response, least significant bit first
+fn status_bits(response: u8) -> Result<[bool; 8], PrinterError> {
+    let bits: [u8; 8] = std::array::from_fn(|i| (response >> i) & 1);
+
+    if !RealTimeStatusResponse::is_pattern_valid(&bits) {
+        return Err(PrinterError::InvalidResponse(format!(
+            "invalid response pattern: {:08b} (0xx1xx10 expected)",
+            response
+        )));
+    }
+
+    Ok(bits.map(|bit| bit == 1))
+}
+
+/// Write the descriptions of the set flags separated by commas, or `none` if no flag is set
+fn write_flags(f: &mut fmt::Formatter<'_>, flags: &[(bool, &str)], none: &str) -> fmt::Result {
+    let flags = flags
+        .iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, description)| *description)
+        .collect::<Vec<_>>();
+
+    if flags.is_empty() {
+        write!(f, "{none}")
+    } else {
+        write!(f, "{}", flags.join(", "))
+    }
+}
+
+/// Printer status (`DLE EOT 1`)
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PrinterStatus {
+    pub drawer_kick_out_connector_pin3_low: bool,
+    pub online: bool,
+    pub waiting_for_online_recovery: bool,
+    pub paper_feed_button_pressed: bool,
+}
+
+impl PrinterStatus {
+    /// Parse the response
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let status = PrinterStatus::parse(0b0001_0010)?;
+    ///
+    /// assert!(status.online);
+    /// assert!(PrinterStatus::parse(0b0001_0011).is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn parse(response: u8) -> Result<Self, PrinterError> {
+        let bits = status_bits(response)?;
+        Ok(Self {
+            drawer_kick_out_connector_pin3_low: !bits[2],
+            online: !bits[3],
+            waiting_for_online_recovery: bits[5],
+            paper_feed_button_pressed: bits[6],
+        })
+    }
+}
+
+impl fmt::Display for PrinterStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", if self.online { "online" } else { "offline" })?;
+        if self.drawer_kick_out_connector_pin3_low {
+            write!(f, ", drawer kick-out connector pin 3 low")?;
+        }
+        if self.waiting_for_online_recovery {
+            write!(f, ", waiting for online recovery")?;
+        }
+        if self.paper_feed_button_pressed {
+            write!(f, ", paper feed button pressed")?;
+        }
+        Ok(())
+    }
+}
+
+/// Offline cause status (`DLE EOT 2`)
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct OfflineCause {
+    pub cover_closed: bool,
+    pub paper_fed_by_paper_feed_button: bool,
+    pub printing_stops_due_to_paper_end: bool,
+    pub error_occurred: bool,
+}
+
+impl OfflineCause {
+    /// Parse the response
+    pub fn parse(response: u8) -> Result<Self, PrinterError> {
+        let bits = status_bits(response)?;
+        Ok(Self {
+            cover_closed: !bits[2],
+            paper_fed_by_paper_feed_button: bits[3],
+            printing_stops_due_to_paper_end: bits[5],
+            error_occurred: bits[6],
+        })
+    }
+}
+
+impl fmt::Display for OfflineCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_flags(
+            f,
+            &[
+                (!self.cover_closed, "cover open"),
+                (
+                    self.paper_fed_by_paper_feed_button,
+                    "paper fed by the paper feed button",
+                ),
+                (self.printing_stops_due_to_paper_end, "printing stopped by paper end"),
+                (self.error_occurred, "error"),
+            ],
+            "none",
+        )
+    }
+}
+
+/// Error cause status (`DLE EOT 3`)
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ErrorCause {
+    pub recoverable_error: bool,
+    pub autocutter_error: bool,
+    pub unrecoverable_error: bool,
+    pub auto_recoverable_error: bool,
+}
+
+impl ErrorCause {
+    /// Parse the response
+    pub fn parse(response: u8) -> Result<Self, PrinterError> {
+        let bits = status_bits(response)?;
+        Ok(Self {
+            recoverable_error: bits[2],
+            autocutter_error: bits[3],
+            unrecoverable_error: bits[5],
+            auto_recoverable_error: bits[6],
+        })
+    }
+
+    /// Check if an error occurred
+    pub fn has_error(&self) -> bool {
+        self.recoverable_error || self.autocutter_error || self.unrecoverable_error || self.auto_recoverable_error
+    }
+}
+
+impl fmt::Display for ErrorCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_flags(
+            f,
+            &[
+                (self.recoverable_error, "recoverable error"),
+                (self.autocutter_error, "autocutter error"),
+                (self.unrecoverable_error, "unrecoverable error"),
+                (self.auto_recoverable_error, "auto-recoverable error"),
+            ],
+            "no error",
+        )
+    }
+}
+
+/// Roll paper sensor status (`DLE EOT 4`)
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PaperSensorStatus {
+    /// Roll paper near-end sensor: paper adequate
+    pub paper_adequate: bool,
+    /// Roll paper end sensor: paper present
+    pub paper_present: bool,
+}
+
+impl PaperSensorStatus {
+    /// Parse the response
+    pub fn parse(response: u8) -> Result<Self, PrinterError> {
+        let bits = status_bits(response)?;
+        Ok(Self {
+            paper_adequate: !bits[2] && !bits[3],
+            paper_present: !bits[5] && !bits[6],
+        })
+    }
+}
+
+impl fmt::Display for PaperSensorStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.paper_present, self.paper_adequate) {
+            (false, _) => write!(f, "paper end"),
+            (true, false) => write!(f, "paper near end"),
+            (true, true) => write!(f, "paper adequate"),
+        }
+    }
+}
+
+/// Ink status (`DLE EOT 7`), for ink A or ink B
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct InkStatus {
+    pub near_end: bool,
+    pub end: bool,
+    pub cartridge_detected: bool,
+    /// Only reported for ink A
+    pub cleaning_performed: bool,
+}
+
+impl InkStatus {
+    /// Parse the response
+    pub fn parse(response: u8) -> Result<Self, PrinterError> {
+        let bits = status_bits(response)?;
+        Ok(Self {
+            near_end: bits[2],
+            end: bits[3],
+            cartridge_detected: !bits[5],
+            cleaning_performed: bits[6],
+        })
+    }
+}
+
+impl fmt::Display for InkStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.cartridge_detected {
+            write!(f, "no cartridge")?;
+        } else if self.end {
+            write!(f, "ink end")?;
+        } else if self.near_end {
+            write!(f, "ink near end")?;
+        } else {
+            write!(f, "ink adequate")?;
+        }
+        if self.cleaning_performed {
+            write!(f, ", cleaning performed")?;
+        }
+        Ok(())
+    }
+}
+
+/// Peeler status (`DLE EOT 8`)
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PeelerStatus {
+    pub waiting_for_label_removal: bool,
+    pub paper_in_peeling_detector: bool,
+}
+
+impl PeelerStatus {
+    /// Parse the response
+    pub fn parse(response: u8) -> Result<Self, PrinterError> {
+        let bits = status_bits(response)?;
+        Ok(Self {
+            waiting_for_label_removal: bits[2],
+            paper_in_peeling_detector: !bits[5],
+        })
+    }
+}
+
+impl fmt::Display for PeelerStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_flags(
+            f,
+            &[
+                (self.waiting_for_label_removal, "waiting for label removal"),
+                (self.paper_in_peeling_detector, "paper in the label peeling detector"),
+            ],
+            "no label",
+        )
+    }
+}
+
+/// Interface status (`DLE EOT 18 1`)
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct InterfaceStatus {
+    pub multiple_interfaces_enabled: bool,
+}
+
+impl InterfaceStatus {
+    /// Parse the response
+    pub fn parse(response: u8) -> Result<Self, PrinterError> {
+        let bits = status_bits(response)?;
+        Ok(Self {
+            multiple_interfaces_enabled: bits[2],
+        })
+    }
+}
+
+impl fmt::Display for InterfaceStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.multiple_interfaces_enabled {
+            true => write!(f, "multiple interfaces enabled"),
+            false => write!(f, "single interface"),
+        }
+    }
+}
+
+/// DM-D (customer display) status (`DLE EOT 18 2`)
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct DMDStatus {
+    pub transmission_ready: bool,
+}
+
+impl DMDStatus {
+    /// Parse the response
+    pub fn parse(response: u8) -> Result<Self, PrinterError> {
+        let bits = status_bits(response)?;
+        Ok(Self {
+            transmission_ready: !bits[2],
+        })
+    }
+}
+
+impl fmt::Display for DMDStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.transmission_ready {
+            true => write!(f, "ready"),
+            false => write!(f, "busy"),
+        }
+    }
+}
+
+/// Printer state aggregated from several real-time status responses
+///
+/// Categories which were not requested are `None`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PrinterState {
+    pub printer: Option<PrinterStatus>,
+    pub offline_cause: Option<OfflineCause>,
+    pub error_cause: Option<ErrorCause>,
+    pub paper_sensor: Option<PaperSensorStatus>,
+    pub ink_a: Option<InkStatus>,
+    pub ink_b: Option<InkStatus>,
+    pub peeler: Option<PeelerStatus>,
+    pub interface: Option<InterfaceStatus>,
+    pub dmd: Option<DMDStatus>,
+}
+
+impl PrinterState {
+    /// Create a new `PrinterState` from responses
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let state = PrinterState::from_responses(&[
+    ///     (RealTimeStatusRequest::Printer, 0b0001_0110),
+    ///     (RealTimeStatusRequest::RollPaperSensor, 0b0001_1110),
+    /// ])?;
+    ///
+    /// assert!(state.is_online());
+    /// assert!(!state.is_paper_adequate());
+    /// assert_eq!(state.to_string(), "printer: online\npaper: paper near end");
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn from_responses(responses: &[(RealTimeStatusRequest, u8)]) -> Result<Self, PrinterError> {
+        let mut state = Self::default();
+        for &(request, response) in responses {
+            state.update(request, response)?;
+        }
+        Ok(state)
+    }
+
+    /// Update the state with a response
+    pub fn update(&mut self, request: RealTimeStatusRequest, response: u8) -> Result<(), PrinterError> {
+        match request {
+            RealTimeStatusRequest::Printer => self.printer = Some(PrinterStatus::parse(response)?),
+            RealTimeStatusRequest::OfflineCause => self.offline_cause = Some(OfflineCause::parse(response)?),
+            RealTimeStatusRequest::ErrorCause => self.error_cause = Some(ErrorCause::parse(response)?),
+            RealTimeStatusRequest::RollPaperSensor => self.paper_sensor = Some(PaperSensorStatus::parse(response)?),
+            RealTimeStatusRequest::InkA => self.ink_a = Some(InkStatus::parse(response)?),
+            RealTimeStatusRequest::InkB => self.ink_b = Some(InkStatus::parse(response)?),
+            RealTimeStatusRequest::Peeler => self.peeler = Some(PeelerStatus::parse(response)?),
+            RealTimeStatusRequest::Interface => self.interface = Some(InterfaceStatus::parse(response)?),
+            RealTimeStatusRequest::DMD => self.dmd = Some(DMDStatus::parse(response)?),
+        }
+        Ok(())
+    }
+
+    /// Check if the printer is online (`false` if the printer status is unknown)
+    pub fn is_online(&self) -> bool {
+        self.printer.is_some_and(|status| status.online)
+    }
+
+    /// Check if an error occurred, from the offline and error cause statuses
+    pub fn has_error(&self) -> bool {
+        self.offline_cause.is_some_and(|cause| cause.error_occurred)
+            || self.error_cause.is_some_and(|cause| cause.has_error())
+    }
+
+    /// Check if the roll paper is adequate (`false` if the roll paper sensor status is unknown)
+    pub fn is_paper_adequate(&self) -> bool {
+        self.paper_sensor
+            .is_some_and(|status| status.paper_present && status.paper_adequate)
+    }
+}
+
+impl fmt::Display for PrinterState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let categories: [(&str, Option<&dyn fmt::Display>); 9] = [
+            ("printer", self.printer.as_ref().map(|s| s as &dyn fmt::Display)),
+            (
+                "offline cause",
+                self.offline_cause.as_ref().map(|s| s as &dyn fmt::Display),
+            ),
+            ("error cause", self.error_cause.as_ref().map(|s| s as &dyn fmt::Display)),
+            ("paper", self.paper_sensor.as_ref().map(|s| s as &dyn fmt::Display)),
+            ("ink A", self.ink_a.as_ref().map(|s| s as &dyn fmt::Display)),
+            ("ink B", self.ink_b.as_ref().map(|s| s as &dyn fmt::Display)),
+            ("peeler", self.peeler.as_ref().map(|s| s as &dyn fmt::Display)),
+            ("interface", self.interface.as_ref().map(|s| s as &dyn fmt::Display)),
+            ("DM-D", self.dmd.as_ref().map(|s| s as &dyn fmt::Display)),
+        ];
+
+        let mut first = true;
+        for (name, status) in categories {
+            if let Some(status) = status {
+                if !first {
+                    writeln!(f)?;
+                }
+                write!(f, "{name}: {status}")?;
+                first = false;
+            }
+        }
+        if first {
+            write!(f, "unknown")?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -207,6 +637,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_parse_real_time_status_response() {
         let response = RealTimeStatusResponse::parse(RealTimeStatusRequest::Printer, 0b00011010).unwrap();
         assert_eq!(response[&RealTimeStatusResponse::DrawerKickOutConnectorPin3Low], true);
@@ -260,4 +691,92 @@ mod tests {
         let response = RealTimeStatusResponse::parse(RealTimeStatusRequest::DMD, 0b00010010).unwrap();
         assert_eq!(response[&RealTimeStatusResponse::DMDTransmissionStatusReady], true);
     }
+
+    #[test]
+    fn test_parse_real_time_status_response_typed() {
+        // The keys are read from the typed statuses
+        let response = RealTimeStatusResponse::parse(RealTimeStatusRequest::InkB, 0b01011010).unwrap();
+        assert_eq!(response.len(), 3);
+        assert!(!response.contains_key(&RealTimeStatusResponse::CleaningPerformed));
+
+        let response = RealTimeStatusResponse::parse(RealTimeStatusRequest::RollPaperSensor, 0b01110010).unwrap();
+        assert!(response[&RealTimeStatusResponse::RollPaperNearEndSensorPaperAdequate]);
+        assert!(!response[&RealTimeStatusResponse::RollPaperEndSensorPaperPresent]);
+
+        assert!(RealTimeStatusResponse::parse(RealTimeStatusRequest::Printer, 0b10010010).is_err());
+        assert!(RealTimeStatusResponse::parse(RealTimeStatusRequest::DMD, 0b00000010).is_err());
+    }
+
+    #[test]
+    fn test_parse_typed_status() {
+        assert_eq!(
+            PrinterStatus::parse(0b0001_1010).unwrap(),
+            PrinterStatus {
+                drawer_kick_out_connector_pin3_low: true,
+                online: false,
+                waiting_for_online_recovery: false,
+                paper_feed_button_pressed: false,
+            }
+        );
+        assert_eq!(
+            OfflineCause::parse(0b0101_1110).unwrap(),
+            OfflineCause {
+                cover_closed: false,
+                paper_fed_by_paper_feed_button: true,
+                printing_stops_due_to_paper_end: false,
+                error_occurred: true,
+            }
+        );
+        assert!(ErrorCause::parse(0b0001_1010).unwrap().autocutter_error);
+        assert!(!ErrorCause::parse(0b0001_0010).unwrap().has_error());
+        assert_eq!(
+            PaperSensorStatus::parse(0b0111_0010).unwrap(),
+            PaperSensorStatus {
+                paper_adequate: true,
+                paper_present: false,
+            }
+        );
+        assert_eq!(
+            InkStatus::parse(0b0101_1010).unwrap(),
+            InkStatus {
+                near_end: false,
+                end: true,
+                cartridge_detected: true,
+                cleaning_performed: true,
+            }
+        );
+        assert!(PeelerStatus::parse(0b0001_0010).unwrap().paper_in_peeling_detector);
+        assert!(InterfaceStatus::parse(0b0001_0110).unwrap().multiple_interfaces_enabled);
+        assert!(DMDStatus::parse(0b0001_0010).unwrap().transmission_ready);
+
+        assert!(PrinterStatus::parse(0b1001_0010).is_err());
+        assert!(InkStatus::parse(0b0000_0010).is_err());
+    }
+
+    #[test]
+    fn test_printer_state() {
+        let state = PrinterState::from_responses(&[
+            (RealTimeStatusRequest::Printer, 0b0001_1010),
+            (RealTimeStatusRequest::OfflineCause, 0b0101_0110),
+            (RealTimeStatusRequest::ErrorCause, 0b0001_1010),
+            (RealTimeStatusRequest::RollPaperSensor, 0b0111_0010),
+            (RealTimeStatusRequest::InkB, 0b0011_0010),
+        ])
+        .unwrap();
+        assert!(!state.is_online());
+        assert!(state.has_error());
+        assert!(!state.is_paper_adequate());
+        assert_eq!(state.ink_a, None);
+        assert_eq!(
+            state.to_string(),
+            "printer: offline, drawer kick-out connector pin 3 low\n\
+             offline cause: cover open, error\n\
+             error cause: autocutter error\n\
+             paper: paper end\n\
+             ink B: no cartridge"
+        );
+
+        assert_eq!(PrinterState::default().to_string(), "unknown");
+        assert!(PrinterState::from_responses(&[(RealTimeStatusRequest::Printer, 0)]).is_err());
+    }
 }
@@ -0,0 +1,204 @@
+//! NV graphics and download graphics
+//!
+//! Graphics are stored in the printer under a key code and printed later without sending the data again.
+//!
+//! [Epson Documentation](https://download4.epson.biz/sec_pubs/pos/reference_en/escpos/gs_lparen_cl.html)
+
+#![cfg(feature = "graphics")]
+
+use super::asb::PrinterResponse;
+use crate::errors::{PrinterError, Result};
+use std::fmt;
+
+/// Header of the responses sent by the printer
+const STORED_GRAPHICS_RESPONSE_HEADER: u8 = 0x37;
+/// Status of a key code list response when another block follows
+const STORED_GRAPHICS_RESPONSE_NEXT_BLOCK: u8 = 0x41;
+/// Status of the last key code list response
+const STORED_GRAPHICS_RESPONSE_LAST_BLOCK: u8 = 0x40;
+
+/// Memory used to store graphics
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphicMemory {
+    /// Non-volatile memory, kept after power off
+    NV,
+    /// Download memory (RAM), lost after power off or `ESC @`
+    Download,
+}
+
+impl fmt::Display for GraphicMemory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphicMemory::NV => write!(f, "NV graphics"),
+            GraphicMemory::Download => write!(f, "download graphics"),
+        }
+    }
+}
+
+/// Key code of a stored graphic (two characters in the 32 - 126 range)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GraphicKey {
+    kc1: u8,
+    kc2: u8,
+}
+
+impl GraphicKey {
+    /// Create a new `GraphicKey`
+    ///
+    /// ```
+    /// use escpos::utils::GraphicKey;
+    ///
+    /// assert!(GraphicKey::new(b'L', b'G').is_ok());
+    /// assert!(GraphicKey::new(b'L', 0).is_err());
+    /// ```
+    pub fn new(kc1: u8, kc2: u8) -> Result<Self> {
+        if !(32..=126).contains(&kc1) || !(32..=126).contains(&kc2) {
+            return Err(PrinterError::Input(format!(
+                "invalid graphic key code (32-126): ({kc1}, {kc2})"
+            )));
+        }
+
+        Ok(Self { kc1, kc2 })
+    }
+
+    /// Get key codes (kc1, kc2)
+    pub fn codes(&self) -> (u8, u8) {
+        (self.kc1, self.kc2)
+    }
+}
+
+impl TryFrom<&str> for GraphicKey {
+    type Error = PrinterError;
+
+    fn try_from(value: &str) -> Result<Self> {
+        match value.as_bytes() {
+            [kc1, kc2] => Self::new(*kc1, *kc2),
+            _ => Err(PrinterError::Input(format!(
+                "graphic key must have 2 characters: {value}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for GraphicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.kc1 as char, self.kc2 as char)
+    }
+}
+
+/// Accept a block response starting with the graphics header, other responses are returned
+pub(crate) fn graphics_response(response: PrinterResponse) -> std::result::Result<Vec<u8>, PrinterResponse> {
+    match response {
+        PrinterResponse::Block(data) if data.first() == Some(&STORED_GRAPHICS_RESPONSE_HEADER) => Ok(data),
+        response => Err(response),
+    }
+}
+
+/// Check the `[header, identifier, data..., NUL]` framing and return the data
+fn response_data(response: &[u8]) -> Result<&[u8]> {
+    match response {
+        [STORED_GRAPHICS_RESPONSE_HEADER, _, data @ .., 0] => Ok(data),
+        _ => Err(PrinterError::InvalidResponse(format!(
+            "invalid graphics response: {response:02X?}"
+        ))),
+    }
+}
+
+/// Parse a capacity response (number of bytes)
+pub(crate) fn parse_capacity(response: &[u8]) -> Result<u32> {
+    let data = response_data(response)?;
+    std::str::from_utf8(data)
+        .ok()
+        .and_then(|capacity| capacity.parse().ok())
+        .ok_or_else(|| PrinterError::InvalidResponse(format!("invalid graphics capacity: {data:02X?}")))
+}
+
+/// Parse a key code list response block
+///
+/// Returns the keys and `true` if another block follows.
+pub(crate) fn parse_key_codes(response: &[u8]) -> Result<(Vec<GraphicKey>, bool)> {
+    let (next, keys) = match response_data(response)? {
+        [STORED_GRAPHICS_RESPONSE_NEXT_BLOCK, keys @ ..] => (true, keys),
+        [STORED_GRAPHICS_RESPONSE_LAST_BLOCK, keys @ ..] => (false, keys),
+        data => {
+            return Err(PrinterError::InvalidResponse(format!(
+                "invalid graphics key code list: {data:02X?}"
+            )))
+        }
+    };
+
+    if keys.len() % 2 != 0 {
+        return Err(PrinterError::InvalidResponse(format!(
+            "invalid graphics key code list: {keys:02X?}"
+        )));
+    }
+
+    let keys = keys
+        .chunks(2)
+        .map(|kc| GraphicKey::new(kc[0], kc[1]).map_err(|e| PrinterError::InvalidResponse(e.to_string())))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((keys, next))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graphic_key() {
+        assert_eq!(GraphicKey::new(32, 126).unwrap().codes(), (32, 126));
+        assert!(GraphicKey::new(31, 65).is_err());
+        assert!(GraphicKey::new(65, 127).is_err());
+
+        let key: GraphicKey = "LG".try_into().unwrap();
+        assert_eq!(key.codes(), (76, 71));
+        assert_eq!(key.to_string(), "LG");
+        assert!(GraphicKey::try_from("L").is_err());
+        assert!(GraphicKey::try_from("LGO").is_err());
+    }
+
+    #[test]
+    fn test_graphics_response() {
+        let block = PrinterResponse::Block(vec![0x37, 0x31, b'0', 0]);
+        assert_eq!(graphics_response(block), Ok(vec![0x37, 0x31, b'0', 0]));
+        let block = PrinterResponse::Block(b"_TM\0".to_vec());
+        assert_eq!(graphics_response(block.clone()), Err(block));
+        assert_eq!(
+            graphics_response(PrinterResponse::Byte(0x37)),
+            Err(PrinterResponse::Byte(0x37))
+        );
+    }
+
+    #[test]
+    fn test_parse_capacity() {
+        assert_eq!(parse_capacity(&[0x37, 0x31, b'2', b'5', b'6', 0]).unwrap(), 256);
+        assert_eq!(parse_capacity(&[0x37, 0x30, b'0', 0]).unwrap(), 0);
+        assert!(parse_capacity(&[0x37, 0x30, 0]).is_err());
+        assert!(parse_capacity(&[0x37, 0x30, b'1', b'a', 0]).is_err());
+        assert!(parse_capacity(&[0x36, 0x30, b'1', 0]).is_err());
+        assert!(parse_capacity(&[0x37, 0x30, b'1']).is_err());
+    }
+
+    #[test]
+    fn test_parse_key_codes() {
+        assert_eq!(
+            parse_key_codes(&[0x37, 0x72, 0x40, b'L', b'G', b'A', b'1', 0]).unwrap(),
+            (
+                vec![
+                    GraphicKey::new(b'L', b'G').unwrap(),
+                    GraphicKey::new(b'A', b'1').unwrap()
+                ],
+                false
+            )
+        );
+        assert_eq!(
+            parse_key_codes(&[0x37, 0x72, 0x41, b'L', b'G', 0]).unwrap(),
+            (vec![GraphicKey::new(b'L', b'G').unwrap()], true)
+        );
+        assert_eq!(parse_key_codes(&[0x37, 0x72, 0x40, 0]).unwrap(), (vec![], false));
+        assert!(parse_key_codes(&[0x37, 0x72, 0x42, 0]).is_err());
+        assert!(parse_key_codes(&[0x37, 0x72, 0x40, b'L', 0]).is_err());
+        assert!(parse_key_codes(&[0x37, 0x72, 0]).is_err());
+    }
+}
@@ -0,0 +1,697 @@
+//! Text rendered to image with TrueType and OpenType fonts
+//!
+//! Lines are shaped with [rustybuzz](https://github.com/harfbuzz/rustybuzz) (ligatures, combining marks,
+//! reordering and conjuncts of complex scripts such as Thai or Devanagari). Characters missing from the font
+//! are drawn with the first [fallback font](TextFont::fallback) containing them, e.g. an emoji font.
+//! Color glyphs (`COLR` layers, `CBDT` and `sbix` bitmaps) are converted to grayscale.
+//!
+//! Runs of right-to-left text are shaped right to left, but lines mixing both directions are not reordered.
+
+#![cfg(feature = "ttf")]
+
+use super::types::JustifyMode;
+use crate::errors::{PrinterError, Result};
+use ab_glyph::{Font, FontRef, GlyphId, PxScale, ScaleFont};
+use image::{imageops, GrayImage, ImageFormat, Luma};
+use rustybuzz::ttf_parser::{self, colr, RasterImageFormat, RgbaColor};
+use rustybuzz::{Face, UnicodeBuffer};
+use std::fmt;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Gray level used for the gradients of color glyphs
+const COLOR_GLYPH_GRADIENT_LUMA: f32 = 128.0;
+
+/// TrueType or OpenType font, with its fallback fonts
+///
+/// Fonts are loaded at runtime, none is embedded in the library.
+pub struct TextFont {
+    fonts: Vec<Vec<u8>>,
+}
+
+impl fmt::Debug for TextFont {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TextFont").field("fonts", &self.fonts.len()).finish()
+    }
+}
+
+impl TextFont {
+    /// Load a font from a file
+    ///
+    /// ```
+    /// use escpos::utils::TextFont;
+    ///
+    /// assert!(TextFont::from_path("./resources/fonts/DejaVuSansMono.ttf").is_ok());
+    /// assert!(TextFont::from_path("./resources/images/small.jpg").is_err());
+    /// ```
+    pub fn from_path(path: &str) -> Result<Self> {
+        let data = std::fs::read(path).map_err(|e| PrinterError::Input(format!("cannot read font {path}: {e}")))?;
+        Self::from_bytes(data)
+    }
+
+    /// Load a font from bytes
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        FontRef::try_from_slice(&data).map_err(|e| PrinterError::Input(format!("invalid font: {e}")))?;
+        Face::from_slice(&data, 0).ok_or_else(|| PrinterError::Input("invalid font: cannot be shaped".to_owned()))?;
+        Ok(Self { fonts: vec![data] })
+    }
+
+    /// Add a fallback font (and its own fallback fonts), used for the characters missing from the previous fonts
+    ///
+    /// ```no_run
+    /// use escpos::utils::TextFont;
+    ///
+    /// let font = TextFont::from_path("./resources/fonts/DejaVuSansMono.ttf")?
+    ///     .fallback(TextFont::from_path("/usr/share/fonts/truetype/noto/NotoColorEmoji.ttf")?);
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn fallback(mut self, font: TextFont) -> Self {
+        self.fonts.extend(font.fonts);
+        self
+    }
+
+    /// Parse the fonts for a size
+    fn load(&self, size: f32) -> Result<Vec<LoadedFont<'_>>> {
+        self.fonts.iter().map(|data| LoadedFont::new(data, size)).collect()
+    }
+}
+
+/// Font parsed for rendering
+struct LoadedFont<'a> {
+    /// Glyph outlines
+    outlines: FontRef<'a>,
+    /// Shaping and color glyphs
+    face: Face<'a>,
+    scale: PxScale,
+    /// Dots per font unit
+    factor: f32,
+}
+
+impl<'a> LoadedFont<'a> {
+    fn new(data: &'a [u8], size: f32) -> Result<Self> {
+        let outlines = FontRef::try_from_slice(data).map_err(|e| PrinterError::Input(format!("invalid font: {e}")))?;
+        let face = Face::from_slice(data, 0).ok_or_else(|| PrinterError::Input("invalid font".to_owned()))?;
+        let scale = PxScale::from(size);
+        let factor = outlines.as_scaled(scale).h_scale_factor();
+
+        Ok(Self {
+            outlines,
+            face,
+            scale,
+            factor,
+        })
+    }
+
+    /// Check if the font has a glyph for a character
+    fn has_glyph(&self, c: char) -> bool {
+        self.face.glyph_index(c).is_some()
+    }
+}
+
+/// Text image option
+#[derive(Debug, Clone, Copy)]
+pub struct TextImageOption {
+    /// Font size in dots
+    size: f32,
+    /// Image width in dots (printer paper width if not set)
+    width: Option<u32>,
+    /// Line alignment
+    alignment: JustifyMode,
+    /// Line height factor
+    line_height: f32,
+}
+
+impl Default for TextImageOption {
+    fn default() -> Self {
+        Self {
+            size: 24.0,
+            width: None,
+            alignment: JustifyMode::LEFT,
+            line_height: 1.0,
+        }
+    }
+}
+
+impl TextImageOption {
+    /// Create a new `TextImageOption`
+    ///
+    /// `size` is the font size in dots, lines are wrapped to `width` dots
+    /// (the printer paper width if `None`).
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let option = TextImageOption::new(32.0, Some(384), JustifyMode::CENTER)?;
+    ///
+    /// assert_eq!(option.width(), Some(384));
+    /// assert!(TextImageOption::new(0.0, None, JustifyMode::LEFT).is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn new(size: f32, width: Option<u32>, alignment: JustifyMode) -> Result<Self> {
+        if !size.is_finite() || size <= 0.0 {
+            return Err(PrinterError::Input(format!("invalid text image font size: {size}")));
+        }
+        if width == Some(0) {
+            return Err(PrinterError::Input(
+                "text image width must be greater than 0".to_owned(),
+            ));
+        }
+
+        Ok(Self {
+            size,
+            width,
+            alignment,
+            line_height: 1.0,
+        })
+    }
+
+    /// Set the line height factor (1.0 by default, the font line height)
+    pub fn line_height(mut self, line_height: f32) -> Result<Self> {
+        if !line_height.is_finite() || line_height <= 0.0 {
+            return Err(PrinterError::Input(format!(
+                "invalid text image line height: {line_height}"
+            )));
+        }
+        self.line_height = line_height;
+        Ok(self)
+    }
+
+    /// Get font size in dots
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+
+    /// Get width in dots
+    pub fn width(&self) -> Option<u32> {
+        self.width
+    }
+
+    /// Get alignment
+    pub fn alignment(&self) -> JustifyMode {
+        self.alignment
+    }
+
+    /// Set width in dots if not set
+    pub(crate) fn default_width(mut self, width: u32) -> Self {
+        self.width.get_or_insert(width);
+        self
+    }
+}
+
+/// Text rendered to a grayscale image
+///
+/// Lines are wrapped on spaces, words larger than the width are split between grapheme clusters.
+///
+/// ```
+/// use escpos::utils::*;
+///
+/// let font = TextFont::from_path("./resources/fonts/DejaVuSansMono.ttf")?;
+/// let option = TextImageOption::new(24.0, Some(200), JustifyMode::LEFT)?;
+/// let image = TextImage::new(&font, "Hello world!", option)?;
+///
+/// assert_eq!(image.image().width(), 200);
+/// # Ok::<(), escpos::errors::PrinterError>(())
+/// ```
+#[derive(Debug)]
+pub struct TextImage {
+    image: GrayImage,
+    lines: Vec<String>,
+}
+
+impl TextImage {
+    /// Render text with a font
+    pub fn new(font: &TextFont, text: &str, option: TextImageOption) -> Result<Self> {
+        let width = option
+            .width
+            .ok_or_else(|| PrinterError::Input("text image width is not set".to_owned()))?;
+        if text.trim().is_empty() {
+            return Err(PrinterError::Input("text image text is empty".to_owned()));
+        }
+
+        let fonts = font.load(option.size)?;
+        let primary = fonts[0].outlines.as_scaled(fonts[0].scale);
+        let lines = wrap(&fonts, text, width as f32);
+        let line_height = (primary.height() + primary.line_gap()) * option.line_height;
+        let height = (line_height * lines.len() as f32).ceil() as u32;
+
+        let mut image = GrayImage::from_pixel(width, height.max(1), Luma([255]));
+        for (index, line) in lines.iter().enumerate() {
+            let (glyphs, line_width) = shape(&fonts, line);
+            let x = match option.alignment {
+                JustifyMode::LEFT => 0.0,
+                JustifyMode::CENTER => ((width as f32 - line_width) / 2.0).max(0.0),
+                JustifyMode::RIGHT => (width as f32 - line_width).max(0.0),
+            };
+            let baseline = line_height * index as f32 + primary.ascent();
+            for glyph in glyphs {
+                draw_glyph(
+                    &mut image,
+                    &fonts[glyph.font],
+                    glyph.id,
+                    x + glyph.x,
+                    baseline - glyph.y,
+                );
+            }
+        }
+
+        Ok(Self { image, lines })
+    }
+
+    /// Get image (black text on white background)
+    pub fn image(&self) -> &GrayImage {
+        &self.image
+    }
+
+    /// Get wrapped lines
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Get image
+    pub fn into_image(self) -> GrayImage {
+        self.image
+    }
+}
+
+/// Shaped glyph, positioned from the start of the line
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ShapedGlyph {
+    /// Index of the font
+    font: usize,
+    id: GlyphId,
+    /// Horizontal position in dots
+    x: f32,
+    /// Vertical offset from the baseline in dots (upward)
+    y: f32,
+}
+
+/// Check if a character is invisible and does not need a glyph (joiners, variation selectors, tags, etc.)
+fn is_default_ignorable(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}' | '\u{034F}' | '\u{200B}'..='\u{200F}' | '\u{2060}'..='\u{206F}' | '\u{FE00}'..='\u{FE0F}' | '\u{E0000}'..='\u{E0FFF}'
+    )
+}
+
+/// Select the font of a grapheme cluster: the first font with all its glyphs,
+/// else the first font with its first character, else the primary font
+fn select_font(grapheme: &str, fonts: usize, has_glyph: impl Fn(usize, char) -> bool) -> usize {
+    let mut visible = grapheme.chars().filter(|&c| !is_default_ignorable(c)).peekable();
+    let Some(&first) = visible.peek() else {
+        return 0;
+    };
+
+    (0..fonts)
+        .find(|&font| {
+            grapheme
+                .chars()
+                .filter(|&c| !is_default_ignorable(c))
+                .all(|c| has_glyph(font, c))
+        })
+        .or_else(|| (0..fonts).find(|&font| has_glyph(font, first)))
+        .unwrap_or(0)
+}
+
+/// Split text in runs of grapheme clusters drawn with the same font
+fn font_runs(text: &str, fonts: usize, has_glyph: impl Fn(usize, char) -> bool) -> Vec<(usize, &str)> {
+    let mut runs: Vec<(usize, std::ops::Range<usize>)> = Vec::new();
+    for (start, grapheme) in text.grapheme_indices(true) {
+        let font = select_font(grapheme, fonts, &has_glyph);
+        let end = start + grapheme.len();
+        match runs.last_mut() {
+            Some((run_font, range)) if *run_font == font => range.end = end,
+            _ => runs.push((font, start..end)),
+        }
+    }
+
+    runs.into_iter().map(|(font, range)| (font, &text[range])).collect()
+}
+
+/// Shape a line, returns the glyphs and the line width in dots
+fn shape(fonts: &[LoadedFont], text: &str) -> (Vec<ShapedGlyph>, f32) {
+    let mut glyphs = Vec::new();
+    let mut caret = 0.0;
+
+    for (index, run) in font_runs(text, fonts.len(), |font, c| fonts[font].has_glyph(c)) {
+        let font = &fonts[index];
+        let mut buffer = UnicodeBuffer::new();
+        buffer.push_str(run);
+        let shaped = rustybuzz::shape(&font.face, &[], buffer);
+
+        for (info, position) in shaped.glyph_infos().iter().zip(shaped.glyph_positions()) {
+            glyphs.push(ShapedGlyph {
+                font: index,
+                id: GlyphId(info.glyph_id as u16),
+                x: caret + position.x_offset as f32 * font.factor,
+                y: position.y_offset as f32 * font.factor,
+            });
+            caret += position.x_advance as f32 * font.factor;
+        }
+    }
+
+    (glyphs, caret)
+}
+
+/// Width of a text in dots
+fn measure(fonts: &[LoadedFont], text: &str) -> f32 {
+    shape(fonts, text).1
+}
+
+/// Wrap text to a width, on spaces when possible
+fn wrap(fonts: &[LoadedFont], text: &str, width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for paragraph in text.lines() {
+        let mut line = String::new();
+
+        for word in paragraph.split(' ') {
+            let candidate = if line.is_empty() {
+                word.to_owned()
+            } else {
+                format!("{line} {word}")
+            };
+
+            if measure(fonts, &candidate) <= width {
+                line = candidate;
+                continue;
+            }
+
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+
+            // Split words larger than the width, without splitting grapheme clusters
+            for grapheme in word.graphemes(true) {
+                let previous = line.len();
+                line.push_str(grapheme);
+                if measure(fonts, &line) > width && previous > 0 {
+                    line.truncate(previous);
+                    lines.push(std::mem::replace(&mut line, grapheme.to_owned()));
+                }
+            }
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Draw a glyph: outline, color layers or bitmap
+fn draw_glyph(image: &mut GrayImage, font: &LoadedFont, id: GlyphId, x: f32, baseline: f32) {
+    let glyph_id = ttf_parser::GlyphId(id.0);
+    if font.face.is_color_glyph(glyph_id) {
+        let mut painter = ColorGlyphPainter {
+            image,
+            font,
+            x,
+            baseline,
+            glyph: None,
+        };
+        let foreground = RgbaColor::new(0, 0, 0, 255);
+        if font
+            .face
+            .paint_color_glyph(glyph_id, 0, foreground, &mut painter)
+            .is_some()
+        {
+            return;
+        }
+    }
+
+    // The coverage of each pixel is used as darkness
+    let drawn = draw_outline(image, font, id, x, baseline, |luma, coverage| {
+        luma.min(255 - (coverage.clamp(0.0, 1.0) * 255.0) as u8)
+    });
+    if !drawn {
+        draw_bitmap(image, font, glyph_id, x, baseline);
+    }
+}
+
+/// Draw the outline of a glyph, `blend` computes the new luminance of a pixel from its coverage
+///
+/// Returns `false` if the glyph has no outline.
+fn draw_outline(
+    image: &mut GrayImage,
+    font: &LoadedFont,
+    id: GlyphId,
+    x: f32,
+    baseline: f32,
+    blend: impl Fn(u8, f32) -> u8,
+) -> bool {
+    let glyph = id.with_scale_and_position(font.scale, ab_glyph::point(x, baseline));
+    let Some(outlined) = font.outlines.outline_glyph(glyph) else {
+        return false;
+    };
+
+    let bounds = outlined.px_bounds();
+    outlined.draw(|gx, gy, coverage| {
+        let (px, py) = (bounds.min.x as i32 + gx as i32, bounds.min.y as i32 + gy as i32);
+        if px < 0 || py < 0 || px as u32 >= image.width() || py as u32 >= image.height() {
+            return;
+        }
+
+        let pixel = image.get_pixel_mut(px as u32, py as u32);
+        pixel.0[0] = blend(pixel.0[0], coverage);
+    });
+
+    true
+}
+
+/// Draw a PNG bitmap glyph (`CBDT` or `sbix`), scaled to the font size
+fn draw_bitmap(image: &mut GrayImage, font: &LoadedFont, id: ttf_parser::GlyphId, x: f32, baseline: f32) {
+    let pixels_per_em = font.face.units_per_em() as f32 * font.factor;
+    let Some(raster) = font.face.glyph_raster_image(id, pixels_per_em.round() as u16) else {
+        return;
+    };
+    if raster.format != RasterImageFormat::PNG || raster.pixels_per_em == 0 {
+        return;
+    }
+    let Ok(bitmap) = image::load_from_memory_with_format(raster.data, ImageFormat::Png) else {
+        return;
+    };
+
+    let ratio = pixels_per_em / f32::from(raster.pixels_per_em);
+    let (width, height) = (
+        (bitmap.width() as f32 * ratio).round().max(1.0) as u32,
+        (bitmap.height() as f32 * ratio).round().max(1.0) as u32,
+    );
+    let bitmap = imageops::resize(&bitmap.to_rgba8(), width, height, imageops::Triangle);
+
+    // The offsets are the bottom left corner of the bitmap from the glyph origin
+    let left = (x + f32::from(raster.x) * ratio).round() as i64;
+    let top = (baseline - f32::from(raster.y) * ratio).round() as i64 - i64::from(height);
+    for (bx, by, pixel) in bitmap.enumerate_pixels() {
+        let (px, py) = (left + i64::from(bx), top + i64::from(by));
+        if px < 0 || py < 0 || px >= i64::from(image.width()) || py >= i64::from(image.height()) {
+            continue;
+        }
+
+        let [red, green, blue, alpha] = pixel.0;
+        let target = image.get_pixel_mut(px as u32, py as u32);
+        target.0[0] = blend_color(target.0[0], luminance(red, green, blue), f32::from(alpha) / 255.0);
+    }
+}
+
+/// Luminance of a color
+fn luminance(red: u8, green: u8, blue: u8) -> f32 {
+    0.299 * f32::from(red) + 0.587 * f32::from(green) + 0.114 * f32::from(blue)
+}
+
+/// Draw a color over a pixel with an opacity
+fn blend_color(luma: u8, color: f32, opacity: f32) -> u8 {
+    let opacity = opacity.clamp(0.0, 1.0);
+    (f32::from(luma) * (1.0 - opacity) + color * opacity).round() as u8
+}
+
+/// Draw the layers of a `COLR` glyph in grayscale
+///
+/// Gradients are drawn with a mid gray and transforms are ignored.
+struct ColorGlyphPainter<'a, 'b> {
+    image: &'b mut GrayImage,
+    font: &'b LoadedFont<'a>,
+    x: f32,
+    baseline: f32,
+    /// Glyph of the current layer
+    glyph: Option<ttf_parser::GlyphId>,
+}
+
+impl<'a> colr::Painter<'a> for ColorGlyphPainter<'a, '_> {
+    fn outline_glyph(&mut self, glyph_id: ttf_parser::GlyphId) {
+        self.glyph = Some(glyph_id);
+    }
+
+    fn paint(&mut self, paint: colr::Paint<'a>) {
+        let Some(glyph) = self.glyph else {
+            return;
+        };
+        let (color, opacity) = match paint {
+            colr::Paint::Solid(color) => (
+                luminance(color.red, color.green, color.blue),
+                f32::from(color.alpha) / 255.0,
+            ),
+            _ => (COLOR_GLYPH_GRADIENT_LUMA, 1.0),
+        };
+
+        draw_outline(
+            self.image,
+            self.font,
+            GlyphId(glyph.0),
+            self.x,
+            self.baseline,
+            |luma, coverage| blend_color(luma, color, coverage * opacity),
+        );
+    }
+
+    fn push_clip(&mut self) {}
+
+    fn push_clip_box(&mut self, _clipbox: colr::ClipBox) {}
+
+    fn pop_clip(&mut self) {}
+
+    fn push_layer(&mut self, _mode: colr::CompositeMode) {}
+
+    fn pop_layer(&mut self) {}
+
+    fn push_transform(&mut self, _transform: ttf_parser::Transform) {}
+
+    fn pop_transform(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FONT: &str = "./resources/fonts/DejaVuSansMono.ttf";
+
+    /// Columns containing at least one dark pixel
+    fn dark_columns(image: &GrayImage) -> Vec<u32> {
+        (0..image.width())
+            .filter(|&x| (0..image.height()).any(|y| image.get_pixel(x, y).0[0] < 128))
+            .collect()
+    }
+
+    #[test]
+    fn test_text_image_option() {
+        assert!(TextImageOption::new(24.0, None, JustifyMode::LEFT).is_ok());
+        assert!(TextImageOption::new(-1.0, None, JustifyMode::LEFT).is_err());
+        assert!(TextImageOption::new(f32::NAN, None, JustifyMode::LEFT).is_err());
+        assert!(TextImageOption::new(24.0, Some(0), JustifyMode::LEFT).is_err());
+        assert!(TextImageOption::default().line_height(0.0).is_err());
+
+        let option = TextImageOption::default().default_width(384);
+        assert_eq!(option.width(), Some(384));
+        let option = TextImageOption::new(24.0, Some(200), JustifyMode::LEFT)
+            .unwrap()
+            .default_width(384);
+        assert_eq!(option.width(), Some(200));
+    }
+
+    #[test]
+    fn test_text_image_errors() {
+        let font = TextFont::from_path(FONT).unwrap();
+        assert!(TextImage::new(&font, "Hello", TextImageOption::default()).is_err());
+
+        let option = TextImageOption::new(24.0, Some(200), JustifyMode::LEFT).unwrap();
+        assert!(TextImage::new(&font, "  ", option).is_err());
+        assert!(TextFont::from_bytes(vec![0; 16]).is_err());
+    }
+
+    #[test]
+    fn test_text_image_wrap() {
+        let font = TextFont::from_path(FONT).unwrap();
+
+        // DejaVu Sans Mono advance is about 10.3 dots at 20 dots: 12 characters per line
+        let option = TextImageOption::new(20.0, Some(130), JustifyMode::LEFT).unwrap();
+        let text = TextImage::new(&font, "Hello world, from escpos\nBye", option).unwrap();
+        assert_eq!(text.lines(), &["Hello world,", "from escpos", "Bye"]);
+
+        let one_line = TextImage::new(&font, "Hello", option).unwrap();
+        assert_eq!(text.image().height(), 3 * one_line.image().height());
+
+        // Words larger than the width are split
+        let text = TextImage::new(&font, "abcdefghijklmnopqrstuvwxyz", option).unwrap();
+        assert_eq!(text.lines(), &["abcdefghijkl", "mnopqrstuvwx", "yz"]);
+    }
+
+    #[test]
+    fn test_text_image_alignment() {
+        let font = TextFont::from_path(FONT).unwrap();
+        let render = |alignment| {
+            let option = TextImageOption::new(24.0, Some(300), alignment).unwrap();
+            let text = TextImage::new(&font, "Hi", option).unwrap();
+            assert_eq!(text.image().width(), 300);
+            dark_columns(text.image())
+        };
+
+        let left = render(JustifyMode::LEFT);
+        assert!(*left.first().unwrap() < 10);
+        assert!(*left.last().unwrap() < 40);
+
+        let center = render(JustifyMode::CENTER);
+        assert!(*center.first().unwrap() > 130);
+        assert!(*center.last().unwrap() < 170);
+
+        let right = render(JustifyMode::RIGHT);
+        assert!(*right.first().unwrap() > 260);
+        assert!(*right.last().unwrap() < 300);
+    }
+
+    #[test]
+    fn test_font_runs() {
+        // Font 0 has latin letters, font 1 has emoji and the variation selector is ignored
+        let has_glyph = |font: usize, c: char| match font {
+            0 => c.is_ascii(),
+            _ => c == '\u{2764}' || c == '\u{1F600}',
+        };
+
+        assert_eq!(
+            font_runs("ab\u{1F600}\u{2764}\u{FE0F}c", 2, has_glyph),
+            vec![(0, "ab"), (1, "\u{1F600}\u{2764}\u{FE0F}"), (0, "c")]
+        );
+        // Characters missing from every font use the primary font
+        assert_eq!(font_runs("a\u{E01}", 2, has_glyph), vec![(0, "a\u{E01}")]);
+        // A cluster uses the font with its first character when no font has all its characters
+        assert_eq!(select_font("\u{1F600}\u{301}", 2, has_glyph), 1);
+        assert_eq!(select_font("\u{200D}", 2, has_glyph), 0);
+    }
+
+    #[test]
+    fn test_text_image_shaping() {
+        let font = TextFont::from_path(FONT).unwrap();
+        let fonts = font.load(20.0).unwrap();
+        let ids = |text: &str| shape(&fonts, text).0.iter().map(|glyph| glyph.id).collect::<Vec<_>>();
+        let cmap = |c: char| GlyphId(fonts[0].face.glyph_index(c).unwrap().0);
+
+        // Combining marks are composed and do not advance
+        assert_eq!(ids("e\u{301}"), vec![cmap('\u{E9}')]);
+        assert_eq!(measure(&fonts, "e\u{301}"), measure(&fonts, "e"));
+
+        // Arabic letters are joined (presentation forms) and laid out from right to left
+        assert_eq!(ids("\u{644}\u{627}"), vec![cmap('\u{FEFB}')]);
+        let salam = ids("\u{633}\u{644}\u{627}\u{645}");
+        assert_eq!(salam.len(), 3);
+        assert_eq!(&salam[1..], &[cmap('\u{FEFC}'), cmap('\u{FEB3}')]);
+    }
+
+    #[test]
+    fn test_text_image_wrap_graphemes() {
+        let font = TextFont::from_path(FONT).unwrap();
+
+        // Combining marks stay with their base letter
+        let option = TextImageOption::new(20.0, Some(25), JustifyMode::LEFT).unwrap();
+        let text = TextImage::new(&font, "e\u{301}e\u{301}e\u{301}", option).unwrap();
+        assert_eq!(text.lines(), &["e\u{301}e\u{301}", "e\u{301}"]);
+    }
+
+    #[test]
+    fn test_text_font_fallback() {
+        let font = TextFont::from_path(FONT).unwrap().fallback(
+            TextFont::from_path(FONT)
+                .unwrap()
+                .fallback(TextFont::from_path(FONT).unwrap()),
+        );
+        assert_eq!(font.fonts.len(), 3);
+
+        let option = TextImageOption::new(24.0, Some(200), JustifyMode::LEFT).unwrap();
+        assert!(TextImage::new(&font, "Hello \u{1F600}", option).is_ok());
+    }
+}
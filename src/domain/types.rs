@@ -19,7 +19,7 @@ impl fmt::Display for CashDrawer {
 }
 
 /// Justify mode
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum JustifyMode {
     LEFT,
     CENTER,
@@ -0,0 +1,213 @@
+//! User-defined characters
+//!
+//! [Epson Documentation](https://download4.epson.biz/sec_pubs/pos/reference_en/escpos/esc_ampersand.html)
+
+use super::character::Font;
+use crate::errors::{PrinterError, Result};
+#[cfg(feature = "graphics")]
+use image::GenericImageView;
+use std::collections::HashMap;
+
+/// First code available for user-defined characters
+const USER_DEFINED_FIRST_CODE: u8 = 32;
+/// Last code available for user-defined characters
+const USER_DEFINED_LAST_CODE: u8 = 126;
+
+/// Number of bytes in the vertical direction (24 dots)
+pub(crate) const USER_DEFINED_VERTICAL_BYTES: u8 = 3;
+
+/// Get the maximum width and the height (in dots) of a user-defined character for a font
+fn font_dimensions(font: Font) -> Result<(u8, u8)> {
+    match font {
+        Font::A => Ok((12, 24)),
+        Font::B => Ok((9, 17)),
+        Font::C => Err(PrinterError::Input(
+            "user-defined characters are only available for fonts A and B".to_owned(),
+        )),
+    }
+}
+
+/// User-defined character
+///
+/// The glyph is stored in the printer under `code` (32 - 126) and used when `character` is written.
+#[derive(Debug, Clone)]
+pub struct UserDefinedCharacter {
+    character: char,
+    code: u8,
+    font: Font,
+    width: u8,
+    data: Vec<u8>,
+}
+
+impl UserDefinedCharacter {
+    /// Create a new `UserDefinedCharacter`
+    ///
+    /// `pixels` is the glyph bitmap, row by row from the top (`true` for a printed dot).
+    /// Each row must have the same width, up to 12 dots for font A and 9 dots for font B.
+    /// Font A glyphs are 24 dots high, font B glyphs 17 dots high, missing rows are left blank.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    ///
+    /// let rows = vec![vec![true; 12]; 24];
+    /// let character = UserDefinedCharacter::new('■', b'#', Font::A, &rows).unwrap();
+    ///
+    /// assert_eq!(character.width(), 12);
+    /// ```
+    pub fn new(character: char, code: u8, font: Font, pixels: &[Vec<bool>]) -> Result<Self> {
+        if !(USER_DEFINED_FIRST_CODE..=USER_DEFINED_LAST_CODE).contains(&code) {
+            return Err(PrinterError::Input(format!(
+                "invalid user-defined character code ({USER_DEFINED_FIRST_CODE}-{USER_DEFINED_LAST_CODE}): {code}"
+            )));
+        }
+
+        let (max_width, max_height) = font_dimensions(font)?;
+        if pixels.len() > max_height as usize {
+            return Err(PrinterError::Input(format!(
+                "user-defined character height is too big for {font} (max {max_height}): {}",
+                pixels.len()
+            )));
+        }
+
+        let width = pixels.first().map(|row| row.len()).unwrap_or_default();
+        if pixels.iter().any(|row| row.len() != width) {
+            return Err(PrinterError::Input(
+                "user-defined character rows must have the same width".to_owned(),
+            ));
+        }
+        if width > max_width as usize {
+            return Err(PrinterError::Input(format!(
+                "user-defined character width is too big for {font} (max {max_width}): {width}"
+            )));
+        }
+
+        // Column format: for each column, 3 bytes from top to bottom, MSB is the top dot
+        let mut data = vec![0; width * USER_DEFINED_VERTICAL_BYTES as usize];
+        for (y, row) in pixels.iter().enumerate() {
+            for (x, _) in row.iter().enumerate().filter(|(_, &pixel)| pixel) {
+                data[x * USER_DEFINED_VERTICAL_BYTES as usize + y / 8] |= 0x80 >> (y % 8);
+            }
+        }
+
+        Ok(Self {
+            character,
+            code,
+            font,
+            width: u8::try_from(width)?,
+            data,
+        })
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Create a new `UserDefinedCharacter` from an image file
+    ///
+    /// The image is scaled to the font height and converted to black and white.
+    pub fn from_image(character: char, code: u8, font: Font, path: &str) -> Result<Self> {
+        let (max_width, max_height) = font_dimensions(font)?;
+        let img = image::open(path)?
+            .resize(u32::from(max_width), u32::from(max_height), image::imageops::Nearest)
+            .grayscale();
+
+        let pixels = (0..img.height())
+            .map(|y| {
+                (0..img.width())
+                    .map(|x| {
+                        let pixel = img.get_pixel(x, y);
+                        pixel.0[3] != 0 && pixel.0[0] <= 128
+                    })
+                    .collect()
+            })
+            .collect::<Vec<Vec<bool>>>();
+
+        Self::new(character, code, font, &pixels)
+    }
+
+    /// Get the Unicode character replaced by this glyph
+    pub fn character(&self) -> char {
+        self.character
+    }
+
+    /// Get the character code (32 - 126)
+    pub fn code(&self) -> u8 {
+        self.code
+    }
+
+    /// Get font
+    pub fn font(&self) -> Font {
+        self.font
+    }
+
+    /// Get width in dots
+    pub fn width(&self) -> u8 {
+        self.width
+    }
+
+    /// Get data in column format
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Printer state used to print the user-defined characters mapped to Unicode characters
+#[derive(Debug, Clone)]
+pub(crate) struct UserDefinedState {
+    /// Code and font of the glyph of each Unicode character
+    pub(crate) characters: HashMap<char, (u8, Font)>,
+    /// Font selected with `ESC M`
+    pub(crate) font: Font,
+    /// User-defined character set selected with `ESC % 1`
+    pub(crate) selected: bool,
+}
+
+impl Default for UserDefinedState {
+    fn default() -> Self {
+        Self {
+            characters: HashMap::new(),
+            font: Font::A,
+            selected: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_defined_character_new() {
+        let rows = vec![vec![false; 12]; 24];
+        assert!(UserDefinedCharacter::new('a', 32, Font::A, &rows).is_ok());
+        assert!(UserDefinedCharacter::new('a', 126, Font::A, &rows).is_ok());
+        assert!(UserDefinedCharacter::new('a', 31, Font::A, &rows).is_err());
+        assert!(UserDefinedCharacter::new('a', 127, Font::A, &rows).is_err());
+        assert!(UserDefinedCharacter::new('a', 65, Font::B, &rows).is_err());
+        assert!(UserDefinedCharacter::new('a', 65, Font::C, &rows).is_err());
+        assert!(UserDefinedCharacter::new('a', 65, Font::A, &vec![vec![false; 13]; 24]).is_err());
+        assert!(UserDefinedCharacter::new('a', 65, Font::A, &[vec![false; 2], vec![false; 3]]).is_err());
+        assert!(UserDefinedCharacter::new('a', 65, Font::B, &vec![vec![false; 9]; 17]).is_ok());
+    }
+
+    #[test]
+    fn test_user_defined_character_data() {
+        // Diagonal on the first 3 columns, last dot of the first column
+        let mut rows = vec![vec![false; 3]; 24];
+        rows[0][0] = true;
+        rows[1][1] = true;
+        rows[8][2] = true;
+        rows[23][0] = true;
+
+        let character = UserDefinedCharacter::new('€', 65, Font::A, &rows).unwrap();
+        assert_eq!(character.width(), 3);
+        assert_eq!(
+            character.data(),
+            &[0b1000_0000, 0, 1, 0b0100_0000, 0, 0, 0, 0b1000_0000, 0]
+        );
+    }
+
+    #[test]
+    fn test_user_defined_character_empty() {
+        let character = UserDefinedCharacter::new(' ', 32, Font::A, &[]).unwrap();
+        assert_eq!(character.width(), 0);
+        assert!(character.data().is_empty());
+    }
+}
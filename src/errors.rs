@@ -2,7 +2,7 @@
 
 #[cfg(feature = "graphics")]
 use image::ImageError;
-use std::{borrow::Cow, cell::BorrowMutError, fmt, io, num::TryFromIntError};
+use std::{borrow::Cow, cell::BorrowMutError, fmt, io, num::TryFromIntError, sync::PoisonError};
 
 /// Custom Result for `PrinterError`
 pub type Result<T> = std::result::Result<T, PrinterError>;
@@ -45,6 +45,12 @@ impl From<BorrowMutError> for PrinterError {
     }
 }
 
+impl<T> From<PoisonError<T>> for PrinterError {
+    fn from(err: PoisonError<T>) -> Self {
+        PrinterError::Io(err.to_string())
+    }
+}
+
 impl From<TryFromIntError> for PrinterError {
     fn from(err: TryFromIntError) -> Self {
         PrinterError::Io(err.to_string())
@@ -2,24 +2,27 @@
 
 use crate::errors::{PrinterError, Result};
 #[cfg(feature = "native_usb")]
-use futures_lite::future::block_on;
+use futures_lite::future::{block_on, poll_once};
 #[cfg(feature = "hidapi")]
 use hidapi::{HidApi, HidDevice};
 #[cfg(feature = "native_usb")]
-use nusb::transfer::RequestBuffer;
+use nusb::transfer::{RequestBuffer, TransferError};
 #[cfg(feature = "usb")]
 use rusb::{Context, DeviceHandle, Direction, TransferType, UsbContext};
 #[cfg(feature = "serial_port")]
 use serialport::SerialPort;
+#[cfg(any(feature = "hidapi", feature = "serial_port"))]
+use std::sync::Mutex;
 use std::{
-    cell::RefCell,
     fs::File,
     io::{self, Read, Write},
     net::{IpAddr, SocketAddr, TcpStream},
     path::Path,
-    rc::Rc,
+    sync::Arc,
     time::Duration,
 };
+#[cfg(feature = "native_usb")]
+use std::{thread::sleep, time::Instant};
 
 #[cfg(feature = "windows-driver")]
 pub mod windows_driver;
@@ -27,6 +30,10 @@ pub mod windows_driver;
 /// Default timeout in seconds for read/write operations
 const DEFAULT_TIMEOUT_SECONDS: u64 = 5;
 
+/// Delay between two polls of a pending native USB read
+#[cfg(feature = "native_usb")]
+const NATIVE_USB_READ_INTERVAL: Duration = Duration::from_millis(10);
+
 /// Printer driver trait
 ///
 /// A custom driver can be implemented by implementing this trait.
@@ -44,6 +51,16 @@ pub trait Driver {
     fn flush(&self) -> Result<()>;
 }
 
+/// Convert a read timeout to no data read
+///
+/// Status readers poll the drivers and stop at their own timeout.
+pub(crate) fn read_until_timeout(result: io::Result<usize>) -> Result<usize> {
+    match result {
+        Err(err) if matches!(err.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => Ok(0),
+        result => Ok(result?),
+    }
+}
+
 // ================ Console driver ================
 
 /// Console driver for debug
@@ -98,7 +115,7 @@ impl Driver for ConsoleDriver {
 pub struct NetworkDriver {
     host: String,
     port: u16,
-    stream: Rc<RefCell<TcpStream>>,
+    stream: Arc<TcpStream>,
     timeout: Duration,
 }
 
@@ -132,7 +149,7 @@ impl NetworkDriver {
         Ok(Self {
             host: host.to_string(),
             port,
-            stream: Rc::new(RefCell::new(stream)),
+            stream: Arc::new(stream),
             timeout,
         })
     }
@@ -144,21 +161,21 @@ impl Driver for NetworkDriver {
     }
 
     fn write(&self, data: &[u8]) -> Result<()> {
-        let mut stream = self.stream.try_borrow_mut()?;
+        let mut stream = self.stream.as_ref();
         stream.set_write_timeout(Some(self.timeout))?;
 
         Ok(stream.write_all(data)?)
     }
 
     fn read(&self, buf: &mut [u8]) -> Result<usize> {
-        let mut stream = self.stream.try_borrow_mut()?;
+        let mut stream = self.stream.as_ref();
         stream.set_read_timeout(Some(self.timeout))?;
 
-        Ok(stream.read(buf)?)
+        read_until_timeout(stream.read(buf))
     }
 
     fn flush(&self) -> Result<()> {
-        Ok(self.stream.try_borrow_mut()?.flush()?)
+        Ok(self.stream.as_ref().flush()?)
     }
 }
 
@@ -168,7 +185,7 @@ impl Driver for NetworkDriver {
 #[derive(Clone)]
 pub struct FileDriver {
     path: String,
-    file: Rc<RefCell<File>>,
+    file: Arc<File>,
 }
 
 impl FileDriver {
@@ -190,7 +207,7 @@ impl FileDriver {
         let file = File::options().read(true).append(true).open(path)?;
         Ok(Self {
             path: path.to_string_lossy().to_string(),
-            file: Rc::new(RefCell::new(file)),
+            file: Arc::new(file),
         })
     }
 }
@@ -201,16 +218,16 @@ impl Driver for FileDriver {
     }
 
     fn write(&self, data: &[u8]) -> Result<()> {
-        self.file.try_borrow_mut()?.write_all(data)?;
+        self.file.as_ref().write_all(data)?;
         Ok(())
     }
 
     fn read(&self, buf: &mut [u8]) -> Result<usize> {
-        Ok(self.file.try_borrow_mut()?.read(buf)?)
+        Ok(self.file.as_ref().read(buf)?)
     }
 
     fn flush(&self) -> Result<()> {
-        Ok(self.file.try_borrow_mut()?.flush()?)
+        Ok(self.file.as_ref().flush()?)
     }
 }
 
@@ -224,7 +241,7 @@ pub struct UsbDriver {
     product_id: u16,
     output_endpoint: u8,
     input_endpoint: u8,
-    device: Rc<RefCell<DeviceHandle<Context>>>,
+    device: Arc<DeviceHandle<Context>>,
     timeout: Duration,
 }
 
@@ -311,7 +328,7 @@ impl UsbDriver {
                             product_id,
                             output_endpoint,
                             input_endpoint,
-                            device: Rc::new(RefCell::new(device_handle)),
+                            device: Arc::new(device_handle),
                             timeout: timeout.unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECONDS)),
                         })
                     }
@@ -335,17 +352,16 @@ impl Driver for UsbDriver {
 
     fn write(&self, data: &[u8]) -> Result<()> {
         self.device
-            .try_borrow_mut()?
             .write_bulk(self.output_endpoint, data, self.timeout)
             .map_err(|e| PrinterError::Io(e.to_string()))?;
         Ok(())
     }
 
     fn read(&self, buf: &mut [u8]) -> Result<usize> {
-        self.device
-            .try_borrow_mut()?
-            .read_bulk(self.input_endpoint, buf, self.timeout)
-            .map_err(|e| PrinterError::Io(e.to_string()))
+        match self.device.read_bulk(self.input_endpoint, buf, self.timeout) {
+            Err(rusb::Error::Timeout) => Ok(0),
+            result => result.map_err(|e| PrinterError::Io(e.to_string())),
+        }
     }
 
     fn flush(&self) -> Result<()> {
@@ -361,7 +377,7 @@ pub struct NativeUsbDriver {
     product_id: u16,
     output_endpoint: u8,
     input_endpoint: u8,
-    device: Rc<RefCell<nusb::Interface>>,
+    device: Arc<nusb::Interface>,
 }
 
 #[cfg(feature = "native_usb")]
@@ -439,7 +455,7 @@ impl NativeUsbDriver {
             product_id,
             output_endpoint,
             input_endpoint,
-            device: Rc::new(RefCell::new(interface)),
+            device: Arc::new(interface),
         })
     }
 }
@@ -454,27 +470,33 @@ impl Driver for NativeUsbDriver {
     }
 
     fn write(&self, data: &[u8]) -> Result<()> {
-        block_on(
-            self.device
-                .try_borrow_mut()?
-                .bulk_out(self.output_endpoint, data.to_vec()),
-        )
-        .into_result()
-        .map_err(|e| PrinterError::Io(e.to_string()))?;
+        block_on(self.device.bulk_out(self.output_endpoint, data.to_vec()))
+            .into_result()
+            .map_err(|e| PrinterError::Io(e.to_string()))?;
         Ok(())
     }
 
+    /// Read the responses one by one, the pending transfer is cancelled after the default timeout
     fn read(&self, buf: &mut [u8]) -> Result<usize> {
-        // Seems to read responses one by one
+        let deadline = Instant::now() + Duration::from_secs(DEFAULT_TIMEOUT_SECONDS);
+        let mut queue = self.device.bulk_in_queue(self.input_endpoint);
         let mut size = 0;
         for b in buf.iter_mut() {
-            let result = block_on(
-                self.device
-                    .try_borrow_mut()?
-                    .bulk_in(self.input_endpoint, RequestBuffer::new(1)),
-            )
-            .into_result()
-            .map_err(|e| PrinterError::Io(e.to_string()))?;
+            queue.submit(RequestBuffer::new(1));
+            let completion = loop {
+                if let Some(completion) = block_on(poll_once(queue.next_complete())) {
+                    break completion;
+                }
+                if Instant::now() >= deadline {
+                    queue.cancel_all();
+                    break block_on(queue.next_complete());
+                }
+                sleep(NATIVE_USB_READ_INTERVAL);
+            };
+            let result = match completion.into_result() {
+                Err(TransferError::Cancelled) => return Ok(size),
+                result => result.map_err(|e| PrinterError::Io(e.to_string()))?,
+            };
 
             if !result.is_empty() {
                 *b = result[0];
@@ -498,7 +520,7 @@ impl Driver for NativeUsbDriver {
 pub struct HidApiDriver {
     vendor_id: u16,
     product_id: u16,
-    device: Rc<RefCell<HidDevice>>,
+    device: Arc<Mutex<HidDevice>>,
 }
 
 #[cfg(feature = "hidapi")]
@@ -524,7 +546,7 @@ impl HidApiDriver {
         Ok(Self {
             vendor_id,
             product_id,
-            device: Rc::new(RefCell::new(device)),
+            device: Arc::new(Mutex::new(device)),
         })
     }
 }
@@ -537,7 +559,7 @@ impl Driver for HidApiDriver {
 
     fn write(&self, data: &[u8]) -> Result<()> {
         self.device
-            .try_borrow_mut()?
+            .lock()?
             .write(data)
             .map_err(|e| PrinterError::Io(e.to_string()))?;
         Ok(())
@@ -545,7 +567,7 @@ impl Driver for HidApiDriver {
 
     fn read(&self, buf: &mut [u8]) -> Result<usize> {
         self.device
-            .try_borrow_mut()?
+            .lock()?
             .read_timeout(buf, i32::try_from(DEFAULT_TIMEOUT_SECONDS * 1_000)?)
             .map_err(|e| PrinterError::Io(e.to_string()))
     }
@@ -562,7 +584,9 @@ impl Driver for HidApiDriver {
 #[derive(Clone)]
 pub struct SerialPortDriver {
     path: String,
-    port: Rc<RefCell<Box<dyn SerialPort>>>,
+    port: Arc<Mutex<Box<dyn SerialPort>>>,
+    /// Clone of the port handle, a pending read does not block the writes
+    reader: Arc<Mutex<Box<dyn SerialPort>>>,
 }
 
 #[cfg(feature = "serial_port")]
@@ -586,10 +610,12 @@ impl SerialPortDriver {
             port = port.timeout(timeout);
         }
         let port = port.open().map_err(|e| PrinterError::Io(e.to_string()))?;
+        let reader = port.try_clone().map_err(|e| PrinterError::Io(e.to_string()))?;
 
         Ok(Self {
             path: path.to_string(),
-            port: Rc::new(RefCell::new(port)),
+            port: Arc::new(Mutex::new(port)),
+            reader: Arc::new(Mutex::new(reader)),
         })
     }
 }
@@ -601,19 +627,19 @@ impl Driver for SerialPortDriver {
     }
 
     fn write(&self, data: &[u8]) -> Result<()> {
-        self.port.try_borrow_mut()?.write_all(data)?;
+        self.port.lock()?.write_all(data)?;
 
         Ok(())
     }
 
     fn read(&self, buf: &mut [u8]) -> Result<usize> {
-        let mut port = self.port.try_borrow_mut()?;
+        let mut port = self.reader.lock()?;
         port.set_timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECONDS))
             .map_err(|e| PrinterError::Io(e.to_string()))?;
-        Ok(port.read(buf)?)
+        read_until_timeout(port.read(buf))
     }
 
     fn flush(&self) -> Result<()> {
-        Ok(self.port.try_borrow_mut()?.flush()?)
+        Ok(self.port.lock()?.flush()?)
     }
 }
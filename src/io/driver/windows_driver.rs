@@ -125,4 +125,4 @@ impl Driver for WindowsDriver {
     fn flush(&self) -> Result<()> {
         self.write_all()
     }
-}
\ No newline at end of file
+}
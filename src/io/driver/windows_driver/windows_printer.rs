@@ -86,4 +86,4 @@ impl Debug for WindowsPrinter {
             .field("name", &self.get_name())
             .finish()
     }
-}
\ No newline at end of file
+}
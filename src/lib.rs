@@ -139,22 +139,14 @@
 //! fn main() -> Result<()> {
 //!     // env_logger::init();
 //!
-//!     // let driver = NetworkDriver::open("192.168.1.248", 9100, None)?;
-//!     let driver = ConsoleDriver::open(true);
-//!     Printer::new(driver.clone(), Protocol::default(), None)
+//!     // The console driver does not answer status requests
+//!     let driver = NetworkDriver::open("192.168.1.248", 9100, None)?;
+//!     let state = Printer::new(driver, Protocol::default(), None)
 //!         .debug_mode(Some(DebugMode::Dec))
-//!         .real_time_status(RealTimeStatusRequest::Printer)?
-//!         .real_time_status(RealTimeStatusRequest::RollPaperSensor)?
-//!         .send_status()?;
-//!
-//!     let mut buf = [0; 1];
-//!     driver.read(&mut buf)?;
-//!
-//!     let status = RealTimeStatusResponse::parse(RealTimeStatusRequest::Printer, buf[0])?;
-//!     println!(
-//!         "Printer online: {}",
-//!         status.get(&RealTimeStatusResponse::Online).unwrap_or(&false)
-//!     );
+//!         .query_status(&[RealTimeStatusRequest::Printer, RealTimeStatusRequest::RollPaperSensor])?;
+//!
+//!     println!("Printer online: {}", state.is_online());
+//!     println!("{state}");
 //!
 //!     Ok(())
 //! }
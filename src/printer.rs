@@ -1,9 +1,18 @@
 //! Printer
 
 use super::errors::Result;
+use crate::errors::PrinterError;
 use crate::printer_options::PrinterOptions;
 use crate::{domain::*, driver::Driver, utils::Protocol};
+#[cfg(feature = "graphics")]
+use image::{DynamicImage, GrayImage};
 use log::debug;
+use std::sync::mpsc::{self, Receiver};
+use std::thread::{self, sleep};
+use std::time::{Duration, Instant};
+
+/// Delay between two reads of real-time status responses
+const STATUS_READ_INTERVAL: Duration = Duration::from_millis(10);
 
 /// Printer
 ///
@@ -33,6 +42,9 @@ pub struct Printer<D: Driver> {
     protocol: Protocol,
     options: PrinterOptions,
     instructions: Vec<Instruction>,
+    user_defined: UserDefinedState,
+    response_parser: PrinterResponseParser,
+    responses: Vec<PrinterResponse>,
 }
 
 impl<D: Driver> Printer<D> {
@@ -60,6 +72,9 @@ impl<D: Driver> Printer<D> {
             protocol,
             options: options.unwrap_or_default(),
             instructions: vec![],
+            user_defined: UserDefinedState::default(),
+            response_parser: PrinterResponseParser::new(),
+            responses: vec![],
         }
     }
 
@@ -117,9 +132,12 @@ impl<D: Driver> Printer<D> {
     }
 
     /// Hardware initialization
+    ///
+    /// User-defined characters are deleted by the printer.
     pub fn init(&mut self) -> Result<&mut Self> {
         let cmd = self.protocol.init();
         self.command("initialization", &[cmd])?;
+        self.user_defined = UserDefinedState::default();
 
         // Set page code
         if let Some(page_code) = self.options.get_page_code() {
@@ -189,6 +207,7 @@ impl<D: Driver> Printer<D> {
     /// Text font
     pub fn font(&mut self, font: Font) -> Result<&mut Self> {
         let cmd = self.protocol.font(font);
+        self.user_defined.font = font;
         self.command("text font", &[cmd])
     }
 
@@ -258,6 +277,34 @@ impl<D: Driver> Printer<D> {
         self.command("upside-down mode", &[cmd])
     }
 
+    /// Print color
+    ///
+    /// Select the color used for the following text on two-color paper.
+    /// Switch back to [`Color::Black`] to restore the default color.
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     Printer::new(driver, Protocol::default(), None)
+    ///         .init()?
+    ///         .color(Color::Red)?
+    ///         .writeln("Total: 42.00")?
+    ///         .color(Color::Black)?
+    ///         .writeln("Thank you!")?
+    ///         .print_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn color(&mut self, color: Color) -> Result<&mut Self> {
+        let cmd = self.protocol.color(color);
+        self.command(&format!("print color {color}"), &[cmd])
+    }
+
     /// Cash drawer
     pub fn cash_drawer(&mut self, pin: CashDrawer) -> Result<&mut Self> {
         let cmd = self.protocol.cash_drawer(pin);
@@ -265,8 +312,16 @@ impl<D: Driver> Printer<D> {
     }
 
     /// Text
+    ///
+    /// Characters mapped with [`define_user_character`](Self::define_user_character)
+    /// are printed with their user-defined glyph, in the font the glyph was defined for.
     pub fn write(&mut self, text: &str) -> Result<&mut Self> {
-        let cmd = self.protocol.text(text, self.options.get_page_code())?;
+        let cmd = match self.user_defined.characters.is_empty() {
+            true => self.protocol.text(text, self.options.get_page_code())?,
+            false => self
+                .protocol
+                .text_with_user_defined(text, self.options.get_page_code(), &self.user_defined)?,
+        };
         self.command("text", &[cmd])
     }
 
@@ -329,6 +384,58 @@ impl<D: Driver> Printer<D> {
         )
     }
 
+    /// Define a user-defined character
+    ///
+    /// The glyph is stored in the printer for its font and used by [`write`](Self::write)
+    /// and [`writeln`](Self::writeln) each time the Unicode character is found in the text.
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let square = vec![vec![true; 12]; 24];
+    ///
+    ///     let driver = ConsoleDriver::open(false);
+    ///     Printer::new(driver, Protocol::default(), None)
+    ///         .init()?
+    ///         .define_user_character(UserDefinedCharacter::new('■', b'#', Font::A, &square)?)?
+    ///         .writeln("■ Paid")?
+    ///         .print_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn define_user_character(&mut self, character: UserDefinedCharacter) -> Result<&mut Self> {
+        let cmd = self.protocol.define_user_character(&character, self.user_defined.font);
+        let glyph = (character.code(), character.font());
+        self.user_defined.characters.retain(|_, defined| *defined != glyph);
+        self.user_defined.characters.insert(character.character(), glyph);
+        self.command(
+            &format!("define user-defined character {}", character.character()),
+            &[cmd],
+        )
+    }
+
+    /// Select or cancel user-defined character set
+    ///
+    /// Only needed when sending user-defined character codes with [`custom`](Self::custom),
+    /// [`write`](Self::write) handles it automatically.
+    pub fn user_defined_characters(&mut self, enabled: bool) -> Result<&mut Self> {
+        let cmd = self.protocol.user_defined_characters(enabled);
+        self.user_defined.selected = enabled;
+        self.command("select user-defined character set", &[cmd])
+    }
+
+    /// Cancel a user-defined character of the current font
+    pub fn cancel_user_defined_character(&mut self, code: u8) -> Result<&mut Self> {
+        let cmd = self.protocol.cancel_user_defined_character(code);
+        let glyph = (code, self.user_defined.font);
+        self.user_defined.characters.retain(|_, defined| *defined != glyph);
+        self.command("cancel user-defined character", &[cmd])
+    }
+
     /// Set horizontal and vertical motion units
     pub fn motion_units(&mut self, x: u8, y: u8) -> Result<&mut Self> {
         let cmd = self.protocol.motion_units(x, y);
@@ -352,9 +459,281 @@ impl<D: Driver> Printer<D> {
         Ok(self)
     }
 
+    /// Enable automatic status back (`GS a`) for the events, disabled with `AsbEvents::default()`
+    ///
+    /// The status packets are read with an [`AsbListener`].
+    pub fn automatic_status_back(&mut self, events: AsbEvents) -> Result<&mut Self> {
+        let cmd = self.protocol.automatic_status_back(events);
+        self.command("automatic status back", &[cmd])
+    }
+
+    /// Query real-time statuses
+    ///
+    /// The `DLE EOT` requests are sent with the pending instructions, then one response byte per request
+    /// is read before the [status timeout](PrinterOptions::status_timeout).
+    /// Other responses received meanwhile (automatic status back packets, etc.) are kept,
+    /// see [`take_responses`](Self::take_responses).
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = NetworkDriver::open("192.168.1.248", 9100, None)?;
+    ///     let state = Printer::new(driver, Protocol::default(), None)
+    ///         .query_status(&[RealTimeStatusRequest::Printer, RealTimeStatusRequest::RollPaperSensor])?;
+    ///     println!("{state}");
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn query_status(&mut self, requests: &[RealTimeStatusRequest]) -> Result<PrinterState> {
+        for &request in requests {
+            self.real_time_status(request)?;
+        }
+        self.send_status()?;
+
+        let responses = self.read_status_responses(requests.len())?;
+        let responses = requests.iter().copied().zip(responses).collect::<Vec<_>>();
+        PrinterState::from_responses(&responses)
+    }
+
+    /// Read `length` real-time status response bytes before the status timeout
+    fn read_status_responses(&mut self, length: usize) -> Result<Vec<u8>> {
+        let deadline = Instant::now() + self.options.get_status_timeout();
+        let mut statuses = Vec::with_capacity(length);
+        while statuses.len() < length {
+            // Other one-byte responses are rejected when parsing the real-time status
+            let status = self.read_response(deadline, |response| match response {
+                PrinterResponse::RealTimeStatus(status) | PrinterResponse::Byte(status) => Ok(status),
+                response => Err(response),
+            })?;
+            match status {
+                Some(status) => statuses.push(status),
+                None => {
+                    return Err(PrinterError::InvalidResponse(format!(
+                        "real-time status timeout: {} of {length} bytes received",
+                        statuses.len()
+                    )))
+                }
+            }
+        }
+
+        Ok(statuses)
+    }
+
+    /// Send the instructions of a `GS` query and read its response before the status timeout
+    ///
+    /// Returns `None` if the printer does not answer. Real-time statuses received meanwhile are kept.
+    fn read_query_response(&mut self, label: &str, cmd: Command) -> Result<Option<PrinterResponse>> {
+        self.command(label, &[cmd])?.send_status()?;
+
+        let deadline = Instant::now() + self.options.get_status_timeout();
+        self.read_response(deadline, |response| match response {
+            response @ PrinterResponse::RealTimeStatus(_) => Err(response),
+            response => Ok(response),
+        })
+    }
+
+    /// Send a `GS` query and read its response, the printer must answer before the status timeout
+    fn query_response(&mut self, label: &str, cmd: Command) -> Result<PrinterResponse> {
+        self.read_query_response(label, cmd)?
+            .ok_or_else(|| PrinterError::InvalidResponse(format!("{label} timeout")))
+    }
+
+    /// Read responses until `expected` accepts one or the deadline is reached (`None`)
+    ///
+    /// Automatic status back packets and the responses rejected by `expected` are kept,
+    /// see [`take_responses`](Self::take_responses).
+    fn read_response<T>(
+        &mut self,
+        deadline: Instant,
+        expected: fn(PrinterResponse) -> std::result::Result<T, PrinterResponse>,
+    ) -> Result<Option<T>> {
+        let mut byte = [0; 1];
+        loop {
+            if self.driver.read(&mut byte)? == 0 {
+                if Instant::now() >= deadline {
+                    return Ok(None);
+                }
+                sleep(STATUS_READ_INTERVAL);
+                continue;
+            }
+
+            match self.response_parser.push(byte[0])? {
+                Some(response @ PrinterResponse::AutomaticStatus(_)) => self.responses.push(response),
+                Some(response) => match expected(response) {
+                    Ok(value) => return Ok(Some(value)),
+                    Err(response) => self.responses.push(response),
+                },
+                None => (),
+            }
+        }
+    }
+
+    /// Query printer ID (`GS I`)
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = NetworkDriver::open("192.168.1.248", 9100, None)?;
+    ///     let id = Printer::new(driver, Protocol::default(), None).printer_id(PrinterIdRequest::ModelName)?;
+    ///     println!("{id:?}");
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn printer_id(&mut self, request: PrinterIdRequest) -> Result<PrinterId> {
+        let cmd = self.protocol.transmit_printer_id(request);
+        let response = self.query_response("transmit printer ID", cmd)?;
+        PrinterId::parse(request, &response)
+    }
+
+    /// Query manufacturer, model name, serial number, firmware version and type ID
+    ///
+    /// The identity can be used to select the [`PrinterOptions`] matching the connected printer.
+    /// The queries stop at the first one the printer does not answer before the
+    /// [status timeout](PrinterOptions::status_timeout), the partial identity is returned
+    /// and the remaining information is `None`.
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = NetworkDriver::open("192.168.1.248", 9100, None)?;
+    ///     let identity = Printer::new(driver, Protocol::default(), None).identify()?;
+    ///     println!("{identity}");
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn identify(&mut self) -> Result<PrinterIdentity> {
+        let mut identity = PrinterIdentity::default();
+        for request in [
+            PrinterIdRequest::Manufacturer,
+            PrinterIdRequest::ModelName,
+            PrinterIdRequest::SerialNumber,
+            PrinterIdRequest::Firmware,
+            PrinterIdRequest::TypeId,
+        ] {
+            let cmd = self.protocol.transmit_printer_id(request);
+            // A late answer would be read as the response of the next query
+            let Some(response) = self.read_query_response("transmit printer ID", cmd)? else {
+                break;
+            };
+            match PrinterId::parse(request, &response)? {
+                PrinterId::Manufacturer(text) => identity.manufacturer = Some(text),
+                PrinterId::ModelName(text) => identity.model_name = Some(text),
+                PrinterId::SerialNumber(text) => identity.serial_number = Some(text),
+                PrinterId::Firmware(text) => identity.firmware = Some(text),
+                PrinterId::TypeId(type_id) => identity.type_id = Some(type_id),
+                id => {
+                    return Err(PrinterError::InvalidResponse(format!(
+                        "unexpected response to printer ID request {request:?}: {id:?}"
+                    )))
+                }
+            }
+        }
+
+        Ok(identity)
+    }
+
+    /// Query paper sensor, drawer or ink status (`GS r`)
+    pub fn transmit_status(&mut self, request: TransmitStatusRequest) -> Result<TransmitStatus> {
+        let cmd = self.protocol.transmit_status(request);
+        match self.query_response("transmit status", cmd)? {
+            PrinterResponse::Byte(status) => TransmitStatus::parse(request, status),
+            response => Err(PrinterError::InvalidResponse(format!(
+                "unexpected response to transmit status request {request:?}: {response:?}"
+            ))),
+        }
+    }
+
+    /// Wait until the printer has processed the previous instructions (`GS ( H`)
+    ///
+    /// The printer sends the process ID back once the data before the request is processed,
+    /// it must be received before the [status timeout](PrinterOptions::status_timeout).
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = NetworkDriver::open("192.168.1.248", 9100, None)?;
+    ///     Printer::new(driver, Protocol::default(), None)
+    ///         .init()?
+    ///         .writeln("Receipt")?
+    ///         .print_cut()?
+    ///         .wait_for_process(ProcessId::new(1)?)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn wait_for_process(&mut self, id: ProcessId) -> Result<&mut Self> {
+        let cmd = self.protocol.process_id_response(id);
+        let response = self.query_response("process ID response", cmd)?;
+        let received = ProcessId::parse(&response)?;
+        if received != id {
+            return Err(PrinterError::InvalidResponse(format!(
+                "unexpected process ID: {received} ({id} expected)"
+            )));
+        }
+
+        Ok(self)
+    }
+
+    /// Take the responses received while reading statuses (automatic status back packets, etc.)
+    pub fn take_responses(&mut self) -> Vec<PrinterResponse> {
+        std::mem::take(&mut self.responses)
+    }
+
     #[cfg(feature = "barcodes")]
     /// Print barcode
-    fn barcode(&mut self, barcode: Barcode) -> Result<&mut Self> {
+    ///
+    /// The module width must be supported by the printer (see [`PrinterOptions::barcode_module_widths`])
+    /// and the [estimated width](Barcode::estimated_width) must fit the paper width.
+    ///
+    /// ```
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::ConsoleDriver;
+    ///
+    /// let mut printer = Printer::new(ConsoleDriver::open(false), Protocol::default(), None);
+    /// let barcode = Barcode::new(BarcodeSystem::CODE128(Code128CodeSet::B), "ab12", BarcodeOption::default())?;
+    /// printer.barcode(barcode)?;
+    ///
+    /// // 95 modules of 6 dots do not fit 512 dots
+    /// let option = BarcodeOption::new(BarcodeWidth::Dots(6), BarcodeHeight::S, BarcodeFont::A, BarcodePosition::Below);
+    /// assert!(printer.barcode(Barcode::new(BarcodeSystem::EAN13, "012403258746", option)?).is_err());
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn barcode(&mut self, barcode: Barcode) -> Result<&mut Self> {
+        let module_width = u8::from(barcode.option.width());
+        let module_widths = self.options.get_barcode_module_widths();
+        if !module_widths.contains(&module_width) {
+            return Err(PrinterError::Input(format!(
+                "barcode module width not supported by the printer ({} - {}): {module_width}",
+                module_widths.start(),
+                module_widths.end()
+            )));
+        }
+        if let Some(width) = barcode.estimated_width() {
+            let paper_width = u32::from(self.options.get_dots_per_line());
+            if width > paper_width {
+                return Err(PrinterError::Input(format!(
+                    "{} barcode is too wide for the paper ({width} dots, max {paper_width})",
+                    barcode.system
+                )));
+            }
+        }
+
         let commands = self.protocol.barcode(&barcode.data, barcode.system, barcode.option)?;
         self.command(&format!("print {} barcode", barcode.system), commands.as_slice())
     }
@@ -443,24 +822,191 @@ impl<D: Driver> Printer<D> {
         self.barcode(Barcode::new(BarcodeSystem::ITF, data, option)?)
     }
 
+    #[cfg(feature = "barcodes")]
+    /// Print CODE 93 barcode with default option
+    pub fn code93(&mut self, data: &str) -> Result<&mut Self> {
+        self.barcode(Barcode::new(BarcodeSystem::CODE93, data, BarcodeOption::default())?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print CODE 93 barcode with option
+    pub fn code93_option(&mut self, data: &str, option: BarcodeOption) -> Result<&mut Self> {
+        self.barcode(Barcode::new(BarcodeSystem::CODE93, data, option)?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print CODE 128 barcode with default option
+    ///
+    /// Code sets are selected automatically to get the shortest barcode.
+    pub fn code128(&mut self, data: &str) -> Result<&mut Self> {
+        self.barcode(Barcode::new(
+            BarcodeSystem::CODE128(Code128CodeSet::Auto),
+            data,
+            BarcodeOption::default(),
+        )?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print CODE 128 barcode with option
+    ///
+    /// Code sets are selected automatically to get the shortest barcode.
+    pub fn code128_option(&mut self, data: &str, option: BarcodeOption) -> Result<&mut Self> {
+        self.barcode(Barcode::new(
+            BarcodeSystem::CODE128(Code128CodeSet::Auto),
+            data,
+            option,
+        )?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print GS1-128 barcode with default option
+    pub fn gs1_128(&mut self, data: &GS1Data) -> Result<&mut Self> {
+        self.barcode(Barcode::gs1(BarcodeSystem::GS1CODE128, data, BarcodeOption::default())?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print GS1-128 barcode with option
+    pub fn gs1_128_option(&mut self, data: &GS1Data, option: BarcodeOption) -> Result<&mut Self> {
+        self.barcode(Barcode::gs1(BarcodeSystem::GS1CODE128, data, option)?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print linear GS1 DataBar barcode with default option
+    pub fn gs1_databar(&mut self, data: &GS1Data, code_type: GS1DataBarType) -> Result<&mut Self> {
+        self.barcode(Barcode::gs1(
+            BarcodeSystem::GS1DataBar(code_type),
+            data,
+            BarcodeOption::default(),
+        )?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print linear GS1 DataBar barcode with option
+    pub fn gs1_databar_option(
+        &mut self,
+        data: &GS1Data,
+        code_type: GS1DataBarType,
+        option: BarcodeOption,
+    ) -> Result<&mut Self> {
+        self.barcode(Barcode::gs1(BarcodeSystem::GS1DataBar(code_type), data, option)?)
+    }
+
     #[cfg(feature = "codes_2d")]
-    /// Construct QR code
-    fn qrcode_builder(&mut self, data: &str, option: Option<QRCodeOption>) -> Result<&mut Self> {
-        let qrcode = QRCode::new(data, option)?;
+    /// Print a QR code
+    ///
+    /// Model 2 QR codes must fit the paper width, the module size is selected
+    /// with [`QRCodeOption::auto_size()`].
+    /// With the `codes_2d_image` feature, the QR code is printed as image if the printer options
+    /// 2D code rendering is `Code2DRendering::Image`. QR codes with a structured append header
+    /// or an ECI are always printed as images, ESC/POS having no structured append or ECI function.
+    ///
+    /// ```
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::ConsoleDriver;
+    ///
+    /// // Latin-1 data, without ECI designator
+    /// let mut printer = Printer::new(ConsoleDriver::open(false), Protocol::default(), None);
+    /// printer.print_qrcode(QRCode::from_bytes(&Eci::Latin1.encode("Crème brûlée")?, None)?)?;
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn print_qrcode(&mut self, qrcode: QRCode) -> Result<&mut Self> {
+        #[cfg(feature = "codes_2d_image")]
+        if self.options.get_code_2d_rendering() == Code2DRendering::Image
+            || qrcode.structured_append.is_some()
+            || qrcode.eci.is_some()
+        {
+            return self.print_qrcode_image(qrcode);
+        }
+        #[cfg(not(feature = "codes_2d_image"))]
+        if qrcode.structured_append.is_some() {
+            return Err(PrinterError::Input(
+                "QR code structured append requires the `codes_2d_image` feature".to_owned(),
+            ));
+        }
+        #[cfg(not(feature = "codes_2d_image"))]
+        if let Some(eci) = qrcode.eci {
+            return Err(PrinterError::Input(format!(
+                "QR code ECI ({eci}) requires the `codes_2d_image` feature"
+            )));
+        }
+
+        let qrcode = qrcode.fit_paper_width(self.options.get_dots_per_line().into())?;
         let commands = self.protocol.qrcode(&qrcode.data, qrcode.option)?;
         self.command("print qrcode", commands.as_slice())
     }
 
+    #[cfg(feature = "codes_2d_image")]
+    /// Print a QR code as image, whatever the printer options 2D code rendering
+    ///
+    /// The symbol is encoded by the crate, each module is a square of the option size in dots
+    /// (4 if 0). Model 1 is rendered as Model 2. The width includes a quiet zone of 4 modules.
+    ///
+    /// ```
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::ConsoleDriver;
+    ///
+    /// let mut printer = Printer::new(ConsoleDriver::open(false), Protocol::default(), None);
+    /// let option = QRCodeOption::new(QRCodeModel::Model2, 0, QRCodeCorrectionLevel::M).auto_size();
+    /// printer.print_qrcode_image(QRCode::new("https://github.com/fabienbellanger/escpos-rs", Some(option))?)?;
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn print_qrcode_image(&mut self, qrcode: QRCode) -> Result<&mut Self> {
+        let image = CodeImage::from_qrcode_for_paper(qrcode, self.options.get_dots_per_line().into())?;
+        self.print_code_image(image)
+    }
+
+    #[cfg(feature = "codes_2d_image")]
+    /// Print data split in linked QR codes (structured append), as images in sequence
+    ///
+    /// See [`QRCode::structured_append()`].
+    ///
+    /// ```
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::ConsoleDriver;
+    ///
+    /// let mut printer = Printer::new(ConsoleDriver::open(false), Protocol::default(), None);
+    /// let option = QRCodeOption::new(QRCodeModel::Model2, 3, QRCodeCorrectionLevel::M);
+    /// let url = format!("https://example.com/invoice?signature={}", "A1b2".repeat(200));
+    /// printer.qrcode_structured_append(url.as_bytes(), 10, option)?;
+    /// # Ok::<(), escpos::errors::PrinterError>(())
+    /// ```
+    pub fn qrcode_structured_append(
+        &mut self,
+        data: &[u8],
+        max_version: u8,
+        option: QRCodeOption,
+    ) -> Result<&mut Self> {
+        for qrcode in QRCode::structured_append(data, max_version, option)? {
+            self.print_qrcode_image(qrcode)?;
+        }
+        Ok(self)
+    }
+
     #[cfg(feature = "codes_2d")]
     /// Print QR code with default option
     pub fn qrcode(&mut self, data: &str) -> Result<&mut Self> {
-        self.qrcode_builder(data, None)
+        self.print_qrcode(QRCode::new(data, None)?)
     }
 
     #[cfg(feature = "codes_2d")]
     /// Print QR code with option
     pub fn qrcode_option(&mut self, data: &str, option: QRCodeOption) -> Result<&mut Self> {
-        self.qrcode_builder(data, Some(option))
+        self.print_qrcode(QRCode::new(data, Some(option))?)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Print QR code of binary data with default option
+    pub fn qrcode_bytes(&mut self, data: &[u8]) -> Result<&mut Self> {
+        self.print_qrcode(QRCode::from_bytes(data, None)?)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Print QR code of binary data with option
+    pub fn qrcode_bytes_option(&mut self, data: &[u8], option: QRCodeOption) -> Result<&mut Self> {
+        self.print_qrcode(QRCode::from_bytes(data, Some(option))?)
     }
 
     #[cfg(feature = "codes_2d")]
@@ -478,73 +1024,277 @@ impl<D: Driver> Printer<D> {
     }
 
     #[cfg(feature = "codes_2d")]
-    /// PDF417
-    pub fn pdf417_option(&mut self, data: &str, option: Pdf417Option) -> Result<&mut Self> {
-        let code = Pdf417::new(data, option);
+    /// Construct 2D GS1 DataBar from GS1 Application Identifiers
+    pub fn gs1_databar_2d_from_gs1(&mut self, data: &GS1Data, option: GS1DataBar2DOption) -> Result<&mut Self> {
+        let code = GS1DataBar2D::from_gs1(data, option)?;
+        let commands = self.protocol.gs1_databar_2d(&code.data, code.option)?;
+        self.command("print 2D GS1 DataBar", commands.as_slice())
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Print composite symbol
+    pub fn print_composite(&mut self, code: Composite) -> Result<&mut Self> {
+        let commands = self.protocol.composite(&code)?;
+        self.command("print composite symbol", commands.as_slice())
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Construct composite symbol with custom option
+    pub fn composite_option(
+        &mut self,
+        linear_data: &str,
+        composite_data: &str,
+        option: CompositeOption,
+    ) -> Result<&mut Self> {
+        self.print_composite(Composite::new(linear_data, composite_data, option)?)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Construct composite symbol (EAN13 linear component)
+    pub fn composite(&mut self, linear_data: &str, composite_data: &str) -> Result<&mut Self> {
+        self.composite_option(linear_data, composite_data, CompositeOption::default())
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Construct composite symbol from GS1 Application Identifiers
+    pub fn composite_from_gs1(
+        &mut self,
+        linear_data: &GS1Data,
+        composite_data: &GS1Data,
+        option: CompositeOption,
+    ) -> Result<&mut Self> {
+        self.print_composite(Composite::from_gs1(linear_data, composite_data, option)?)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Print PDF417
+    ///
+    /// PDF417 is always printed with commands, whatever the printer options 2D code rendering:
+    /// the crate does not encode PDF417. ESC/POS having no ECI function, PDF417 with an ECI cannot be printed.
+    pub fn print_pdf417(&mut self, code: Pdf417) -> Result<&mut Self> {
+        if let Some(eci) = code.eci {
+            return Err(PrinterError::Input(format!(
+                "PDF417 ECI ({eci}) cannot be printed with commands"
+            )));
+        }
         let commands = self.protocol.pdf417(&code.data, code.option)?;
         self.command("print PDF417", commands.as_slice())
     }
 
+    #[cfg(feature = "codes_2d")]
+    /// PDF417
+    pub fn pdf417_option(&mut self, data: &str, option: Pdf417Option) -> Result<&mut Self> {
+        self.print_pdf417(Pdf417::new(data, option))
+    }
+
     #[cfg(feature = "codes_2d")]
     /// PDF417
     pub fn pdf417(&mut self, data: &str) -> Result<&mut Self> {
-        let code = Pdf417::new(data, Pdf417Option::default());
-        self.pdf417_option(data, code.option)
+        self.pdf417_option(data, Pdf417Option::default())
     }
 
     #[cfg(feature = "codes_2d")]
-    /// MaxiCode
-    pub fn maxi_code_option(&mut self, data: &str, mode: MaxiCodeMode) -> Result<&mut Self> {
-        let code = MaxiCode::new(data, mode);
+    /// PDF417 of binary data with custom option
+    pub fn pdf417_bytes_option(&mut self, data: &[u8], option: Pdf417Option) -> Result<&mut Self> {
+        self.print_pdf417(Pdf417::from_bytes(data, option))
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// PDF417 of binary data
+    pub fn pdf417_bytes(&mut self, data: &[u8]) -> Result<&mut Self> {
+        self.pdf417_bytes_option(data, Pdf417Option::default())
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Print MaxiCode
+    ///
+    /// MaxiCode is always printed with commands, whatever the printer options 2D code rendering.
+    /// ESC/POS having no ECI function, MaxiCode with an ECI cannot be printed.
+    pub fn print_maxi_code(&mut self, code: MaxiCode) -> Result<&mut Self> {
+        if let Some(eci) = code.eci {
+            return Err(PrinterError::Input(format!(
+                "MaxiCode ECI ({eci}) cannot be printed with commands"
+            )));
+        }
         let commands = self.protocol.maxi_code(&code.data, code.mode)?;
         self.command("print MaxiCode", commands.as_slice())
     }
 
     #[cfg(feature = "codes_2d")]
     /// MaxiCode
-    pub fn maxi_code(&mut self, data: &str) -> Result<&mut Self> {
-        let code = MaxiCode::new(data, MaxiCodeMode::default());
-        self.maxi_code_option(data, code.mode)
+    pub fn maxi_code_option(&mut self, data: &str, mode: MaxiCodeMode) -> Result<&mut Self> {
+        self.print_maxi_code(MaxiCode::new(data, mode)?)
     }
 
     #[cfg(feature = "codes_2d")]
-    /// DataMatrix
-    pub fn data_matrix_option(&mut self, data: &str, option: DataMatrixOption) -> Result<&mut Self> {
-        let code = DataMatrix::new(data, option);
-        let commands = self.protocol.data_matrix(&code.data, code.option)?;
-        self.command("print DataMatrix", commands.as_slice())
+    /// MaxiCode
+    pub fn maxi_code(&mut self, data: &str) -> Result<&mut Self> {
+        self.maxi_code_option(data, MaxiCodeMode::default())
     }
 
     #[cfg(feature = "codes_2d")]
-    /// DataMatrix
-    pub fn data_matrix(&mut self, data: &str) -> Result<&mut Self> {
-        let code = DataMatrix::new(data, DataMatrixOption::default());
-        self.data_matrix_option(data, code.option)
+    /// MaxiCode of a structured carrier message (mode 2 or 3)
+    pub fn maxi_code_carrier_message(&mut self, message: &MaxiCodeCarrierMessage) -> Result<&mut Self> {
+        self.print_maxi_code(MaxiCode::from_carrier_message(message))
     }
 
     #[cfg(feature = "codes_2d")]
-    /// Aztec code
-    pub fn aztec_option(&mut self, data: &str, option: AztecOption) -> Result<&mut Self> {
-        let code = Aztec::new(data, option);
-        let commands = self.protocol.aztec(&code.data, code.option)?;
-        self.command("print Aztec", commands.as_slice())
+    /// MaxiCode of binary data with custom option
+    pub fn maxi_code_bytes_option(&mut self, data: &[u8], mode: MaxiCodeMode) -> Result<&mut Self> {
+        self.print_maxi_code(MaxiCode::from_bytes(data, mode)?)
     }
 
     #[cfg(feature = "codes_2d")]
-    /// Aztec code
-    pub fn aztec(&mut self, data: &str) -> Result<&mut Self> {
-        let code = Aztec::new(data, AztecOption::default());
-        self.aztec_option(data, code.option)
+    /// MaxiCode of binary data
+    pub fn maxi_code_bytes(&mut self, data: &[u8]) -> Result<&mut Self> {
+        self.maxi_code_bytes_option(data, MaxiCodeMode::default())
     }
 
-    #[cfg(feature = "graphics")]
-    /// Print image
-    pub fn bit_image_option(&mut self, path: &str, option: BitImageOption) -> Result<&mut Self> {
-        let cmd = self.protocol.cancel();
+    #[cfg(feature = "codes_2d")]
+    /// Print DataMatrix
+    ///
+    /// With the `codes_2d_image` feature, DataMatrix is printed as image if the printer options
+    /// 2D code rendering is `Code2DRendering::Image`. DataMatrix with an ECI is always printed as image,
+    /// ESC/POS having no ECI function.
+    pub fn print_data_matrix(&mut self, code: DataMatrix) -> Result<&mut Self> {
+        #[cfg(feature = "codes_2d_image")]
+        if self.options.get_code_2d_rendering() == Code2DRendering::Image || code.eci.is_some() {
+            return self.print_data_matrix_image(code);
+        }
+        #[cfg(not(feature = "codes_2d_image"))]
+        if let Some(eci) = code.eci {
+            return Err(PrinterError::Input(format!(
+                "DataMatrix ECI ({eci}) requires the `codes_2d_image` feature"
+            )));
+        }
+
+        let commands = self.protocol.data_matrix(&code.data, code.option)?;
+        self.command("print DataMatrix", commands.as_slice())
+    }
+
+    #[cfg(feature = "codes_2d_image")]
+    /// Print DataMatrix as image, whatever the printer options 2D code rendering
+    ///
+    /// The ECC 200 symbol is encoded by the crate, each module is a square of the option size in dots.
+    /// The width includes a quiet zone of 1 module.
+    pub fn print_data_matrix_image(&mut self, code: DataMatrix) -> Result<&mut Self> {
+        self.print_code_image(CodeImage::from_data_matrix(&code)?)
+    }
+
+    #[cfg(feature = "codes_2d_image")]
+    /// Print a 2D code image, without resizing
+    fn print_code_image(&mut self, code: CodeImage) -> Result<&mut Self> {
+        let paper_width = u32::from(self.options.get_dots_per_line());
+        let width = code.image().width();
+        if width > paper_width {
+            return Err(PrinterError::Input(format!(
+                "2D code is too wide for the paper ({width} dots, max {paper_width})"
+            )));
+        }
+        self.bit_image_from_gray_image(
+            code.into_image(),
+            BitImageOption::new(None, None, BitImageSize::Normal)?,
+        )
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// DataMatrix
+    pub fn data_matrix_option(&mut self, data: &str, option: DataMatrixOption) -> Result<&mut Self> {
+        self.print_data_matrix(DataMatrix::new(data, option))
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// DataMatrix
+    pub fn data_matrix(&mut self, data: &str) -> Result<&mut Self> {
+        self.data_matrix_option(data, DataMatrixOption::default())
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// DataMatrix of binary data with custom option
+    pub fn data_matrix_bytes_option(&mut self, data: &[u8], option: DataMatrixOption) -> Result<&mut Self> {
+        self.print_data_matrix(DataMatrix::from_bytes(data, option))
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// DataMatrix of binary data
+    pub fn data_matrix_bytes(&mut self, data: &[u8]) -> Result<&mut Self> {
+        self.data_matrix_bytes_option(data, DataMatrixOption::default())
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Print Aztec code
+    ///
+    /// With the `codes_2d_image` feature, Aztec code is printed as image if the printer options
+    /// 2D code rendering is `Code2DRendering::Image`. Aztec code with an ECI is always printed as image,
+    /// ESC/POS having no ECI function.
+    pub fn print_aztec(&mut self, code: Aztec) -> Result<&mut Self> {
+        #[cfg(feature = "codes_2d_image")]
+        if self.options.get_code_2d_rendering() == Code2DRendering::Image || code.eci.is_some() {
+            return self.print_aztec_image(code);
+        }
+        #[cfg(not(feature = "codes_2d_image"))]
+        if let Some(eci) = code.eci {
+            return Err(PrinterError::Input(format!(
+                "Aztec code ECI ({eci}) requires the `codes_2d_image` feature"
+            )));
+        }
+
+        let commands = self.protocol.aztec(&code.data, code.option)?;
+        self.command("print Aztec", commands.as_slice())
+    }
+
+    #[cfg(feature = "codes_2d_image")]
+    /// Print Aztec code as image, whatever the printer options 2D code rendering
+    ///
+    /// The symbol is encoded by the crate, each module is a square of the option size in dots.
+    /// The width includes a quiet zone of 1 module.
+    pub fn print_aztec_image(&mut self, code: Aztec) -> Result<&mut Self> {
+        self.print_code_image(CodeImage::from_aztec(&code)?)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Aztec code
+    pub fn aztec_option(&mut self, data: &str, option: AztecOption) -> Result<&mut Self> {
+        self.print_aztec(Aztec::new(data, option))
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Aztec code
+    pub fn aztec(&mut self, data: &str) -> Result<&mut Self> {
+        self.aztec_option(data, AztecOption::default())
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Aztec code of binary data with custom option
+    pub fn aztec_bytes_option(&mut self, data: &[u8], option: AztecOption) -> Result<&mut Self> {
+        self.print_aztec(Aztec::from_bytes(data, option))
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Aztec code of binary data
+    pub fn aztec_bytes(&mut self, data: &[u8]) -> Result<&mut Self> {
+        self.aztec_bytes_option(data, AztecOption::default())
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Apply the printer options (bit image mode if the option does not set one and paper width)
+    fn bit_image_option_with_printer_options(&self, option: BitImageOption) -> BitImageOption {
+        let option = option.paper_width(self.options.get_dots_per_line());
+        match option.get_mode() {
+            Some(_) => option,
+            None => option.mode(self.options.get_bit_image_mode()),
+        }
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print image
+    pub fn bit_image_option(&mut self, path: &str, option: BitImageOption) -> Result<&mut Self> {
+        let cmd = self.protocol.cancel();
         self.command("cancel data", &[cmd])?;
 
-        let cmd = self.protocol.bit_image(path, option)?;
-        self.command("print bit image", &[cmd])
+        let option = self.bit_image_option_with_printer_options(option);
+        let cmds = self.protocol.bit_image(path, option)?;
+        self.command("print bit image", &cmds)
     }
 
     #[cfg(feature = "graphics")]
@@ -559,8 +1309,9 @@ impl<D: Driver> Printer<D> {
         let cmd = self.protocol.cancel();
         self.command("cancel data", &[cmd])?;
 
-        let cmd = self.protocol.bit_image_from_bytes(bytes, option)?;
-        self.command("print bit image from bytes", &[cmd])
+        let option = self.bit_image_option_with_printer_options(option);
+        let cmds = self.protocol.bit_image_from_bytes(bytes, option)?;
+        self.command("print bit image from bytes", &cmds)
     }
 
     #[cfg(feature = "graphics")]
@@ -569,25 +1320,416 @@ impl<D: Driver> Printer<D> {
         self.bit_image_from_bytes_option(bytes, BitImageOption::default())
     }
 
-    // #[cfg(feature = "graphics")]
-    // /// Print image
-    // fn _image(&mut self, path: &str) -> Result<&mut Self> {
-    //     let cmd = self.protocol.graphic_density(GraphicDensity::Low);
-    //     self.command("set graphic density", cmd)?;
-    //
-    //     let cmd = self.protocol.graphic_data(path)?;
-    //     self.command("set graphic data", cmd)?;
-    //
-    //     // Print
-    //     let cmd = self.protocol.graphic_print();
-    //     self.command("print graphic", cmd)
-    // }
+    #[cfg(feature = "graphics")]
+    /// Print image from `DynamicImage`
+    pub fn bit_image_from_dynamic_image(&mut self, img: DynamicImage, option: BitImageOption) -> Result<&mut Self> {
+        let option = self.bit_image_option_with_printer_options(option);
+        self.print_bit_image(&BitImage::from_dynamic_image(img, option)?)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print image from `GrayImage`
+    pub fn bit_image_from_gray_image(&mut self, img: GrayImage, option: BitImageOption) -> Result<&mut Self> {
+        let option = self.bit_image_option_with_printer_options(option);
+        self.print_bit_image(&BitImage::from_gray_image(img, option)?)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print image from a 1-bit packed buffer (see [`BitImage::from_packed_bits`])
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     let signature = vec![0b1010_1010; 16 * 8];
+    ///     Printer::new(driver, Protocol::default(), None)
+    ///         .init()?
+    ///         .bit_image_from_packed_bits(&signature, 128, 8, BitImageOption::default())?
+    ///         .print_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn bit_image_from_packed_bits(
+        &mut self,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        option: BitImageOption,
+    ) -> Result<&mut Self> {
+        let option = self.bit_image_option_with_printer_options(option);
+        self.print_bit_image(&BitImage::from_packed_bits(data, width, height, option)?)
+    }
+
+    #[cfg(feature = "ttf")]
+    /// Print text rendered with a TrueType or OpenType font
+    ///
+    /// Lines are wrapped to the option width, or to the printer options paper width if not set.
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     let font = TextFont::from_path("./resources/fonts/DejaVuSansMono.ttf")?;
+    ///     Printer::new(driver, Protocol::default(), None)
+    ///         .init()?
+    ///         .text_image(&font, "Thank you!", TextImageOption::new(48.0, None, JustifyMode::CENTER)?)?
+    ///         .print_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn text_image(&mut self, font: &TextFont, text: &str, option: TextImageOption) -> Result<&mut Self> {
+        let option = option.default_width(u32::from(self.options.get_dots_per_line()));
+        let image = TextImage::new(font, text, option)?.into_image();
+        self.bit_image_from_gray_image(image, BitImageOption::new(None, None, BitImageSize::Normal)?)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print a [`BitImage`]
+    ///
+    /// The image is already built, so the printer options paper width is not applied.
+    /// The printer options mode is used if the image option does not set one.
+    pub fn print_bit_image(&mut self, bit_image: &BitImage) -> Result<&mut Self> {
+        let cmd = self.protocol.cancel();
+        self.command("cancel data", &[cmd])?;
+
+        let mode = bit_image.mode().unwrap_or(self.options.get_bit_image_mode());
+        let cmds = self.protocol.build_bit_image(bit_image, mode)?;
+        self.command("print bit image", &cmds)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Send the instructions and read a graphics response block before the status timeout
+    ///
+    /// Other responses received meanwhile are kept, see [`take_responses`](Self::take_responses).
+    fn read_graphics_response(&mut self, cmd: Command) -> Result<Vec<u8>> {
+        let label = "transmit graphics information";
+        self.command(label, &[cmd])?.send_status()?;
+
+        let deadline = Instant::now() + self.options.get_status_timeout();
+        self.read_response(deadline, graphics_response)?
+            .ok_or_else(|| PrinterError::InvalidResponse(format!("{label} timeout")))
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Store an image in NV or download graphics memory
+    ///
+    /// An existing graphic with the same key is replaced.
+    /// NV memory has a limited number of write cycles, prefer [`store_graphic_if_missing`](Self::store_graphic_if_missing).
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     let key = GraphicKey::try_from("LG")?;
+    ///     Printer::new(driver, Protocol::default(), None)
+    ///         .init()?
+    ///         .store_graphic(GraphicMemory::Download, key, "./resources/images/rust-logo-small.png", BitImageOption::default())?
+    ///         .print_stored_graphic(GraphicMemory::Download, key, GraphicSize::Normal, GraphicSize::Normal)?
+    ///         .print_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn store_graphic(
+        &mut self,
+        memory: GraphicMemory,
+        key: GraphicKey,
+        path: &str,
+        option: BitImageOption,
+    ) -> Result<&mut Self> {
+        let option = option.paper_width(self.options.get_dots_per_line());
+        let bit_image = BitImage::new(path, option)?;
+        let cmd = self.protocol.stored_graphic_define(memory, key, &bit_image)?;
+        self.command(&format!("define {memory} {key}"), &[cmd])
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Store an image in NV or download graphics memory if the key is not already defined
+    ///
+    /// The key code list is read from the printer, so pending instructions are sent first.
+    pub fn store_graphic_if_missing(
+        &mut self,
+        memory: GraphicMemory,
+        key: GraphicKey,
+        path: &str,
+        option: BitImageOption,
+    ) -> Result<&mut Self> {
+        if self.stored_graphic_keys(memory)?.contains(&key) {
+            return Ok(self);
+        }
+
+        self.store_graphic(memory, key, path, option)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print a graphic stored in NV or download graphics memory
+    pub fn print_stored_graphic(
+        &mut self,
+        memory: GraphicMemory,
+        key: GraphicKey,
+        width_size: GraphicSize,
+        height_size: GraphicSize,
+    ) -> Result<&mut Self> {
+        let cmd = self.protocol.stored_graphic_print(memory, key, width_size, height_size);
+        self.command(&format!("print {memory} {key}"), &[cmd])
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Delete a graphic stored in NV or download graphics memory
+    pub fn delete_stored_graphic(&mut self, memory: GraphicMemory, key: GraphicKey) -> Result<&mut Self> {
+        let cmd = self.protocol.stored_graphic_delete(memory, key);
+        self.command(&format!("delete {memory} {key}"), &[cmd])
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Delete all graphics stored in NV or download graphics memory
+    pub fn delete_all_stored_graphics(&mut self, memory: GraphicMemory) -> Result<&mut Self> {
+        let cmd = self.protocol.stored_graphics_delete_all(memory);
+        self.command(&format!("delete all {memory}"), &[cmd])
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Get the key codes of the graphics stored in NV or download graphics memory
+    ///
+    /// Pending instructions are sent to the printer before the request.
+    pub fn stored_graphic_keys(&mut self, memory: GraphicMemory) -> Result<Vec<GraphicKey>> {
+        let cmd = self.protocol.stored_graphics_key_codes(memory);
+        let (mut keys, mut next) = parse_key_codes(&self.read_graphics_response(cmd)?)?;
+
+        // The printer waits for an acknowledgement before sending the next block
+        while next {
+            let (mut block, more) = parse_key_codes(&self.read_graphics_response(vec![ACK])?)?;
+            keys.append(&mut block);
+            next = more;
+        }
+
+        Ok(keys)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Get the remaining capacity (in bytes) of NV or download graphics memory
+    ///
+    /// Pending instructions are sent to the printer before the request.
+    pub fn stored_graphics_remaining_capacity(&mut self, memory: GraphicMemory) -> Result<u32> {
+        let cmd = self.protocol.stored_graphics_remaining_capacity(memory);
+        parse_capacity(&self.read_graphics_response(cmd)?)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Get the capacity (in bytes) of NV graphics memory
+    ///
+    /// Pending instructions are sent to the printer before the request.
+    pub fn nv_graphics_capacity(&mut self) -> Result<u32> {
+        let cmd = self.protocol.nv_graphics_capacity();
+        parse_capacity(&self.read_graphics_response(cmd)?)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print graphic (`GS ( L` / `GS 8 L`)
+    ///
+    /// The image is stored in the print buffer (one command per color plane) and printed.
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     Printer::new(driver, Protocol::default(), None)
+    ///         .init()?
+    ///         .graphic("./resources/images/rust-logo-small.png", GraphicOption::default())?
+    ///         .print_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn graphic(&mut self, path: &str, option: GraphicOption) -> Result<&mut Self> {
+        let cmd = self.protocol.graphic_density(option.density);
+        self.command("set graphic density", &[cmd])?;
+
+        let option = option.paper_width(self.options.get_dots_per_line());
+        let cmds = self.protocol.graphic_data(path, option)?;
+        self.command("store graphic data", &cmds)?;
+
+        let cmd = self.protocol.graphic_print();
+        self.command("print graphic", &[cmd])
+    }
+}
+
+/// Automatic status back listener
+///
+/// Reads the printer responses one byte at a time and separates the automatic status back packets
+/// from other responses (`DLE EOT`, `GS r`, `GS I`, etc.).
+/// Built-in drivers can be cloned to read from the printer connection: the listener is polled
+/// by the thread owning the printer or [spawned](Self::spawn) in a background thread.
+///
+/// ```no_run
+/// use escpos::printer::{AsbListener, Printer};
+/// use escpos::utils::*;
+/// use escpos::{driver::*, errors::Result};
+///
+/// fn main() -> Result<()> {
+///     let driver = NetworkDriver::open("192.168.1.248", 9100, None)?;
+///     Printer::new(driver.clone(), Protocol::default(), None)
+///         .automatic_status_back(AsbEvents::all())?
+///         .print()?;
+///
+///     AsbListener::new(driver).listen(|response| {
+///         if let PrinterResponse::AutomaticStatus(status) = response {
+///             println!("{status}");
+///         }
+///         true
+///     })
+/// }
+/// ```
+pub struct AsbListener<D: Driver> {
+    driver: D,
+    parser: PrinterResponseParser,
+}
+
+impl<D: Driver> AsbListener<D> {
+    /// Create a new `AsbListener`
+    pub fn new(driver: D) -> Self {
+        Self {
+            driver,
+            parser: PrinterResponseParser::new(),
+        }
+    }
+
+    /// Read the next response, `None` if the driver has no more data
+    ///
+    /// A read waits at most the driver timeout.
+    pub fn poll(&mut self) -> Result<Option<PrinterResponse>> {
+        let mut byte = [0; 1];
+        while self.driver.read(&mut byte)? == 1 {
+            if let Some(response) = self.parser.push(byte[0])? {
+                return Ok(Some(response));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Call `callback` for each response until it returns `false` or a read fails
+    pub fn listen<F>(&mut self, mut callback: F) -> Result<()>
+    where
+        F: FnMut(PrinterResponse) -> bool,
+    {
+        loop {
+            match self.poll()? {
+                Some(response) => {
+                    if !callback(response) {
+                        return Ok(());
+                    }
+                }
+                None => sleep(STATUS_READ_INTERVAL),
+            }
+        }
+    }
+}
+
+impl<D: Driver + Send + 'static> AsbListener<D> {
+    /// Read the responses in a background thread
+    ///
+    /// The thread stops after a read error, which is sent, or when the receiver is dropped.
+    pub fn spawn(mut self) -> Receiver<Result<PrinterResponse>> {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || loop {
+            let response = match self.poll() {
+                Ok(Some(response)) => Ok(response),
+                Ok(None) => {
+                    sleep(STATUS_READ_INTERVAL);
+                    continue;
+                }
+                Err(err) => Err(err),
+            };
+            let stop = response.is_err();
+            if sender.send(response).is_err() || stop {
+                break;
+            }
+        });
+
+        receiver
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::driver::ConsoleDriver;
+    use crate::driver::*;
+    use crate::io::driver::read_until_timeout;
+    use std::{
+        collections::VecDeque,
+        io::{self, Read, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+    };
+
+    /// Scripted read: response bytes or read error
+    type ScriptedRead = std::result::Result<Vec<u8>, io::ErrorKind>;
+
+    /// Driver recording written data and replaying scripted responses or read errors, one read at a time
+    #[derive(Clone, Default)]
+    struct ScriptedDriver {
+        written: Arc<Mutex<Vec<u8>>>,
+        reads: Arc<Mutex<VecDeque<ScriptedRead>>>,
+    }
+
+    impl ScriptedDriver {
+        fn new(reads: &[&[u8]]) -> Self {
+            Self::with_errors(reads.iter().map(|read| Ok(read.to_vec())).collect())
+        }
+
+        fn with_errors(reads: Vec<ScriptedRead>) -> Self {
+            Self {
+                written: Arc::default(),
+                reads: Arc::new(Mutex::new(reads.into())),
+            }
+        }
+    }
+
+    impl Driver for ScriptedDriver {
+        fn name(&self) -> String {
+            "scripted".to_owned()
+        }
+
+        fn write(&self, data: &[u8]) -> Result<()> {
+            self.written.lock().unwrap().extend_from_slice(data);
+            Ok(())
+        }
+
+        fn read(&self, buf: &mut [u8]) -> Result<usize> {
+            let mut reads = self.reads.lock().unwrap();
+            let mut read = match reads.pop_front() {
+                Some(Ok(read)) => read,
+                // Read errors are converted like the built-in drivers do
+                Some(Err(kind)) => return read_until_timeout(Err(kind.into())),
+                None => return Ok(0),
+            };
+            let size = read.len().min(buf.len());
+            buf[..size].copy_from_slice(&read[..size]);
+            if size < read.len() {
+                reads.push_front(Ok(read.split_off(size)));
+            }
+            Ok(size)
+        }
+
+        fn flush(&self) -> Result<()> {
+            Ok(())
+        }
+    }
 
     #[test]
     fn test_command() {
@@ -605,4 +1747,604 @@ mod tests {
 
         assert_eq!(printer.instructions, expected);
     }
+
+    #[test]
+    fn test_write_with_user_defined_characters() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        let character = UserDefinedCharacter::new('€', b'E', Font::A, &[vec![true]]).unwrap();
+        printer.define_user_character(character).unwrap().write("1€E").unwrap();
+        assert_eq!(
+            printer.instructions[1],
+            Instruction::new("text", &[vec![49, 27, 37, 1, 69, 27, 37, 0, 69]], None)
+        );
+
+        printer
+            .cancel_user_defined_character(b'E')
+            .unwrap()
+            .write("1€")
+            .unwrap();
+        assert_eq!(
+            printer.instructions[3],
+            Instruction::new("text", &[vec![49, 226, 130, 172]], None)
+        );
+    }
+
+    #[test]
+    fn test_write_with_user_defined_characters_state() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        let character = UserDefinedCharacter::new('€', b'E', Font::B, &[vec![true]]).unwrap();
+        printer
+            .font(Font::B)
+            .unwrap()
+            .define_user_character(character)
+            .unwrap()
+            .user_defined_characters(true)
+            .unwrap()
+            .write("€")
+            .unwrap();
+        assert_eq!(
+            printer.instructions[1],
+            Instruction::new(
+                "define user-defined character €",
+                &[vec![27, 38, 3, 69, 69, 1, 128, 0, 0]],
+                None
+            )
+        );
+        assert_eq!(printer.instructions[3], Instruction::new("text", &[vec![69]], None));
+
+        // `ESC ?` with font A selected does not cancel the font B glyph
+        printer
+            .font(Font::A)
+            .unwrap()
+            .user_defined_characters(false)
+            .unwrap()
+            .write("€")
+            .unwrap();
+        assert_eq!(
+            printer.instructions[6],
+            Instruction::new("text", &[vec![27, 77, 1, 27, 37, 1, 69, 27, 37, 0, 27, 77, 0]], None)
+        );
+        printer.cancel_user_defined_character(b'E').unwrap().write("€").unwrap();
+        assert_eq!(
+            printer.instructions[8],
+            Instruction::new("text", &[vec![27, 77, 1, 27, 37, 1, 69, 27, 37, 0, 27, 77, 0]], None)
+        );
+    }
+
+    #[cfg(feature = "codes_2d")]
+    #[test]
+    fn test_print_qrcode_auto_size() {
+        let mut options = PrinterOptions::default();
+        options.dots_per_line(384);
+        let mut printer = Printer::new(ConsoleDriver::open(false), Protocol::default(), Some(options));
+
+        // Version 1, 21 modules of 15 dots
+        let option = QRCodeOption::new(QRCodeModel::Model2, 4, QRCodeCorrectionLevel::M).auto_size();
+        printer
+            .print_qrcode(QRCode::new("1234", Some(option)).unwrap())
+            .unwrap();
+        assert_eq!(printer.instructions[0].commands[1], vec![29, 40, 107, 3, 0, 49, 67, 15]);
+
+        // Version 5, 37 modules of 15 dots
+        let option = QRCodeOption::new(QRCodeModel::Model2, 15, QRCodeCorrectionLevel::L);
+        let data = "a".repeat(100);
+        assert!(printer.print_qrcode(QRCode::new(&data, Some(option)).unwrap()).is_err());
+    }
+
+    #[cfg(feature = "codes_2d_image")]
+    #[test]
+    fn test_print_2d_code_image() {
+        let mut options = PrinterOptions::default();
+        options.dots_per_line(384);
+        options.code_2d_rendering(Code2DRendering::Image);
+        let mut printer = Printer::new(ConsoleDriver::open(false), Protocol::default(), Some(options));
+
+        // Version 1, 21 modules and quiet zone of 8 modules, 4 dots per module: 116 dots (15 bytes)
+        let option = QRCodeOption::new(QRCodeModel::Model2, 4, QRCodeCorrectionLevel::M);
+        printer
+            .print_qrcode(QRCode::new("1234", Some(option)).unwrap())
+            .unwrap();
+        assert_eq!(printer.instructions[1].name, "print bit image");
+        assert_eq!(printer.instructions[1].commands[0][..6], [29, 118, 48, 0, 15, 0]);
+
+        // Automatic size: 13 dots per module, 377 dots (48 bytes)
+        let option = QRCodeOption::new(QRCodeModel::Model1, 0, QRCodeCorrectionLevel::M).auto_size();
+        printer
+            .print_qrcode(QRCode::new("1234", Some(option)).unwrap())
+            .unwrap();
+        assert_eq!(printer.instructions[3].commands[0][..6], [29, 118, 48, 0, 48, 0]);
+
+        // 10 x 10 modules and quiet zone of 2 modules, 4 dots per module: 48 dots (6 bytes)
+        let option = DataMatrixOption::new(DataMatrixType::Square(0), 4).unwrap();
+        printer.print_data_matrix(DataMatrix::new("123456", option)).unwrap();
+        assert_eq!(printer.instructions[5].commands[0][..6], [29, 118, 48, 0, 6, 0]);
+
+        let option = DataMatrixOption::new(DataMatrixType::Square(144), 16).unwrap();
+        assert!(printer.print_data_matrix(DataMatrix::new("123456", option)).is_err());
+
+        // Compact Aztec code of 1 layer: 15 modules and quiet zone of 1 module, 3 dots per module: 51 dots (7 bytes)
+        let option = AztecOption::new(AztecMode::Compact(0), 3, 23).unwrap();
+        printer.print_aztec(Aztec::new("1234", option)).unwrap();
+        assert_eq!(printer.instructions.last().unwrap().name, "print bit image");
+        assert_eq!(
+            printer.instructions.last().unwrap().commands[0][..6],
+            [29, 118, 48, 0, 7, 0]
+        );
+
+        // PDF417 and MaxiCode are printed with commands
+        printer.pdf417("1234").unwrap();
+        assert_eq!(printer.instructions.last().unwrap().name, "print PDF417");
+        printer.maxi_code("1234").unwrap();
+        assert_eq!(printer.instructions.last().unwrap().name, "print MaxiCode");
+
+        // Image per call
+        let mut printer = Printer::new(ConsoleDriver::open(false), Protocol::default(), None);
+        printer.qrcode("1234").unwrap();
+        assert_eq!(printer.instructions[0].name, "print qrcode");
+        printer.print_qrcode_image(QRCode::new("1234", None).unwrap()).unwrap();
+        assert_eq!(printer.instructions[2].name, "print bit image");
+    }
+
+    #[cfg(feature = "codes_2d_image")]
+    #[test]
+    fn test_qrcode_structured_append() {
+        let mut printer = Printer::new(ConsoleDriver::open(false), Protocol::default(), None);
+        let option = QRCodeOption::new(QRCodeModel::Model2, 2, QRCodeCorrectionLevel::L);
+        printer.qrcode_structured_append(&[b'a'; 100], 1, option).unwrap();
+
+        // 7 symbols of version 1 and quiet zone, 2 dots per module: 58 dots (8 bytes)
+        assert_eq!(printer.instructions.len(), 14);
+        assert!(printer.instructions.chunks(2).all(|instructions| {
+            instructions[1].name == "print bit image" && instructions[1].commands[0][..6] == [29, 118, 48, 0, 8, 0]
+        }));
+    }
+
+    #[cfg(all(feature = "codes_2d", not(feature = "codes_2d_image")))]
+    #[test]
+    fn test_qrcode_structured_append_without_image() {
+        let mut printer = Printer::new(ConsoleDriver::open(false), Protocol::default(), None);
+        let option = QRCodeOption::new(QRCodeModel::Model2, 2, QRCodeCorrectionLevel::L);
+        let qrcodes = QRCode::structured_append(&[b'a'; 100], 1, option).unwrap();
+        assert!(printer.print_qrcode(qrcodes.into_iter().next().unwrap()).is_err());
+    }
+
+    #[cfg(feature = "codes_2d")]
+    #[test]
+    fn test_print_2d_code_eci() {
+        let mut printer = Printer::new(ConsoleDriver::open(false), Protocol::default(), None);
+
+        // ESC/POS 2D code functions have no ECI designator
+        let pdf417 = Pdf417::with_eci("Café", Eci::Latin1, Pdf417Option::default()).unwrap();
+        assert!(printer.print_pdf417(pdf417).is_err());
+        let maxi_code = MaxiCode::with_eci("Café", Eci::Latin1, MaxiCodeMode::default()).unwrap();
+        assert!(printer.print_maxi_code(maxi_code).is_err());
+        assert!(printer.instructions.is_empty());
+
+        // Data in a character set without ECI designator
+        let data = Eci::Latin1.encode("Café").unwrap();
+        printer
+            .print_pdf417(Pdf417::from_bytes(&data, Pdf417Option::default()))
+            .unwrap();
+        assert_eq!(printer.instructions[0].name, "print PDF417");
+
+        let qrcode = QRCode::with_eci("Café", Eci::Latin1, None).unwrap();
+        let data_matrix = DataMatrix::with_eci("Café", Eci::Latin1, DataMatrixOption::default()).unwrap();
+        let aztec = Aztec::with_eci("Café", Eci::Latin1, AztecOption::default()).unwrap();
+        #[cfg(feature = "codes_2d_image")]
+        {
+            // QR codes, DataMatrix and Aztec codes with an ECI are printed as images
+            printer.print_qrcode(qrcode).unwrap();
+            assert_eq!(printer.instructions.last().unwrap().name, "print bit image");
+            printer.print_data_matrix(data_matrix).unwrap();
+            assert_eq!(printer.instructions.last().unwrap().name, "print bit image");
+            printer.print_aztec(aztec).unwrap();
+            assert_eq!(printer.instructions.last().unwrap().name, "print bit image");
+        }
+        #[cfg(not(feature = "codes_2d_image"))]
+        {
+            assert!(printer.print_qrcode(qrcode).is_err());
+            assert!(printer.print_data_matrix(data_matrix).is_err());
+            assert!(printer.print_aztec(aztec).is_err());
+        }
+    }
+
+    #[cfg(feature = "barcodes")]
+    #[test]
+    fn test_barcode_printer_options() {
+        let option = |width| BarcodeOption::new(width, BarcodeHeight::S, BarcodeFont::A, BarcodePosition::Below);
+        let barcode = |width| Barcode::new(BarcodeSystem::EAN8, "9857458", option(width)).unwrap();
+
+        let mut printer = Printer::new(ConsoleDriver::open(false), Protocol::default(), None);
+        assert!(printer.barcode(barcode(BarcodeWidth::Dots(6))).is_ok());
+        assert!(printer.barcode(barcode(BarcodeWidth::Dots(7))).is_err());
+
+        // 67 modules of 8 dots fit 576 dots but not 512 dots
+        let mut options = PrinterOptions::default();
+        options.barcode_module_widths(2..=68);
+        let mut printer = Printer::new(ConsoleDriver::open(false), Protocol::default(), Some(options.clone()));
+        assert!(printer.barcode(barcode(BarcodeWidth::XS)).is_err());
+        assert!(printer.barcode(barcode(BarcodeWidth::Dots(8))).is_err());
+
+        options.dots_per_line(576);
+        let mut printer = Printer::new(ConsoleDriver::open(false), Protocol::default(), Some(options));
+        assert!(printer.barcode(barcode(BarcodeWidth::Dots(8))).is_ok());
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_bit_image_from_packed_bits() {
+        let data = [0xFF, 0x00];
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        printer
+            .bit_image_from_packed_bits(&data, 8, 2, BitImageOption::default())
+            .unwrap();
+        assert_eq!(
+            printer.instructions[1],
+            Instruction::new("print bit image", &[vec![29, 118, 48, 0, 1, 0, 2, 0, 0xFF, 0x00]], None)
+        );
+
+        // Printer options mode
+        let mut options = PrinterOptions::default();
+        options.bit_image_mode(BitImageMode::Column8DotDoubleDensity);
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(options));
+        printer
+            .bit_image_from_packed_bits(&data, 8, 2, BitImageOption::default())
+            .unwrap();
+        assert_eq!(
+            printer.instructions[1],
+            Instruction::new(
+                "print bit image",
+                &[
+                    vec![27, 51, 8],
+                    [vec![27, 42, 1, 8, 0], vec![0x80; 8], vec![10]].concat(),
+                    vec![27, 50]
+                ],
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn test_query_status() {
+        let driver = ScriptedDriver::new(&[&[0b0001_0110], &[], &[0b0111_0010, 0b0001_0010]]);
+        let mut printer = Printer::new(driver.clone(), Protocol::default(), None);
+        printer.init().unwrap();
+        let state = printer
+            .query_status(&[
+                RealTimeStatusRequest::Printer,
+                RealTimeStatusRequest::RollPaperSensor,
+                RealTimeStatusRequest::ErrorCause,
+            ])
+            .unwrap();
+
+        assert_eq!(
+            *driver.written.lock().unwrap(),
+            vec![27, 64, 16, 4, 1, 0, 16, 4, 4, 0, 16, 4, 3, 0]
+        );
+        assert!(state.is_online());
+        assert!(!state.is_paper_adequate());
+        assert_eq!(state.error_cause, Some(ErrorCause::default()));
+        assert_eq!(state.offline_cause, None);
+    }
+
+    #[test]
+    fn test_query_status_errors() {
+        let mut options = PrinterOptions::default();
+        options.status_timeout(Duration::from_millis(30));
+
+        let driver = ScriptedDriver::new(&[&[0b0001_0110]]);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(options.clone()));
+        let result = printer.query_status(&[RealTimeStatusRequest::Printer, RealTimeStatusRequest::OfflineCause]);
+        assert!(matches!(result, Err(PrinterError::InvalidResponse(message)) if message.contains("1 of 2 bytes")));
+
+        let driver = ScriptedDriver::new(&[&[0b0001_0110, 0xFF]]);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(options));
+        let result = printer.query_status(&[RealTimeStatusRequest::Printer, RealTimeStatusRequest::OfflineCause]);
+        assert!(matches!(result, Err(PrinterError::InvalidResponse(message)) if message.contains("11111111")));
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_stored_graphics_responses() {
+        // A real-time status, a stray block and an ASB packet are received before the key code lists
+        let driver = ScriptedDriver::with_errors(vec![
+            Ok(vec![0b0001_0010]),
+            Ok(b"_TM\0".to_vec()),
+            Err(io::ErrorKind::TimedOut),
+            Ok(vec![0b0001_0000, 0, 0, 0]),
+            Ok(vec![0x37, 0x72, 0x41, b'L', b'G', 0]),
+            Ok(vec![0x37, 0x72, 0x40, b'A', b'1', 0]),
+        ]);
+        let mut printer = Printer::new(driver.clone(), Protocol::default(), None);
+        let keys = printer.stored_graphic_keys(GraphicMemory::NV).unwrap();
+        assert_eq!(
+            keys,
+            vec![
+                GraphicKey::new(b'L', b'G').unwrap(),
+                GraphicKey::new(b'A', b'1').unwrap()
+            ]
+        );
+        assert!(driver.written.lock().unwrap().ends_with(&[ACK]));
+
+        let responses = printer.take_responses();
+        assert_eq!(responses.len(), 3);
+        assert_eq!(responses[0], PrinterResponse::RealTimeStatus(0b0001_0010));
+        assert_eq!(responses[1], PrinterResponse::Block(b"_TM\0".to_vec()));
+        assert!(matches!(responses[2], PrinterResponse::AutomaticStatus(_)));
+
+        let mut options = PrinterOptions::default();
+        options.status_timeout(Duration::from_millis(30));
+        let driver = ScriptedDriver::new(&[&[0x37, 0x30, b'1']]);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(options));
+        assert!(matches!(
+            printer.nv_graphics_capacity(),
+            Err(PrinterError::InvalidResponse(message)) if message == "transmit graphics information timeout"
+        ));
+    }
+
+    #[test]
+    fn test_query_status_read_timeout() {
+        // Driver reads timing out before the responses are received
+        let driver = ScriptedDriver::with_errors(vec![
+            Err(io::ErrorKind::TimedOut),
+            Ok(vec![0b0001_0110]),
+            Err(io::ErrorKind::WouldBlock),
+            Ok(vec![0b0001_0010]),
+        ]);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        let state = printer
+            .query_status(&[RealTimeStatusRequest::Printer, RealTimeStatusRequest::OfflineCause])
+            .unwrap();
+        assert!(state.is_online());
+
+        let mut options = PrinterOptions::default();
+        options.status_timeout(Duration::from_millis(30));
+        let driver = ScriptedDriver::with_errors(vec![Err(io::ErrorKind::TimedOut)]);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(options));
+        let result = printer.query_status(&[RealTimeStatusRequest::Printer]);
+        assert!(matches!(result, Err(PrinterError::InvalidResponse(message)) if message.contains("timeout")));
+
+        let driver = ScriptedDriver::with_errors(vec![Err(io::ErrorKind::ConnectionReset)]);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        assert!(matches!(
+            printer.query_status(&[RealTimeStatusRequest::Printer]),
+            Err(PrinterError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_query_status_with_automatic_status_back() {
+        let driver = ScriptedDriver::new(&[&[0b0001_0000, 0, 0b0000_0011], &[0, 0b0001_0110]]);
+        let mut printer = Printer::new(driver.clone(), Protocol::default(), None);
+        let state = printer
+            .automatic_status_back(AsbEvents::all())
+            .unwrap()
+            .query_status(&[RealTimeStatusRequest::Printer])
+            .unwrap();
+
+        assert_eq!(*driver.written.lock().unwrap(), vec![29, 97, 79, 16, 4, 1, 0]);
+        assert!(state.is_online());
+        let responses = printer.take_responses();
+        assert_eq!(responses.len(), 1);
+        assert!(
+            matches!(responses[0], PrinterResponse::AutomaticStatus(status) if status.online && !status.paper_adequate)
+        );
+        assert!(printer.take_responses().is_empty());
+    }
+
+    #[test]
+    fn test_printer_id() {
+        let driver = ScriptedDriver::new(&[&[0b0001_0110, b'_', b'T', b'M'], &[b'-', b'T', b'2', b'0', 0]]);
+        let mut printer = Printer::new(driver.clone(), Protocol::default(), None);
+        let id = printer.printer_id(PrinterIdRequest::ModelName).unwrap();
+
+        assert_eq!(*driver.written.lock().unwrap(), vec![29, 73, 67]);
+        assert_eq!(id, PrinterId::ModelName("TM-T20".to_string()));
+        assert_eq!(
+            printer.take_responses(),
+            vec![PrinterResponse::RealTimeStatus(0b0001_0110)]
+        );
+    }
+
+    #[test]
+    fn test_identify() {
+        let driver = ScriptedDriver::new(&[
+            b"_EPSON\0",
+            b"_TM-T88V\0",
+            b"_ABC1234\0",
+            b"_30.01 ESC/POS\0",
+            &[0b0000_0010],
+        ]);
+        let mut printer = Printer::new(driver.clone(), Protocol::default(), None);
+        let identity = printer.identify().unwrap();
+
+        assert_eq!(
+            *driver.written.lock().unwrap(),
+            vec![29, 73, 66, 29, 73, 67, 29, 73, 68, 29, 73, 65, 29, 73, 2]
+        );
+        assert_eq!(
+            identity,
+            PrinterIdentity {
+                manufacturer: Some("EPSON".to_string()),
+                model_name: Some("TM-T88V".to_string()),
+                serial_number: Some("ABC1234".to_string()),
+                firmware: Some("30.01 ESC/POS".to_string()),
+                type_id: Some(PrinterTypeId {
+                    multi_byte_characters: false,
+                    autocutter: true,
+                }),
+            }
+        );
+        assert_eq!(
+            identity.to_string(),
+            "EPSON TM-T88V (serial number: ABC1234, firmware: 30.01 ESC/POS)"
+        );
+
+        // The queries stop at the first one without response
+        let mut options = PrinterOptions::default();
+        options.status_timeout(Duration::from_millis(30));
+        let driver = ScriptedDriver::new(&[b"_EPSON\0", b"_TM-T20\0"]);
+        let mut printer = Printer::new(driver.clone(), Protocol::default(), Some(options.clone()));
+        let identity = printer.identify().unwrap();
+        assert_eq!(
+            *driver.written.lock().unwrap(),
+            vec![29, 73, 66, 29, 73, 67, 29, 73, 68]
+        );
+        assert_eq!(identity.model_name.as_deref(), Some("TM-T20"));
+        assert_eq!(identity.to_string(), "EPSON TM-T20");
+        assert_eq!(
+            (identity.serial_number, identity.firmware, identity.type_id),
+            (None, None, None)
+        );
+
+        let mut printer = Printer::new(ScriptedDriver::new(&[]), Protocol::default(), Some(options.clone()));
+        let identity = printer.identify().unwrap();
+        assert_eq!(identity, PrinterIdentity::default());
+        assert_eq!(identity.to_string(), "unknown printer");
+
+        let mut printer = Printer::new(ScriptedDriver::new(&[b"EPSON"]), Protocol::default(), Some(options));
+        assert!(printer.identify().is_err());
+    }
+
+    #[test]
+    fn test_transmit_status() {
+        let driver = ScriptedDriver::new(&[&[0b0000_0001]]);
+        let mut printer = Printer::new(driver.clone(), Protocol::default(), None);
+        let status = printer.transmit_status(TransmitStatusRequest::Drawer).unwrap();
+
+        assert_eq!(*driver.written.lock().unwrap(), vec![29, 114, 2]);
+        assert_eq!(
+            status,
+            TransmitStatus::Drawer {
+                kick_out_connector_pin3_low: false
+            }
+        );
+
+        let mut options = PrinterOptions::default();
+        options.status_timeout(Duration::from_millis(30));
+        let driver = ScriptedDriver::new(&[b"_A\0"]);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(options.clone()));
+        assert!(printer.transmit_status(TransmitStatusRequest::Ink).is_err());
+
+        let driver = ScriptedDriver::new(&[]);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(options));
+        let result = printer.transmit_status(TransmitStatusRequest::Ink);
+        assert!(matches!(result, Err(PrinterError::InvalidResponse(message)) if message.contains("timeout")));
+    }
+
+    #[test]
+    fn test_wait_for_process() {
+        let driver = ScriptedDriver::new(&[&[0x37, 0x22, b'0', b'0', b'1', b'2', 0]]);
+        let mut printer = Printer::new(driver.clone(), Protocol::default(), None);
+        printer
+            .init()
+            .unwrap()
+            .wait_for_process(ProcessId::new(12).unwrap())
+            .unwrap();
+
+        assert_eq!(
+            *driver.written.lock().unwrap(),
+            vec![27, 64, 29, 40, 72, 6, 0, 48, 48, 48, 48, 49, 50]
+        );
+
+        let driver = ScriptedDriver::new(&[&[0x37, 0x22, b'0', b'0', b'1', b'3', 0]]);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        let result = printer.wait_for_process(ProcessId::new(12).unwrap());
+        assert!(matches!(result, Err(PrinterError::InvalidResponse(message)) if message.contains("0013")));
+    }
+
+    #[test]
+    fn test_asb_listener() {
+        let driver = ScriptedDriver::new(&[&[0b0001_0100, 0, 0], &[0, 0b0001_0010, b'_', b'A'], &[0, 0b0001_1100]]);
+        let mut listener = AsbListener::new(driver.clone());
+
+        let response = listener.poll().unwrap();
+        assert!(matches!(response, Some(PrinterResponse::AutomaticStatus(status)) if status.online));
+        assert_eq!(
+            listener.poll().unwrap(),
+            Some(PrinterResponse::RealTimeStatus(0b0001_0010))
+        );
+        assert_eq!(
+            listener.poll().unwrap(),
+            Some(PrinterResponse::Block(vec![b'_', b'A', 0]))
+        );
+        assert_eq!(listener.poll().unwrap(), None);
+
+        let mut responses = vec![];
+        driver
+            .reads
+            .lock()
+            .unwrap()
+            .push_back(Ok(vec![0, 0, 0, 0b0001_0000, 0, 0, 0]));
+        listener
+            .listen(|response| {
+                responses.push(response);
+                responses.len() < 2
+            })
+            .unwrap();
+        assert!(matches!(responses[0], PrinterResponse::AutomaticStatus(status) if !status.online));
+        assert!(matches!(responses[1], PrinterResponse::AutomaticStatus(status) if status.online));
+
+        driver
+            .reads
+            .lock()
+            .unwrap()
+            .push_back(Ok(vec![0b0001_0000, 0xFF, 0, 0]));
+        assert!(listener.poll().is_err());
+    }
+
+    #[test]
+    fn test_drivers_send() {
+        fn assert_send<D: Driver + Send + 'static>() {}
+
+        assert_send::<ConsoleDriver>();
+        assert_send::<NetworkDriver>();
+        assert_send::<FileDriver>();
+        #[cfg(feature = "usb")]
+        assert_send::<UsbDriver>();
+        #[cfg(feature = "native_usb")]
+        assert_send::<NativeUsbDriver>();
+        #[cfg(feature = "hidapi")]
+        assert_send::<HidApiDriver>();
+        #[cfg(feature = "serial_port")]
+        assert_send::<SerialPortDriver>();
+    }
+
+    #[test]
+    fn test_asb_listener_spawn_network_driver() {
+        let server = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = server.local_addr().unwrap().port();
+        let driver = NetworkDriver::open("127.0.0.1", port, Some(Duration::from_millis(50))).unwrap();
+        let (mut connection, _) = server.accept().unwrap();
+
+        let receiver = AsbListener::new(driver.clone()).spawn();
+        // Writes are not blocked by the pending read of the listener
+        Printer::new(driver, Protocol::default(), None)
+            .automatic_status_back(AsbEvents::all())
+            .unwrap()
+            .print()
+            .unwrap();
+        let mut written = [0; 3];
+        connection.read_exact(&mut written).unwrap();
+        assert_eq!(written, [29, 97, 79]);
+
+        connection.write_all(&[0b0001_0100, 0, 0, 0]).unwrap();
+        let response = receiver.recv_timeout(Duration::from_secs(5)).unwrap().unwrap();
+        assert!(matches!(response, PrinterResponse::AutomaticStatus(status) if status.online));
+    }
+
+    #[test]
+    fn test_asb_listener_spawn() {
+        let driver = ScriptedDriver::new(&[&[0b0001_0100, 0, 0, 0], &[0b0001_0000, 0b1000_0000, 0, 0]]);
+        let receiver = AsbListener::new(driver).spawn();
+
+        let response = receiver.recv_timeout(Duration::from_secs(5)).unwrap().unwrap();
+        assert!(matches!(response, PrinterResponse::AutomaticStatus(status) if status.online));
+        assert!(receiver.recv_timeout(Duration::from_secs(5)).unwrap().is_err());
+        assert!(receiver.recv_timeout(Duration::from_secs(5)).is_err());
+    }
 }
@@ -1,6 +1,13 @@
 //! Printer options
 
+#[cfg(feature = "graphics")]
+use crate::domain::BitImageMode;
+#[cfg(feature = "codes_2d_image")]
+use crate::domain::Code2DRendering;
 use crate::domain::{DebugMode, PageCode};
+#[cfg(feature = "barcodes")]
+use std::ops::RangeInclusive;
+use std::time::Duration;
 
 /// Printer options
 #[derive(Debug, Clone)]
@@ -13,6 +20,24 @@ pub struct PrinterOptions {
 
     /// Number of characters per line (default: 42)
     characters_per_line: u8,
+
+    /// Paper width in dots (default: 512)
+    dots_per_line: u16,
+
+    /// [Bit image mode](BitImageMode) used when the image option does not set one
+    #[cfg(feature = "graphics")]
+    bit_image_mode: BitImageMode,
+
+    /// Barcode module widths in dots supported by the printer (default: 1 - 6)
+    #[cfg(feature = "barcodes")]
+    barcode_module_widths: RangeInclusive<u8>,
+
+    /// [2D code rendering](Code2DRendering) of QR codes, DataMatrix and Aztec codes (default: commands)
+    #[cfg(feature = "codes_2d_image")]
+    code_2d_rendering: Code2DRendering,
+
+    /// Maximum time to wait for real-time status responses (default: 5 seconds)
+    status_timeout: Duration,
 }
 
 impl Default for PrinterOptions {
@@ -27,12 +52,21 @@ impl Default for PrinterOptions {
     /// assert_eq!(options.get_page_code(), None);
     /// assert_eq!(options.get_debug_mode(), None);
     /// assert_eq!(options.get_characters_per_line(), 42);
+    /// assert_eq!(options.get_dots_per_line(), 512);
     /// ```
     fn default() -> Self {
         Self {
             page_code: None,
             debug_mode: None,
             characters_per_line: 42,
+            dots_per_line: 512,
+            #[cfg(feature = "graphics")]
+            bit_image_mode: BitImageMode::default(),
+            #[cfg(feature = "barcodes")]
+            barcode_module_widths: 1..=6,
+            #[cfg(feature = "codes_2d_image")]
+            code_2d_rendering: Code2DRendering::default(),
+            status_timeout: Duration::from_secs(5),
         }
     }
 }
@@ -55,6 +89,14 @@ impl PrinterOptions {
             page_code,
             characters_per_line,
             debug_mode,
+            dots_per_line: 512,
+            #[cfg(feature = "graphics")]
+            bit_image_mode: BitImageMode::default(),
+            #[cfg(feature = "barcodes")]
+            barcode_module_widths: 1..=6,
+            #[cfg(feature = "codes_2d_image")]
+            code_2d_rendering: Code2DRendering::default(),
+            status_timeout: Duration::from_secs(5),
         }
     }
 
@@ -97,6 +139,25 @@ impl PrinterOptions {
         self.characters_per_line = characters_per_line;
     }
 
+    /// Get the paper width in dots
+    pub fn get_dots_per_line(&self) -> u16 {
+        self.dots_per_line
+    }
+
+    /// Set the paper width in dots (384 for 58mm paper, 512 or 576 for 80mm paper)
+    ///
+    /// ```
+    /// use escpos::printer_options::PrinterOptions;
+    ///
+    /// let mut printer_options = PrinterOptions::default();
+    /// printer_options.dots_per_line(384);
+    ///
+    /// assert_eq!(printer_options.get_dots_per_line(), 384);
+    /// ```
+    pub fn dots_per_line(&mut self, dots_per_line: u16) {
+        self.dots_per_line = dots_per_line;
+    }
+
     /// Get the [debug mode](DebugMode)
     pub fn get_debug_mode(&self) -> Option<DebugMode> {
         self.debug_mode
@@ -116,4 +177,95 @@ impl PrinterOptions {
     pub fn debug_mode(&mut self, debug_mode: Option<DebugMode>) {
         self.debug_mode = debug_mode;
     }
+
+    #[cfg(feature = "graphics")]
+    /// Get the [bit image mode](BitImageMode)
+    pub fn get_bit_image_mode(&self) -> BitImageMode {
+        self.bit_image_mode
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Set the [bit image mode](BitImageMode)
+    ///
+    /// Use a column format for printers without raster image support.
+    ///
+    /// ```
+    /// use escpos::printer_options::PrinterOptions;
+    /// use escpos::utils::BitImageMode;
+    ///
+    /// let mut printer_options = PrinterOptions::default();
+    /// printer_options.bit_image_mode(BitImageMode::Column24DotDoubleDensity);
+    ///
+    /// assert_eq!(printer_options.get_bit_image_mode(), BitImageMode::Column24DotDoubleDensity);
+    /// ```
+    pub fn bit_image_mode(&mut self, bit_image_mode: BitImageMode) {
+        self.bit_image_mode = bit_image_mode;
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Get the barcode module widths in dots supported by the printer
+    pub fn get_barcode_module_widths(&self) -> RangeInclusive<u8> {
+        self.barcode_module_widths.clone()
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Set the barcode module widths in dots supported by the printer (2 - 6 for most printers, up to 68 for some)
+    ///
+    /// Barcodes with a module width outside this range are refused.
+    ///
+    /// ```
+    /// use escpos::printer_options::PrinterOptions;
+    ///
+    /// let mut printer_options = PrinterOptions::default();
+    /// printer_options.barcode_module_widths(2..=68);
+    ///
+    /// assert_eq!(printer_options.get_barcode_module_widths(), 2..=68);
+    /// ```
+    pub fn barcode_module_widths(&mut self, barcode_module_widths: RangeInclusive<u8>) {
+        self.barcode_module_widths = barcode_module_widths;
+    }
+
+    #[cfg(feature = "codes_2d_image")]
+    /// Get the [2D code rendering](Code2DRendering)
+    pub fn get_code_2d_rendering(&self) -> Code2DRendering {
+        self.code_2d_rendering
+    }
+
+    #[cfg(feature = "codes_2d_image")]
+    /// Set the [2D code rendering](Code2DRendering)
+    ///
+    /// Use images for printers ignoring `GS ( k` commands.
+    ///
+    /// ```
+    /// use escpos::printer_options::PrinterOptions;
+    /// use escpos::utils::Code2DRendering;
+    ///
+    /// let mut printer_options = PrinterOptions::default();
+    /// printer_options.code_2d_rendering(Code2DRendering::Image);
+    ///
+    /// assert_eq!(printer_options.get_code_2d_rendering(), Code2DRendering::Image);
+    /// ```
+    pub fn code_2d_rendering(&mut self, code_2d_rendering: Code2DRendering) {
+        self.code_2d_rendering = code_2d_rendering;
+    }
+
+    /// Get the maximum time to wait for real-time status responses
+    pub fn get_status_timeout(&self) -> Duration {
+        self.status_timeout
+    }
+
+    /// Set the maximum time to wait for real-time status responses
+    ///
+    /// ```
+    /// use escpos::printer_options::PrinterOptions;
+    /// use std::time::Duration;
+    ///
+    /// let mut printer_options = PrinterOptions::default();
+    /// printer_options.status_timeout(Duration::from_millis(500));
+    ///
+    /// assert_eq!(printer_options.get_status_timeout(), Duration::from_millis(500));
+    /// ```
+    pub fn status_timeout(&mut self, status_timeout: Duration) {
+        self.status_timeout = status_timeout;
+    }
 }